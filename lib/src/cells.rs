@@ -14,11 +14,24 @@ use serde::{Deserialize, Serialize};
 pub const DEAD: State = State(0);
 pub const ALIVE: State = State(1);
 
+/// The relative positions of the eight neighbors,
+/// in the same order as `LifeCell::nbhd`.
+pub(crate) const NBHD: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
 /// Possible states of a known cell.
 ///
 /// During the search, the state of a cell is represented by `Option<State>`,
 /// where `None` means that the state of the cell is unknown.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
 pub struct State(pub usize);
 
@@ -76,8 +89,9 @@ pub struct LifeCell<'a, R: Rule> {
     pub(crate) succ: Option<CellRef<'a, R>>,
     /// The eight cells in the neighborhood.
     pub(crate) nbhd: [Option<CellRef<'a, R>>; 8],
-    /// The cells in the same generation that must has the same state
-    /// with this cell because of the symmetry.
+    /// The cells that must has the same state with this cell,
+    /// in the same generation because of the symmetry,
+    /// or in other generations because the cell is in the stator.
     pub(crate) sym: Vec<CellRef<'a, R>>,
 
     /// Whether the cell is on the first row or column.
@@ -88,11 +102,11 @@ pub struct LifeCell<'a, R: Rule> {
 
 impl<'a, R: Rule> LifeCell<'a, R> {
     /// Generates a new cell with state `state`, such that its neighborhood
-    /// descriptor says that all neighboring cells also have the same state.
+    /// descriptor says that all neighboring cells also have the same state,
+    /// and the successor has state `succ_state`.
     ///
     /// `first_gen` and `first_col` are set to `false`.
-    pub(crate) fn new(coord: Coord, background: State, b0: bool) -> Self {
-        let succ_state = if b0 { !background } else { background };
+    pub(crate) fn new(coord: Coord, background: State, succ_state: Option<State>) -> Self {
         LifeCell {
             coord,
             background,
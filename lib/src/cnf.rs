@@ -0,0 +1,765 @@
+//! Encoding the search problem as a SAT problem in DIMACS CNF.
+
+use crate::{
+    cells::{CellRef, Coord, ALIVE, DEAD},
+    config::{Config, Objective},
+    error::Error,
+    rules::NtLife,
+    search::Reason,
+    traits::Search,
+    world::World,
+};
+use ca_rules::{ParseNtLife, ParseNtLifeGen};
+use std::{collections::HashMap, fmt::Write};
+
+/// The `b` and `s` data of a non-totalistic Life-like rule.
+struct Transitions {
+    b: Vec<u8>,
+    s: Vec<u8>,
+    gen: usize,
+}
+
+impl ParseNtLife for Transitions {
+    fn from_bs(b: Vec<u8>, s: Vec<u8>) -> Self {
+        Transitions { b, s, gen: 2 }
+    }
+}
+
+impl ParseNtLifeGen for Transitions {
+    fn from_bsg(b: Vec<u8>, s: Vec<u8>, gen: usize) -> Self {
+        Transitions { b, s, gen }
+    }
+}
+
+impl Transitions {
+    fn parse(rule_string: &str) -> Result<Self, Error> {
+        let rule = <Self as ParseNtLife>::parse_rule(rule_string)
+            .or_else(|_| <Self as ParseNtLifeGen>::parse_rule(rule_string))?;
+        if rule.gen > 2 {
+            return Err(Error::CnfError(String::from(
+                "Generations rules are not supported",
+            )));
+        }
+        Ok(rule)
+    }
+
+    /// The state of the successor for each configuration of the cell
+    /// and its neighbors.
+    ///
+    /// The index is `self << 8 | nbhd`, where `self` is the state of the cell,
+    /// and bit `i` of `nbhd` is the state of its `7 - i`-th neighbor.
+    fn table(&self) -> Vec<bool> {
+        (0..512)
+            .map(|index| {
+                let nbhd = (index & 0xff) as u8;
+                if index >> 8 == 0 {
+                    self.b.contains(&nbhd)
+                } else {
+                    self.s.contains(&nbhd)
+                }
+            })
+            .collect()
+    }
+
+    /// The prime implicants of the transition function and its negation.
+    ///
+    /// Each implicant is `(care, bits, succ)`: whenever the inputs agree
+    /// with `bits` on the bits in `care`, the successor is `succ`.
+    /// Encoding the transitions with prime implicants gives much fewer
+    /// and shorter clauses than listing all 512 configurations.
+    fn implicants(&self) -> Vec<(u16, u16, bool)> {
+        let table = self.table();
+        // Cubes are numbers in base 3, where digit `k` is the value of
+        // bit `k` of the inputs, or 2 if the bit is free.
+        let powers = (0..9).map(|k| 3usize.pow(k)).collect::<Vec<_>>();
+        let digit = |cube: usize, k: usize| cube / powers[k] % 3;
+        let mut values: Vec<Option<bool>> = Vec::with_capacity(powers[8] * 3);
+        for cube in 0..powers[8] * 3 {
+            let value = match (0..9).find(|&k| digit(cube, k) == 2) {
+                None => Some(table[(0..9).fold(0, |acc, k| acc | digit(cube, k) << k)]),
+                Some(k) => {
+                    let zero = values[cube - 2 * powers[k]];
+                    if zero == values[cube - powers[k]] {
+                        zero
+                    } else {
+                        None
+                    }
+                }
+            };
+            values.push(value);
+        }
+        let mut implicants = Vec::new();
+        for (cube, &value) in values.iter().enumerate() {
+            if let Some(succ) = value {
+                let prime = (0..9).all(|k| {
+                    let d = digit(cube, k);
+                    d == 2 || values[cube + (2 - d) * powers[k]] != value
+                });
+                if prime {
+                    let (care, bits) =
+                        (0..9).fold((0, 0), |(care, bits), k| match digit(cube, k) {
+                            2 => (care, bits),
+                            d => (care | 1 << k, bits | (d as u16) << k),
+                        });
+                    implicants.push((care, bits, succ));
+                }
+            }
+        }
+        implicants
+    }
+}
+
+/// Whether a rule string is Conway's Game of Life, in any notation.
+pub(crate) fn is_life(rule_string: &str) -> bool {
+    let sorted = |transitions: Transitions| {
+        let (mut b, mut s) = (transitions.b, transitions.s);
+        b.sort_unstable();
+        s.sort_unstable();
+        (b, s)
+    };
+    match (
+        Transitions::parse(rule_string),
+        Transitions::parse("B3/S23"),
+    ) {
+        (Ok(rule), Ok(life)) => sorted(rule) == sorted(life),
+        _ => false,
+    }
+}
+
+/// Parses a rule with two states as an `NtLife`.
+///
+/// Returns an error for Generations rules.
+pub(crate) fn two_state_rule(rule_string: &str) -> Result<NtLife, Error> {
+    let transitions = Transitions::parse(rule_string)?;
+    Ok(NtLife::new(transitions.b, transitions.s))
+}
+
+/// A literal in the encoding, which may be a constant.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Lit {
+    Const(bool),
+    Var(i32),
+}
+
+impl Lit {
+    fn neg(self) -> Self {
+        match self {
+            Lit::Const(b) => Lit::Const(!b),
+            Lit::Var(v) => Lit::Var(-v),
+        }
+    }
+}
+
+/// A search problem encoded as a SAT problem in conjunctive normal form.
+///
+/// Every unknown cell in the search range becomes a variable,
+/// which is true when the cell is alive. Cells which must be equal
+/// because of the symmetry share the variable of the first one.
+/// Cells whose states are already known, including the cells outside
+/// the search range, are constants and do not appear in the formula.
+/// Variables after the cell variables are auxiliary.
+///
+/// Only rules with two states are supported.
+///
+/// The [`dimacs`](#method.dimacs) output can be given to any SAT solver,
+/// and the model it prints can be read back with
+/// [`read_model`](#method.read_model).
+#[derive(Clone, Debug)]
+pub struct Cnf {
+    /// The configuration of the encoded problem.
+    config: Config,
+
+    /// The coordinates of the cell of each cell variable.
+    ///
+    /// Variable `i + 1` is the cell `cells[i]`.
+    cells: Vec<Coord>,
+
+    /// The literals of the unknown cells,
+    /// including those which share a variable with another cell.
+    lits: HashMap<Coord, Lit>,
+
+    /// Total number of variables, including the auxiliary ones.
+    var_count: usize,
+
+    /// The clauses.
+    clauses: Vec<Vec<i32>>,
+}
+
+/// Builds the clauses.
+struct Encoder {
+    /// The coordinates of the cell of each cell variable.
+    cells: Vec<Coord>,
+
+    /// The literals of the unknown cells.
+    lits: HashMap<Coord, Lit>,
+    var_count: i32,
+    clauses: Vec<Vec<i32>>,
+}
+
+impl Encoder {
+    /// Creates an encoder with a literal for each unknown cell in the world,
+    /// and no clauses.
+    fn new(world: &World<NtLife>) -> Self {
+        let config = &world.config;
+        // Cells that are equal by the symmetry share a literal.
+        // So do the cells of the still life in a catalyst search,
+        // which always equal some cells in generation 0.
+        let mut cells = Vec::new();
+        let mut lits = HashMap::new();
+        let mut assign = |cell: CellRef<NtLife>| {
+            if cell.state.get().is_none() {
+                let lit = cell
+                    .sym
+                    .iter()
+                    .find_map(|sym| match sym.state.get() {
+                        Some(state) => Some(Lit::Const(state == ALIVE)),
+                        None => lits.get(&sym.coord).copied(),
+                    })
+                    .unwrap_or_else(|| {
+                        cells.push(cell.coord);
+                        Lit::Var(cells.len() as i32)
+                    });
+                lits.insert(cell.coord, lit);
+            }
+        };
+        for x in 0..config.width {
+            for y in 0..config.height {
+                for t in 0..config.period {
+                    assign(world.find_cell((x, y, t)).unwrap());
+                }
+            }
+        }
+        world.still_cells().for_each(assign);
+        Encoder {
+            var_count: cells.len() as i32,
+            cells,
+            lits,
+            clauses: Vec::new(),
+        }
+    }
+
+    fn lit(&self, cell: CellRef<NtLife>) -> Lit {
+        match cell.state.get() {
+            Some(state) => Lit::Const(state == ALIVE),
+            None => self.lits[&cell.coord],
+        }
+    }
+
+    fn new_var(&mut self) -> i32 {
+        self.var_count += 1;
+        self.var_count
+    }
+
+    /// Adds a clause. Constant literals are simplified away.
+    fn add(&mut self, lits: &[Lit]) {
+        let mut clause = Vec::with_capacity(lits.len());
+        for &lit in lits {
+            match lit {
+                Lit::Const(true) => return,
+                Lit::Const(false) => (),
+                Lit::Var(v) => clause.push(v),
+            }
+        }
+        self.clauses.push(clause);
+    }
+
+    /// Adds clauses saying that two literals are equal.
+    fn equal(&mut self, a: Lit, b: Lit) {
+        if a == b {
+            return;
+        }
+        self.add(&[a.neg(), b]);
+        self.add(&[a, b.neg()]);
+    }
+
+    /// Adds clauses saying that the successor of the cell is determined
+    /// by the cell and its neighbors.
+    fn transition(&mut self, cell: CellRef<NtLife>, implicants: &[(u16, u16, bool)]) {
+        let succ = match cell.succ {
+            Some(succ) => self.lit(succ),
+            None => return,
+        };
+        let background = Lit::Const(cell.background == ALIVE);
+        // Input `k` is bit `k` of the index of the transition table.
+        let inputs = cell
+            .nbhd
+            .iter()
+            .rev()
+            .map(|neigh| neigh.map_or(background, |neigh| self.lit(neigh)))
+            .chain(std::iter::once(self.lit(cell)))
+            .collect::<Vec<_>>();
+        for &(care, bits, value) in implicants {
+            let mut clause = Vec::with_capacity(10);
+            for (k, &input) in inputs.iter().enumerate() {
+                if care & 1 << k != 0 {
+                    clause.push(if bits & 1 << k != 0 {
+                        input.neg()
+                    } else {
+                        input
+                    });
+                }
+            }
+            clause.push(if value { succ } else { succ.neg() });
+            self.add(&clause);
+        }
+    }
+
+    /// Creates a variable for each group of literals, which is true
+    /// if some literal in this group or an earlier group is true.
+    ///
+    /// Only this direction of the implication is encoded.
+    fn any_prefix<'b>(&mut self, groups: impl Iterator<Item = &'b Vec<Lit>>) -> Vec<Lit> {
+        let mut vars: Vec<Lit> = Vec::new();
+        for group in groups {
+            let var = Lit::Var(self.new_var());
+            for &lit in group {
+                self.add(&[lit.neg(), var]);
+            }
+            if let Some(&last) = vars.last() {
+                self.add(&[last.neg(), var]);
+            }
+            vars.push(var);
+        }
+        vars
+    }
+
+    /// Adds clauses saying that at most `k` of the literals are true
+    /// when `guard` is true, using a sequential counter.
+    fn at_most(&mut self, lits: &[Lit], k: isize, guard: Lit) {
+        let k = k - lits.iter().filter(|&&lit| lit == Lit::Const(true)).count() as isize;
+        let vars = lits
+            .iter()
+            .filter_map(|&lit| match lit {
+                Lit::Var(v) => Some(v),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        if k < 0 {
+            self.add(&[guard.neg()]);
+            return;
+        }
+        let k = k as usize;
+        if k >= vars.len() {
+            return;
+        }
+        if k == 0 {
+            for &v in &vars {
+                self.add(&[guard.neg(), Lit::Var(-v)]);
+            }
+            return;
+        }
+        // `counter[j]` is true if more than `j` of the variables so far are true.
+        let mut counter: Vec<i32> = Vec::new();
+        for (i, &v) in vars.iter().enumerate() {
+            let x = Lit::Var(v);
+            if i > 0 {
+                self.add(&[guard.neg(), x.neg(), Lit::Var(-counter[k - 1])]);
+            }
+            if i == vars.len() - 1 {
+                break;
+            }
+            let new_counter = (0..k).map(|_| self.new_var()).collect::<Vec<_>>();
+            self.add(&[x.neg(), Lit::Var(new_counter[0])]);
+            for j in 0..k {
+                if i > 0 {
+                    self.add(&[Lit::Var(-counter[j]), Lit::Var(new_counter[j])]);
+                    if j > 0 {
+                        self.add(&[x.neg(), Lit::Var(-counter[j - 1]), Lit::Var(new_counter[j])]);
+                    }
+                }
+            }
+            counter = new_counter;
+        }
+    }
+
+    /// The literals of the cells in the search range in generation `t`.
+    fn gen_lits(&self, world: &World<NtLife>, t: isize) -> Vec<Lit> {
+        let config = &world.config;
+        (0..config.width)
+            .flat_map(|x| (0..config.height).map(move |y| (x, y)))
+            .map(|(x, y)| self.lit(world.find_cell((x, y, t)).unwrap()))
+            .collect()
+    }
+
+    /// Adds clauses for the max cell count and the bound of the objective
+    /// in the configuration of the world.
+    fn bounds(&mut self, world: &World<NtLife>) {
+        let config = &world.config;
+        // Some generation has at most `max_cell_count` living cells.
+        if let Some(max) = config.max_cell_count {
+            let guards = (0..config.period)
+                .map(|_| Lit::Var(self.new_var()))
+                .collect::<Vec<_>>();
+            self.add(&guards);
+            for (t, &guard) in guards.iter().enumerate() {
+                let lits = self.gen_lits(world, t as isize);
+                self.at_most(&lits, max as isize, guard);
+            }
+        }
+
+        // The objective meets its bound.
+        if let Some(bound) = config.bound {
+            // Whether a cell differs from the background.
+            let differ = |encoder: &Encoder, x, y, t| {
+                let cell = world.find_cell((x, y, t)).unwrap();
+                let lit = encoder.lit(cell);
+                if cell.background == ALIVE {
+                    lit.neg()
+                } else {
+                    lit
+                }
+            };
+            match config.objective {
+                Objective::MinCellCount => (),
+                Objective::MinCellCountGen(t) => {
+                    let lits = self.gen_lits(world, t.rem_euclid(config.period));
+                    self.at_most(&lits, bound as isize, Lit::Const(true));
+                }
+                Objective::MinBoundingBox => {
+                    let columns = (0..config.width)
+                        .map(|x| {
+                            (0..config.height)
+                                .flat_map(|y| (0..config.period).map(move |t| (y, t)))
+                                .map(|(y, t)| differ(self, x, y, t))
+                                .collect::<Vec<_>>()
+                        })
+                        .collect::<Vec<_>>();
+                    let rows = (0..config.height)
+                        .map(|y| {
+                            (0..config.width)
+                                .flat_map(|x| (0..config.period).map(move |t| (x, t)))
+                                .map(|(x, t)| differ(self, x, y, t))
+                                .collect::<Vec<_>>()
+                        })
+                        .collect::<Vec<_>>();
+                    // `left[i]` is true if some cell in the columns `0..=i`
+                    // differs from the background, and `right[i]` if some cell
+                    // in the columns `i..width` does. Similarly for the rows.
+                    let left = self.any_prefix(columns.iter());
+                    let mut right = self.any_prefix(columns.iter().rev());
+                    right.reverse();
+                    let top = self.any_prefix(rows.iter());
+                    let mut bottom = self.any_prefix(rows.iter().rev());
+                    bottom.reverse();
+                    // Forbids the boxes from `(i, k)` to `(j, l)` whose areas exceed the bound,
+                    // taking the smallest such `l` for each `i`, `j` and `k`.
+                    for (i, &left) in left.iter().enumerate() {
+                        for (j, &right) in right.iter().enumerate().skip(i) {
+                            let height = bound / (j - i + 1) + 1;
+                            for (k, &top) in top.iter().enumerate() {
+                                if let Some(&bottom) = bottom.get(k + height - 1) {
+                                    self.add(&[left.neg(), right.neg(), top.neg(), bottom.neg()]);
+                                }
+                            }
+                        }
+                    }
+                }
+                Objective::MinRotor | Objective::MaxRotor => {
+                    // `rotor` is true if the position is in the rotor,
+                    // or only implied by it when maximizing.
+                    let mut rotors = Vec::new();
+                    for x in 0..config.width {
+                        for y in 0..config.height {
+                            let states = (0..config.period)
+                                .map(|t| differ(self, x, y, t))
+                                .collect::<Vec<_>>();
+                            let rotor = Lit::Var(self.new_var());
+                            if config.objective.is_max() {
+                                let mut some_differ = vec![rotor.neg()];
+                                let mut some_equal = vec![rotor.neg()];
+                                for &state in &states {
+                                    some_differ.push(state);
+                                    some_equal.push(state.neg());
+                                }
+                                self.add(&some_differ);
+                                self.add(&some_equal);
+                                rotors.push(rotor.neg());
+                            } else {
+                                for &state in &states[1..] {
+                                    self.add(&[states[0], state.neg(), rotor]);
+                                    self.add(&[states[0].neg(), state, rotor]);
+                                }
+                                rotors.push(rotor);
+                            }
+                        }
+                    }
+                    let max = if config.objective.is_max() {
+                        config.width * config.height - bound as isize
+                    } else {
+                        bound as isize
+                    };
+                    self.at_most(&rotors, max, Lit::Const(true));
+                }
+            }
+        }
+    }
+}
+
+impl Config {
+    /// Encodes the search problem as a SAT problem.
+    ///
+    /// The encoding includes the size, the period, the translation,
+    /// the transformation, the symmetry, the known cells, the rule,
+    /// the maximal number of living cells, the bound of the objective,
+    /// and whether the front is nonempty.
+    /// A satisfying assignment is a nonempty pattern whose period is
+    /// exactly the given period, i.e., the kind of result the search
+    /// would report. If the configuration is not periodic, the period
+    /// and the front are not encoded.
+    ///
+    /// The search order and the choice of the new state do not matter here.
+    ///
+    /// Returns an error for Generations rules.
+    pub fn cnf(&self) -> Result<Cnf, Error> {
+        let transitions = Transitions::parse(&self.rule_string)?;
+        let implicants = transitions.implicants();
+        let rule = NtLife::new(transitions.b, transitions.s);
+        let world = World::new(self, rule).init_known_cells()?;
+
+        let mut encoder = Encoder::new(&world);
+        let in_range = |x, y| 0 <= x && x < self.width && 0 <= y && y < self.height;
+
+        // Transitions and symmetries.
+        let mut encode = |cell: CellRef<NtLife>| {
+            encoder.transition(cell, &implicants);
+            let (x, y, _) = cell.coord;
+            if in_range(x, y) {
+                let lit = encoder.lit(cell);
+                for &sym in cell.sym.iter() {
+                    let sym_lit = encoder.lit(sym);
+                    encoder.equal(lit, sym_lit);
+                }
+            }
+        };
+        for x in -1..=self.width {
+            for y in -1..=self.height {
+                for t in 0..self.period {
+                    encode(world.find_cell((x, y, t)).unwrap());
+                }
+            }
+        }
+        world.still_cells().for_each(encode);
+
+        // The pattern is nonempty.
+        let gen0 = encoder.gen_lits(&world, 0);
+        encoder.add(&gen0);
+
+        // The pattern does not have a smaller period.
+        for t in 1..self.period {
+            if !self.periodic || self.period % t != 0 {
+                continue;
+            }
+            let mut clause = Vec::new();
+            for x in -1..=self.width {
+                for y in -1..=self.height {
+                    let a = encoder.lit(world.find_cell((x, y, 0)).unwrap());
+                    let b = encoder.lit(world.find_cell((x, y, t)).unwrap());
+                    match (a, b) {
+                        (Lit::Const(a), Lit::Const(b)) => clause.push(Lit::Const(a != b)),
+                        (Lit::Const(a), lit) | (lit, Lit::Const(a)) => {
+                            clause.push(if a { lit.neg() } else { lit })
+                        }
+                        (a, b) => {
+                            let differ = Lit::Var(encoder.new_var());
+                            encoder.add(&[differ.neg(), a, b]);
+                            encoder.add(&[differ.neg(), a.neg(), b.neg()]);
+                            clause.push(differ);
+                        }
+                    }
+                }
+            }
+            encoder.add(&clause);
+        }
+
+        // The front is nonempty.
+        if self.non_empty_front && self.periodic {
+            let mut clause = Vec::new();
+            for x in 0..self.width {
+                for y in 0..self.height {
+                    for t in 0..self.period {
+                        let cell = world.find_cell((x, y, t)).unwrap();
+                        if cell.is_front {
+                            clause.push(encoder.lit(cell));
+                        }
+                    }
+                }
+            }
+            encoder.add(&clause);
+        }
+
+        encoder.bounds(&world);
+
+        Ok(Cnf {
+            config: self.clone(),
+            cells: encoder.cells,
+            lits: encoder.lits,
+            var_count: encoder.var_count as usize,
+            clauses: encoder.clauses,
+        })
+    }
+}
+
+impl Cnf {
+    /// Adds the clauses for the max cell count and the bound of the objective
+    /// in a stricter configuration, which differs from the encoded one
+    /// only in these two fields.
+    ///
+    /// The clauses of the old bounds are kept, and the new auxiliary variables
+    /// come after the existing ones, so that a solver of the old problem
+    /// can go on with the new clauses, without losing its learnt clauses.
+    ///
+    /// Returns the number of the old clauses.
+    pub(crate) fn tighten(&mut self, config: &Config) -> Result<usize, Error> {
+        let world = World::new(config, two_state_rule(&config.rule_string)?).init_known_cells()?;
+        let mut encoder = Encoder::new(&world);
+        encoder.var_count = self.var_count as i32;
+        encoder.bounds(&world);
+        let old_count = self.clauses.len();
+        self.config = config.clone();
+        self.var_count = encoder.var_count as usize;
+        self.clauses.append(&mut encoder.clauses);
+        Ok(old_count)
+    }
+
+    /// The configuration of the encoded problem.
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// The coordinates of the cells represented by the cell variables.
+    ///
+    /// Variable `i + 1` is the cell `cell_vars()[i]`,
+    /// and the cells equal to it by the symmetry.
+    /// The coordinates are those in the world, so generations other than 0
+    /// are not affected by the translation and the transformation.
+    pub fn cell_vars(&self) -> &[Coord] {
+        &self.cells
+    }
+
+    /// The literal of a cell, or `None` if its state is already known
+    /// before the search.
+    pub(crate) fn cell_lit(&self, coord: Coord) -> Option<Lit> {
+        self.lits.get(&coord).copied()
+    }
+
+    /// Total number of variables, including the auxiliary ones.
+    pub fn var_count(&self) -> usize {
+        self.var_count
+    }
+
+    /// The clauses, in the same format as DIMACS CNF
+    /// without the trailing `0`.
+    pub fn clauses(&self) -> &[Vec<i32>] {
+        &self.clauses
+    }
+
+    /// The variable map as text.
+    ///
+    /// Each line is of the form `var x y t`.
+    pub fn var_map(&self) -> String {
+        let mut map = String::new();
+        for (i, (x, y, t)) in self.cells.iter().enumerate() {
+            writeln!(map, "{} {} {} {}", i + 1, x, y, t).unwrap();
+        }
+        map
+    }
+
+    /// The problem in DIMACS CNF format.
+    ///
+    /// The variable map is included as comment lines of the form
+    /// `c var x y t`.
+    pub fn dimacs(&self) -> String {
+        let mut dimacs = String::new();
+        writeln!(
+            dimacs,
+            "c rlifesrc {} × {} × {}, translation ({}, {}), rule {}",
+            self.config.width,
+            self.config.height,
+            self.config.period,
+            self.config.dx,
+            self.config.dy,
+            self.config.rule_string
+        )
+        .unwrap();
+        for line in self.var_map().lines() {
+            writeln!(dimacs, "c {}", line).unwrap();
+        }
+        writeln!(dimacs, "p cnf {} {}", self.var_count, self.clauses.len()).unwrap();
+        for clause in self.clauses.iter() {
+            for lit in clause {
+                write!(dimacs, "{} ", lit).unwrap();
+            }
+            dimacs.push_str("0\n");
+        }
+        dimacs
+    }
+
+    /// Reads a model printed by a SAT solver, and returns the world
+    /// where all the cells are known.
+    ///
+    /// Both the format of the SAT competitions (`s SATISFIABLE` followed by
+    /// `v` lines) and that of MiniSat (`SAT` followed by a list of literals)
+    /// are accepted. Auxiliary variables are ignored.
+    ///
+    /// The model is checked against the rule and the other constraints.
+    /// As there are no unknown cells left, calling `search` on the returned
+    /// world would return `Status::None`, just like after the last result.
+    pub fn read_model(&self, model: &str) -> Result<Box<dyn Search>, Error> {
+        let mut values = HashMap::new();
+        for line in model.lines().map(str::trim) {
+            if line.contains("UNSAT") {
+                return Err(Error::CnfError(String::from(
+                    "the problem is unsatisfiable",
+                )));
+            }
+            let literals = match line.split_whitespace().next() {
+                None | Some("c") | Some("s") | Some("SAT") => continue,
+                Some("v") => &line[1..],
+                _ => line,
+            };
+            for literal in literals.split_whitespace() {
+                let literal = literal
+                    .parse::<i32>()
+                    .map_err(|_| Error::CnfError(format!("invalid literal {}", literal)))?;
+                if literal != 0 {
+                    values.insert(literal.abs(), literal > 0);
+                }
+            }
+        }
+        let world = self.world_from_model(|var| values.get(&(var as i32)).copied())?;
+        Ok(Box::new(world))
+    }
+
+    /// Creates a world where the cells take the values of the cell variables
+    /// in a model, and checks that it is a solution.
+    ///
+    /// `value` gives the value of each variable, starting from 1.
+    pub(crate) fn world_from_model<F>(&self, value: F) -> Result<World<'static, NtLife>, Error>
+    where
+        F: Fn(usize) -> Option<bool>,
+    {
+        let config = &self.config;
+        let mut world =
+            World::new(config, two_state_rule(&config.rule_string)?).init_known_cells()?;
+        for (i, &coord) in self.cells.iter().enumerate() {
+            let state = match value(i + 1) {
+                Some(true) => ALIVE,
+                Some(false) => DEAD,
+                None => return Err(Error::CnfError(format!("variable {} is missing", i + 1))),
+            };
+            let cell = world.find_cell(coord).unwrap();
+            if cell.state.get().is_none() {
+                world.set_cell(cell, state, Reason::Deduce);
+            }
+        }
+        if !world.proceed()
+            || !world.nontrivial()
+            || matches!(config.max_cell_count, Some(max) if world.cell_count() > max)
+            || !world.within_bound()
+            || (config.non_empty_front && config.periodic && world.front_cell_count == 0)
+        {
+            return Err(Error::CnfError(String::from("the model is not a solution")));
+        }
+        Ok(world)
+    }
+}
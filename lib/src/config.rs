@@ -1,15 +1,20 @@
 //! World configuration.
 
 use crate::{
-    cells::Coord,
+    cells::{Coord, State, DEAD},
+    cnf::{is_life, two_state_rule},
     error::Error,
+    pattern::Pattern,
     rules::{Life, LifeGen, NtLife, NtLifeGen, Rule},
+    sat::{solver::luby, SatWorld},
     traits::Search,
     world::World,
 };
 use derivative::Derivative;
 use std::{
     cmp::Ordering,
+    collections::HashSet,
+    convert::TryFrom,
     fmt::{self, Debug, Formatter},
     str::FromStr,
 };
@@ -35,9 +40,13 @@ use serde::{Deserialize, Serialize};
 /// The symbol after it is the axis of reflection.
 ///
 /// Some of the transformations are only valid when the world is square.
+///
+/// It is serialized as a string in the same format as `FromStr`,
+/// e.g., `"F|"`.
 #[derive(Clone, Copy, Derivative, PartialEq, Eq)]
 #[derivative(Default)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialize", serde(into = "String", try_from = "String"))]
 pub enum Transform {
     /// `Id`.
     ///
@@ -92,7 +101,7 @@ impl FromStr for Transform {
     }
 }
 
-impl Debug for Transform {
+impl fmt::Display for Transform {
     fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
         let s = match self {
             Transform::Id => "Id",
@@ -109,6 +118,37 @@ impl Debug for Transform {
     }
 }
 
+impl From<Transform> for String {
+    fn from(transform: Transform) -> Self {
+        transform.to_string()
+    }
+}
+
+/// Also accepts the names of the variants, e.g., `"FlipCol"`,
+/// which are used in the files of older versions.
+impl TryFrom<String> for Transform {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse().or_else(|e| match s.as_str() {
+            "Rotate90" => Ok(Transform::Rotate90),
+            "Rotate180" => Ok(Transform::Rotate180),
+            "Rotate270" => Ok(Transform::Rotate270),
+            "FlipRow" => Ok(Transform::FlipRow),
+            "FlipCol" => Ok(Transform::FlipCol),
+            "FlipDiag" => Ok(Transform::FlipDiag),
+            "FlipAntidiag" => Ok(Transform::FlipAntidiag),
+            _ => Err(e),
+        })
+    }
+}
+
+impl Debug for Transform {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        write!(f, "{}", self)
+    }
+}
+
 impl Transform {
     /// Whether the transformation requires the world to be square.
     ///
@@ -134,9 +174,13 @@ impl Transform {
 /// Please see the [Life Wiki](https://conwaylife.com/wiki/Symmetry) for details.
 ///
 /// Some of the symmetries are only valid when the world is square.
+///
+/// It is serialized as a string in the same format as `FromStr`,
+/// e.g., `"D2|"`.
 #[derive(Clone, Copy, Derivative, PartialEq, Eq)]
 #[derivative(Default)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialize", serde(into = "String", try_from = "String"))]
 pub enum Symmetry {
     /// `C1`.
     ///
@@ -203,7 +247,7 @@ impl FromStr for Symmetry {
     }
 }
 
-impl Debug for Symmetry {
+impl fmt::Display for Symmetry {
     fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
         let s = match self {
             Symmetry::C1 => "C1",
@@ -222,6 +266,36 @@ impl Debug for Symmetry {
     }
 }
 
+impl From<Symmetry> for String {
+    fn from(symmetry: Symmetry) -> Self {
+        symmetry.to_string()
+    }
+}
+
+/// Also accepts the names of the variants, e.g., `"D2Col"`,
+/// which are used in the files of older versions.
+impl TryFrom<String> for Symmetry {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse().or_else(|e| match s.as_str() {
+            "D2Row" => Ok(Symmetry::D2Row),
+            "D2Col" => Ok(Symmetry::D2Col),
+            "D2Diag" => Ok(Symmetry::D2Diag),
+            "D2Antidiag" => Ok(Symmetry::D2Antidiag),
+            "D4Ortho" => Ok(Symmetry::D4Ortho),
+            "D4Diag" => Ok(Symmetry::D4Diag),
+            _ => Err(e),
+        })
+    }
+}
+
+impl Debug for Symmetry {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        write!(f, "{}", self)
+    }
+}
+
 impl Symmetry {
     /// Whether the transformation requires the world to be square.
     ///
@@ -286,7 +360,8 @@ pub enum NewState {
     ChooseAlive,
     /// Random.
     ///
-    /// For life-like rules, the probability of either state is 1/2.
+    /// For life-like rules, the probability of either state is 1/2,
+    /// unless `density` is set in the configuration.
     Random,
 }
 
@@ -296,12 +371,193 @@ impl Default for NewState {
     }
 }
 
+/// The engine that solves the search problem.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum Engine {
+    /// The backtracking algorithm invented by Dean Hickerson,
+    /// as in lifesrc and WinLifeSearch.
+    #[default]
+    Backtrack,
+    /// An embedded SAT solver.
+    ///
+    /// The problem is encoded as in [`Config::cnf`](struct.Config.html#method.cnf),
+    /// so Generations rules are not supported.
+    /// The search order and the choice of the new state only
+    /// affect the first decisions of the solver.
+    Sat,
+}
+
+/// The objective to optimize when `reduce_max` is set.
+///
+/// Each objective is a number computed from a result. The search keeps
+/// a bound of it, and rejects a partial result as soon as the known cells
+/// show that the bound cannot be met.
+///
+/// The cells of the still life in a catalyst search are not counted.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum Objective {
+    /// Minimizes the minimum number of living cells in all generations.
+    ///
+    /// Its bound is `max_cell_count`.
+    #[default]
+    MinCellCount,
+    /// Minimizes the number of living cells in a generation.
+    ///
+    /// The generation is taken modulo the period.
+    MinCellCountGen(isize),
+    /// Minimizes the area of the bounding box of the cells
+    /// that differ from the background in any generation.
+    MinBoundingBox,
+    /// Minimizes the size of the rotor, i.e., the number of positions
+    /// whose states are not the same in all generations.
+    MinRotor,
+    /// Maximizes the size of the rotor.
+    MaxRotor,
+}
+
+impl FromStr for Objective {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "cells" => Ok(Objective::MinCellCount),
+            "box" => Ok(Objective::MinBoundingBox),
+            "rotor" => Ok(Objective::MinRotor),
+            "max-rotor" => Ok(Objective::MaxRotor),
+            _ => s
+                .strip_prefix("cells:")
+                .and_then(|t| t.parse().ok())
+                .map(Objective::MinCellCountGen)
+                .ok_or_else(|| String::from("invalid objective")),
+        }
+    }
+}
+
+impl Objective {
+    /// Whether the objective is to be maximized.
+    pub fn is_max(self) -> bool {
+        self == Objective::MaxRotor
+    }
+
+    /// Whether no result can be better than a result with this value.
+    pub(crate) fn is_optimum(self, value: usize) -> bool {
+        !self.is_max() && value == 0
+    }
+}
+
+/// When to restart the search.
+///
+/// A restart discards all decisions and starts the search again,
+/// keeping only the cells that are known before the first decision.
+/// It is useful with `NewState::Random`, where each restart
+/// explores a different part of the search tree.
+///
+/// The search no longer restarts once a result is found,
+/// so that the results are not found again.
+///
+/// The SAT engine ignores it, since the solver restarts by itself.
+///
+/// It is serialized as a string in the same format as `FromStr`,
+/// e.g., `"luby:100"`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialize", serde(into = "String", try_from = "String"))]
+pub enum Restart {
+    /// Never restarts.
+    #[default]
+    Never,
+    /// Restarts after every given number of conflicts.
+    ///
+    /// The search might never end if there is no result.
+    Fixed(u64),
+    /// Restarts after numbers of conflicts given by the Luby sequence
+    /// 1, 1, 2, 1, 1, 2, 4, 1, 1, 2, ..., multiplied by the given number.
+    ///
+    /// The intervals between restarts grow without bound,
+    /// so the search still ends if there is no result.
+    Luby(u64),
+}
+
+impl FromStr for Restart {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "never" {
+            return Ok(Restart::Never);
+        }
+        let parse = |n: &str| n.parse().ok().filter(|&n| n > 0);
+        if let Some(n) = s.strip_prefix("fixed:").and_then(parse) {
+            Ok(Restart::Fixed(n))
+        } else if let Some(n) = s.strip_prefix("luby:").and_then(parse) {
+            Ok(Restart::Luby(n))
+        } else {
+            Err(String::from("invalid restart policy"))
+        }
+    }
+}
+
+impl fmt::Display for Restart {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Restart::Never => write!(f, "never"),
+            Restart::Fixed(n) => write!(f, "fixed:{}", n),
+            Restart::Luby(n) => write!(f, "luby:{}", n),
+        }
+    }
+}
+
+impl From<Restart> for String {
+    fn from(restart: Restart) -> Self {
+        restart.to_string()
+    }
+}
+
+impl TryFrom<String> for Restart {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl Restart {
+    /// The number of conflicts before the next restart,
+    /// after the given number of restarts.
+    ///
+    /// Returns `None` if it never restarts.
+    pub(crate) fn interval(self, restarts: u64) -> Option<u64> {
+        match self {
+            Restart::Never | Restart::Fixed(0) | Restart::Luby(0) => None,
+            Restart::Fixed(n) => Some(n),
+            Restart::Luby(n) => Some(n.saturating_mul(luby(restarts))),
+        }
+    }
+}
+
+/// A cell whose state is known before the search.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct KnownCell {
+    /// The coordinates of the cell.
+    pub coord: Coord,
+
+    /// The state of the cell.
+    pub state: State,
+}
+
 /// World configuration.
 ///
 /// The world will be generated from this configuration.
+///
+/// When deserialized, missing fields take their default values,
+/// so that a configuration file only needs to contain the fields
+/// that differ from the default.
 #[derive(Clone, Debug, Derivative, PartialEq, Eq)]
 #[derivative(Default)]
 #[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serialize", serde(default))]
 pub struct Config {
     /// Width.
     #[derivative(Default(value = "16"))]
@@ -331,6 +587,55 @@ pub struct Config {
     /// Symmetries of the pattern.
     pub symmetry: Symmetry,
 
+    /// Whether the pattern returns to the first generation
+    /// after the last generation.
+    ///
+    /// If it is `false`, the first generation has no predecessor,
+    /// and the last generation has no successor. Such a search finds
+    /// the evolutions of `period` generations, e.g., the ancestors of
+    /// a pattern given as the known cells of the last generation.
+    /// The translation, the transformation and the front are ignored.
+    #[derivative(Default(value = "true"))]
+    pub periodic: bool,
+
+    /// Whether the cells that are not known in any generation
+    /// must be stable, i.e., have the same state in all generations.
+    ///
+    /// The known cells are the rotor, and the other cells form the stator.
+    /// Cells that are equal to some rotor cell by the symmetry
+    /// are also in the rotor.
+    pub stator: bool,
+
+    /// Whether to search for catalysts.
+    ///
+    /// The known cells in generation 0 are the active region, which evolves
+    /// freely. All other cells form the catalyst: in generation 0, they must
+    /// be a still life by themselves, and in the last generation, they must
+    /// return to their states in generation 0. It should be used with a
+    /// non-periodic configuration.
+    pub catalyst: bool,
+
+    /// Whether the world grows along the search order.
+    ///
+    /// With the `RowFirst` search order, a row is added to the bottom of
+    /// the world whenever the last row becomes nonempty, and removed again
+    /// when the search backtracks beyond that time, so the `height`
+    /// is only the initial height. With `ColumnFirst`, a column is added
+    /// to the right instead. The pattern is closed when its last row is
+    /// empty, so that it is followed by a pair of empty rows, counting
+    /// the row outside the search range.
+    ///
+    /// The symmetry and the transformation must not involve the height
+    /// (or the width), the engine must be `Backtrack`,
+    /// and it must not be a catalyst search.
+    pub grow: bool,
+
+    /// The maximal height (or width) that the world can grow to.
+    ///
+    /// A pattern whose last row is still nonempty at this size
+    /// is a conflict. `None` means that there is no limit.
+    pub grow_limit: Option<isize>,
+
     /// The order to find a new unknown cell.
     ///
     /// It will always search all generations of a cell first,
@@ -343,6 +648,49 @@ pub struct Config {
     /// How to choose a state for an unknown cell.
     pub new_state: NewState,
 
+    /// The probability, in percent, of choosing `ALIVE`
+    /// when `new_state` is `Random`.
+    ///
+    /// The other states are chosen with equal probabilities.
+    /// Values above 100 are treated as 100.
+    ///
+    /// `None` means that all states are chosen with equal probabilities.
+    pub density: Option<u32>,
+
+    /// The seed of the random number generator used by `NewState::Random`.
+    ///
+    /// Searches with the same configuration and the same seed
+    /// make the same choices.
+    ///
+    /// `None` means that the seed is chosen randomly.
+    pub seed: Option<u64>,
+
+    /// When to restart the search.
+    pub restart: Restart,
+
+    /// Whether to record a proof, i.e., a trace of the leaves of the search tree.
+    ///
+    /// After the search ends with `Status::None`, the proof shows that
+    /// there are no other results than those found, and can be checked
+    /// independently by `check_proof`.
+    ///
+    /// The rule must have two states, the engine must be `Backtrack`,
+    /// and the search must not grow, restart, or tighten the bound
+    /// by `reduce_max`.
+    ///
+    /// It is not serialized, because the leaves are kept in memory
+    /// until they are taken by `Search::take_proof`, so a search
+    /// restored from a save would keep them forever.
+    #[cfg_attr(feature = "serialize", serde(skip))]
+    pub proof: bool,
+
+    /// Whether to record why each cell has its state.
+    ///
+    /// The deductions that lead to a cell can then be traced back
+    /// to the known cells and the decisions by `Search::explain`.
+    /// It makes the search slower.
+    pub explain: bool,
+
     /// The number of minimum living cells in all generations must not
     /// exceed this number.
     ///
@@ -356,16 +704,38 @@ pub struct Config {
     #[derivative(Default(value = "true"))]
     pub non_empty_front: bool,
 
-    /// Whether to automatically reduce the `max_cell_count`
+    /// Whether to automatically tighten the bound of the `objective`
     /// when a result is found.
     ///
-    /// The `max_cell_count` will be set to the cell count of
-    /// the current result minus one.
+    /// The bound will be set to the value of the objective in the current
+    /// result minus one, or plus one if it is to be maximized, so that
+    /// each result is better than the last one. When the search ends,
+    /// the last result is optimal.
     pub reduce_max: bool,
 
+    /// The objective to optimize.
+    pub objective: Objective,
+
+    /// The bound of the `objective`, unless it is `MinCellCount`.
+    ///
+    /// The value of the objective must not exceed this number,
+    /// or must be at least this number if it is to be maximized.
+    ///
+    /// `None` means that there is no bound.
+    pub bound: Option<usize>,
+
+    /// The engine that solves the search problem.
+    pub engine: Engine,
+
     /// The rule string of the cellular automaton.
     #[derivative(Default(value = "String::from(\"B3/S23\")"))]
     pub rule_string: String,
+
+    /// Cells whose states are known before the search.
+    ///
+    /// The coordinates are translated in the same way as `get_cell_state`.
+    /// Cells outside the search range must be in the background state.
+    pub known_cells: Vec<KnownCell>,
 }
 
 impl Config {
@@ -398,6 +768,28 @@ impl Config {
         self
     }
 
+    /// Sets whether the pattern returns to the first generation
+    /// after the last generation.
+    pub fn set_periodic(mut self, periodic: bool) -> Self {
+        self.periodic = periodic;
+        self
+    }
+
+    /// Sets whether the cells that are not known in any generation
+    /// must be stable.
+    pub fn set_stator(mut self, stator: bool) -> Self {
+        self.stator = stator;
+        self
+    }
+
+    /// Sets whether the world grows along the search order,
+    /// and the maximal height (or width) it can grow to.
+    pub fn set_grow(mut self, grow: bool, grow_limit: Option<isize>) -> Self {
+        self.grow = grow;
+        self.grow_limit = grow_limit;
+        self
+    }
+
     /// Sets the search order.
     pub fn set_search_order(mut self, search_order: Option<SearchOrder>) -> Self {
         self.search_order = search_order;
@@ -410,6 +802,37 @@ impl Config {
         self
     }
 
+    /// Sets the probability, in percent, of choosing `ALIVE`
+    /// when `new_state` is `Random`.
+    pub fn set_density(mut self, density: Option<u32>) -> Self {
+        self.density = density;
+        self
+    }
+
+    /// Sets the seed of the random number generator.
+    pub fn set_seed(mut self, seed: Option<u64>) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Sets when to restart the search.
+    pub fn set_restart(mut self, restart: Restart) -> Self {
+        self.restart = restart;
+        self
+    }
+
+    /// Sets whether to record a proof.
+    pub fn set_proof(mut self, proof: bool) -> Self {
+        self.proof = proof;
+        self
+    }
+
+    /// Sets whether to record why each cell has its state.
+    pub fn set_explain(mut self, explain: bool) -> Self {
+        self.explain = explain;
+        self
+    }
+
     /// Sets the maximal number of living cells.
     pub fn set_max_cell_count(mut self, max_cell_count: Option<usize>) -> Self {
         self.max_cell_count = max_cell_count;
@@ -422,19 +845,181 @@ impl Config {
         self
     }
 
-    /// Sets whether to automatically reduce the `max_cell_count`
+    /// Sets whether to automatically tighten the bound of the objective
     /// when a result is found.
     pub fn set_reduce_max(mut self, reduce_max: bool) -> Self {
         self.reduce_max = reduce_max;
         self
     }
 
+    /// Sets the objective to optimize.
+    pub fn set_objective(mut self, objective: Objective) -> Self {
+        self.objective = objective;
+        self
+    }
+
+    /// Sets the bound of the objective.
+    pub fn set_bound(mut self, bound: Option<usize>) -> Self {
+        self.bound = bound;
+        self
+    }
+
+    /// Sets the engine that solves the search problem.
+    pub fn set_engine(mut self, engine: Engine) -> Self {
+        self.engine = engine;
+        self
+    }
+
     /// Sets the rule string.
     pub fn set_rule_string(mut self, rule_string: String) -> Self {
         self.rule_string = rule_string;
         self
     }
 
+    /// Sets the known cells.
+    pub fn set_known_cells(mut self, known_cells: Vec<KnownCell>) -> Self {
+        self.known_cells = known_cells;
+        self
+    }
+
+    /// Adds the known cells of a pattern to the known cells,
+    /// placing its upper left corner at `(x, y)` in generation `t`.
+    ///
+    /// Unknown cells in the pattern are ignored.
+    pub fn add_pattern(mut self, pattern: &Pattern, x: isize, y: isize, t: isize) -> Self {
+        self.known_cells
+            .extend(pattern.known_cells().map(|((px, py), state)| KnownCell {
+                coord: (x + px, y + py, t),
+                state,
+            }));
+        self
+    }
+
+    /// Sets a pattern as the last generation, placing its upper left corner
+    /// at `(x, y)`, and makes the configuration non-periodic,
+    /// so that the search finds its predecessors.
+    ///
+    /// Cells in the last generation outside the pattern are dead.
+    /// Unknown cells in the pattern are ignored.
+    pub fn set_target(mut self, pattern: &Pattern, x: isize, y: isize) -> Self {
+        self.periodic = false;
+        let t = self.period - 1;
+        for i in 0..self.width {
+            for j in 0..self.height {
+                let (px, py) = (i - x, j - y);
+                if px < 0 || px >= pattern.width() || py < 0 || py >= pattern.height() {
+                    self.known_cells.push(KnownCell {
+                        coord: (i, j, t),
+                        state: DEAD,
+                    });
+                }
+            }
+        }
+        self.add_pattern(pattern, x, y, t)
+    }
+
+    /// Sets whether to search for catalysts.
+    pub fn set_catalyst(mut self, catalyst: bool) -> Self {
+        self.catalyst = catalyst;
+        self
+    }
+
+    /// Sets up a search for catalysts of a reaction.
+    ///
+    /// The reaction is placed in generation 0 with its upper left corner
+    /// at `(x, y)`. Its known cells are the active region, and unknown cells
+    /// in it may be part of the catalyst. The configuration is made
+    /// non-periodic, so the period is the number of generations in which
+    /// the catalyst must recover.
+    pub fn set_reaction(mut self, reaction: &Pattern, x: isize, y: isize) -> Self {
+        self.periodic = false;
+        self.catalyst = true;
+        self.add_pattern(reaction, x, y, 0)
+    }
+
+    /// Sets up a search for the smallest stator supporting a rotor.
+    ///
+    /// `rotor` contains a pattern for each generation, placing their
+    /// upper left corners at `(x, y)`. The period is set to the number of
+    /// patterns, and the known cells of the patterns are added to the
+    /// known cells. All other cells must be stable. The `max_cell_count`
+    /// is automatically reduced when a result is found, so each result
+    /// has a smaller stator than the last one. The front is not forced
+    /// to be nonempty.
+    pub fn set_rotor(mut self, rotor: &[Pattern], x: isize, y: isize) -> Self {
+        self.period = rotor.len() as isize;
+        self.stator = true;
+        self.reduce_max = true;
+        self.non_empty_front = false;
+        for (t, pattern) in rotor.iter().enumerate() {
+            self = self.add_pattern(pattern, x, y, t as isize);
+        }
+        self
+    }
+
+    /// Whether the world can grow along the search order,
+    /// i.e., the symmetry and the transformation do not involve
+    /// the size in that direction, the engine is `Backtrack`,
+    /// and it is not a catalyst search.
+    pub(crate) fn can_grow(&self) -> bool {
+        self.engine == Engine::Backtrack
+            && !self.catalyst
+            && match self.auto_search_order() {
+                SearchOrder::RowFirst => {
+                    matches!(self.symmetry, Symmetry::C1 | Symmetry::D2Col)
+                        && matches!(self.transform, Transform::Id | Transform::FlipCol)
+                }
+                SearchOrder::ColumnFirst => {
+                    matches!(self.symmetry, Symmetry::C1 | Symmetry::D2Row)
+                        && matches!(self.transform, Transform::Id | Transform::FlipRow)
+                }
+            }
+    }
+
+    /// Whether the rule is Conway's Game of Life, in any notation.
+    ///
+    /// Only such rules can be shown in LifeHistory format.
+    pub fn is_life(&self) -> bool {
+        is_life(&self.rule_string)
+    }
+
+    /// Whether a proof can be recorded and checked, i.e.,
+    /// the rule has two states, the engine is `Backtrack`,
+    /// and the search does not grow, restart, or tighten the bound
+    /// by `reduce_max`.
+    ///
+    /// The proof is checked against [`cnf`](#method.cnf),
+    /// which only supports rules with two states.
+    pub(crate) fn can_record_proof(&self) -> bool {
+        two_state_rule(&self.rule_string).is_ok()
+            && self.engine == Engine::Backtrack
+            && !self.grow
+            && self.restart == Restart::Never
+            && !self.reduce_max
+    }
+
+    /// Tightens the bound of the objective after a result
+    /// with the given value of the objective is found,
+    /// so that later results must be better.
+    pub(crate) fn tighten_bound(&mut self, value: usize) {
+        match self.objective {
+            Objective::MinCellCount => self.max_cell_count = Some(value.saturating_sub(1)),
+            Objective::MaxRotor => self.bound = Some(value + 1),
+            _ => self.bound = Some(value.saturating_sub(1)),
+        }
+    }
+
+    /// The positions of the known cells in generation 0,
+    /// i.e., the active region of a catalyst search.
+    pub(crate) fn active_cells(&self) -> HashSet<(isize, isize)> {
+        self.known_cells
+            .iter()
+            .map(|known| self.translate(known.coord))
+            .filter(|&(_, _, t)| t == 0)
+            .map(|(x, y, _)| (x, y))
+            .collect()
+    }
+
     /// Automatically determines the search order if `search_order` is `None`.
     pub(crate) fn auto_search_order(&self) -> SearchOrder {
         self.search_order.unwrap_or_else(|| {
@@ -457,8 +1042,38 @@ impl Config {
         })
     }
 
+    /// Comment lines describing the configuration, used in the RLE output.
+    ///
+    /// `description` is appended as the last line, e.g., the generation.
+    pub(crate) fn rle_comments(&self, description: String) -> Vec<String> {
+        let config = if self.periodic {
+            format!(
+                "width = {}, height = {}, period = {}, dx = {}, dy = {}, \
+                 transform = {}, symmetry = {}",
+                self.width,
+                self.height,
+                self.period,
+                self.dx,
+                self.dy,
+                self.transform,
+                self.symmetry
+            )
+        } else {
+            format!(
+                "width = {}, height = {}, generations = {}, non-periodic, symmetry = {}",
+                self.width, self.height, self.period, self.symmetry
+            )
+        };
+        vec![String::from("Generated by rlifesrc."), config, description]
+    }
+
     /// Applies the transformation and translation to a coord.
+    ///
+    /// Does nothing if the configuration is not periodic.
     pub(crate) fn translate(&self, coord: Coord) -> Coord {
+        if !self.periodic {
+            return coord;
+        }
         let (mut x, mut y, mut t) = coord;
         while t < 0 {
             t += self.period;
@@ -496,7 +1111,8 @@ impl Config {
     }
 
     /// Creates a new world from the configuration.
-    /// Returns an error if the rule string is invalid.
+    /// Returns an error if the rule string is invalid,
+    /// or if the world cannot grow when `grow` is set.
     ///
     /// In rules that contain `B0`, cells outside the search range are
     /// considered `Dead` in even generations, `Alive` in odd generations.
@@ -504,18 +1120,28 @@ impl Config {
     ///
     /// After the last generation, the pattern will return to
     /// the first generation, applying the transformation first,
-    /// and then the translation defined by `dx` and `dy`.
+    /// and then the translation defined by `dx` and `dy`,
+    /// unless the configuration is not periodic.
     pub fn world(&self) -> Result<Box<dyn Search>, Error> {
+        if self.grow && !self.can_grow() {
+            return Err(Error::GrowError);
+        }
+        if self.proof && !self.can_record_proof() {
+            return Err(Error::ProofConfigError);
+        }
+        if self.engine == Engine::Sat {
+            return Ok(Box::new(SatWorld::new(self)?));
+        }
         if let Ok(rule) = self.rule_string.parse::<Life>() {
-            Ok(Box::new(World::new(&self, rule)))
+            self.world_with_rule(rule)
         } else if let Ok(rule) = self.rule_string.parse::<NtLife>() {
-            Ok(Box::new(World::new(&self, rule)))
+            self.world_with_rule(rule)
         } else if let Ok(rule) = self.rule_string.parse::<LifeGen>() {
             if rule.gen() > 2 {
-                Ok(Box::new(World::new(&self, rule)))
+                self.world_with_rule(rule)
             } else {
                 let rule = rule.non_gen();
-                Ok(Box::new(World::new(&self, rule)))
+                self.world_with_rule(rule)
             }
         } else {
             let rule = self
@@ -523,11 +1149,16 @@ impl Config {
                 .parse::<NtLifeGen>()
                 .map_err(Error::ParseRuleError)?;
             if rule.gen() > 2 {
-                Ok(Box::new(World::new(&self, rule)))
+                self.world_with_rule(rule)
             } else {
                 let rule = rule.non_gen();
-                Ok(Box::new(World::new(&self, rule)))
+                self.world_with_rule(rule)
             }
         }
     }
+
+    /// Creates a new world with the given rule, and sets the known cells.
+    fn world_with_rule<R: Rule + 'static>(&self, rule: R) -> Result<Box<dyn Search>, Error> {
+        Ok(Box::new(World::new(self, rule).init_known_cells()?))
+    }
 }
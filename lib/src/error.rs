@@ -9,6 +9,52 @@ pub enum Error {
     GetCellError(Coord),
     #[error("Unable to set cell at {0:?}")]
     SetCellError(Coord),
+    #[error("Invalid state for known cell {0:?}")]
+    KnownStateError(Coord),
+    #[error("Known cell {0:?} exceeds the max cell count or the bound of the objective, or leaves the front empty")]
+    KnownCellError(Coord),
     #[error("Invalid rule: {0:?}")]
     ParseRuleError(#[from] ParseRuleError),
+    #[error("LifeHistory only supports Conway's Game of Life, not {0:?}")]
+    HistoryRuleError(String),
+    #[error("The state of cell {0:?} is unknown")]
+    UnknownCellError(Coord),
+    #[error("Generation {0} on the unbounded plane does not match the result")]
+    EvolutionError(isize),
+    #[error("The pattern does not return to generation 0 after {0} generations")]
+    PeriodError(isize),
+    #[error("The pattern has a smaller period {0}")]
+    SubperiodError(isize),
+    #[error("The catalyst is not a still life")]
+    StillLifeError,
+    #[error("The catalyst does not recover in generation {0}")]
+    RecoveryError(isize),
+    #[error("The world cannot grow with this symmetry, transformation or engine, or in a catalyst search")]
+    GrowError,
+    #[error("Proofs are only supported for rules with two states, by the backtracking engine, without growth, restarts or reduce_max")]
+    ProofConfigError,
+    #[error("Invalid proof: {0}")]
+    ParseProofError(String),
+    #[error("Invalid proof at leaf {0}: {1}")]
+    ProofError(usize, String),
+    #[error("The causes of the cells are not recorded")]
+    ExplainError,
+    #[error("Invalid pattern: {0}")]
+    ParsePatternError(String),
+    #[error("Unable to import: {0}")]
+    ImportError(String),
+    #[error("Unable to encode or decode the SAT problem: {0}")]
+    CnfError(String),
+    #[error("Unsupported save format version {0}")]
+    SaveVersionError(u32),
+    #[error("The rule {0:?} in the save header does not match the configuration")]
+    SaveRuleError(String),
+    #[error("Cell {0:?} in the save is outside the search range")]
+    SaveCellError(Coord),
+    #[error("Invalid state for cell {0:?} in the save")]
+    SaveStateError(Coord),
+    #[error("Cell {0:?} in the save conflicts with the earlier cells")]
+    SaveConflictError(Coord),
+    #[error("Invalid clause excluding a result in the save")]
+    SaveBlockError,
 }
@@ -0,0 +1,321 @@
+//! Explaining why a cell has its state.
+//!
+//! When `explain` is set in the configuration, the world records
+//! the cause of each cell it sets in the `set_stack`, so that the
+//! deductions can be traced back to the known cells and the decisions.
+
+use crate::{
+    cells::{CellRef, Coord, State, ALIVE, DEAD},
+    error::Error,
+    rules::Rule,
+    search::Reason,
+    world::World,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{self, Display, Formatter},
+};
+
+/// The states of a cell where the rule is applied, its neighbors
+/// and its successor, when the rule determines one of them.
+///
+/// A state is `None` if it is unknown at that time.
+/// The determined cell is always unknown.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Transition {
+    /// The state of the cell where the rule is applied.
+    pub state: Option<State>,
+    /// The states of its neighbors, at the relative positions
+    /// `(-1, -1)`, `(-1, 0)`, `(-1, 1)`, `(0, -1)`, `(0, 1)`,
+    /// `(1, -1)`, `(1, 0)`, `(1, 1)`.
+    pub nbhd: [Option<State>; 8],
+    /// The state of its successor.
+    pub succ: Option<State>,
+}
+
+/// Displays the cell and its neighbors as three rows separated by `/`,
+/// followed by `->` and the successor. Known states are `.` for `Dead`,
+/// `o` for `Alive`, and numbers for the dying states of Generations rules.
+/// Unknown states are `?`.
+impl Display for Transition {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let symbol = |state: Option<State>| match state {
+            None => String::from("?"),
+            Some(DEAD) => String::from("."),
+            Some(ALIVE) => String::from("o"),
+            Some(State(i)) => i.to_string(),
+        };
+        let n = self.nbhd;
+        let rows = [
+            [n[0], n[3], n[5]],
+            [n[1], self.state, n[6]],
+            [n[2], n[4], n[7]],
+        ];
+        for (i, row) in rows.iter().enumerate() {
+            if i > 0 {
+                write!(f, "/")?;
+            }
+            for &state in row {
+                write!(f, "{}", symbol(state))?;
+            }
+        }
+        write!(f, " -> {}", symbol(self.succ))
+    }
+}
+
+/// How the rule determines a cell from the cell where it is applied,
+/// and the transition that forces the state.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RuleCase {
+    /// The cell is the successor of that cell,
+    /// determined by that cell and its neighbors.
+    Successor(Transition),
+    /// The cell is that cell itself,
+    /// determined by its neighbors and its successor.
+    Cell(Transition),
+    /// The cell is a neighbor of that cell,
+    /// determined by that cell, its other neighbors and its successor.
+    Neighbor(Transition),
+}
+
+impl RuleCase {
+    /// The transition that forces the state.
+    pub fn transition(&self) -> &Transition {
+        match self {
+            RuleCase::Successor(transition)
+            | RuleCase::Cell(transition)
+            | RuleCase::Neighbor(transition) => transition,
+        }
+    }
+}
+
+/// Why a cell has its state.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Cause {
+    /// The cell is known before the search,
+    /// e.g., it is a known cell in the configuration.
+    Known,
+    /// The state is chosen by the search.
+    Decision,
+    /// The state is switched by backtracking,
+    /// after the other states led to conflicts.
+    Backtrack,
+    /// The cell equals the cell with these coordinates,
+    /// by the symmetry, or because it is in the stator or the catalyst.
+    Symmetry(Coord),
+    /// The rule applied at the cell with these coordinates
+    /// determines the state.
+    Rule(Coord, RuleCase),
+}
+
+/// A cell, its state, and why it has the state.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Deduction {
+    /// The coordinates of the cell.
+    pub coord: Coord,
+    /// The state of the cell.
+    pub state: State,
+    /// Why the cell has the state.
+    pub cause: Cause,
+}
+
+impl Display for Deduction {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{:?} = {}: ", self.coord, self.state.0)?;
+        match self.cause {
+            Cause::Known => write!(f, "known"),
+            Cause::Decision => write!(f, "chosen by the search"),
+            Cause::Backtrack => write!(f, "switched by backtracking"),
+            Cause::Symmetry(coord) => write!(f, "equal to {:?}", coord),
+            Cause::Rule(coord, RuleCase::Successor(transition)) => {
+                write!(f, "successor of {:?} by the rule, {}", coord, transition)
+            }
+            Cause::Rule(_, RuleCase::Cell(transition)) => write!(
+                f,
+                "determined by its neighbors and successor by the rule, {}",
+                transition
+            ),
+            Cause::Rule(coord, RuleCase::Neighbor(transition)) => {
+                write!(f, "neighbor of {:?} by the rule, {}", coord, transition)
+            }
+        }
+    }
+}
+
+/// What the cells are being deduced from, during `proceed`.
+#[derive(Clone, Copy)]
+pub(crate) enum Deducer<'a, R: Rule> {
+    /// The cells that equal this cell.
+    Symmetry(CellRef<'a, R>),
+    /// The rule applied at this cell.
+    Rule(CellRef<'a, R>),
+}
+
+/// The cells involved in the rule applied at a cell:
+/// the cell itself, its successor and its neighbors.
+fn rule_cells<R: Rule>(cell: CellRef<R>) -> Vec<CellRef<R>> {
+    let mut cells = vec![cell];
+    cells.extend(cell.succ);
+    cells.extend(cell.nbhd.iter().flatten());
+    cells
+}
+
+impl<'a, R: Rule> World<'a, R> {
+    /// The cause of setting a cell, before its state is set.
+    ///
+    /// Cells deduced outside `proceed` are considered known.
+    pub(crate) fn cause(&self, cell: CellRef<'a, R>, reason: Reason) -> Cause {
+        match reason {
+            Reason::Decide(_) => Cause::Decision,
+            Reason::TryAnother(..) | Reason::Backtrack => Cause::Backtrack,
+            Reason::Deduce => match self.deducer {
+                None => Cause::Known,
+                Some(Deducer::Symmetry(from)) => Cause::Symmetry(from.coord),
+                Some(Deducer::Rule(from)) => {
+                    // Neighbors outside the world have the background state.
+                    let mut nbhd = [Some(from.background); 8];
+                    for (state, neigh) in nbhd.iter_mut().zip(from.nbhd.iter()) {
+                        if let Some(neigh) = neigh {
+                            *state = neigh.state.get();
+                        }
+                    }
+                    let transition = Transition {
+                        state: from.state.get(),
+                        nbhd,
+                        succ: from.succ.and_then(|succ| succ.state.get()),
+                    };
+                    let case = if cell == from {
+                        RuleCase::Cell(transition)
+                    } else if Some(cell) == from.succ {
+                        RuleCase::Successor(transition)
+                    } else {
+                        RuleCase::Neighbor(transition)
+                    };
+                    Cause::Rule(from.coord, case)
+                }
+            },
+        }
+    }
+
+    /// Finds a cell, or a cell of the still life in a catalyst search,
+    /// by its coordinates.
+    fn find_any_cell(&self, coord: Coord) -> Option<CellRef<'a, R>> {
+        let (x, y, t) = coord;
+        if t < 0 {
+            self.find_still((x, y))
+        } else {
+            self.find_cell(coord)
+        }
+    }
+
+    /// The cells whose states may determine the state of a cell
+    /// with the given cause.
+    fn premises(&self, cause: Cause) -> Vec<CellRef<'a, R>> {
+        match cause {
+            Cause::Known | Cause::Decision | Cause::Backtrack => Vec::new(),
+            Cause::Symmetry(coord) => self.find_any_cell(coord).into_iter().collect(),
+            Cause::Rule(coord, _) => self
+                .find_any_cell(coord)
+                .map(rule_cells)
+                .unwrap_or_default(),
+        }
+    }
+
+    /// The known cells that lead to the given cells, including themselves,
+    /// in the order they are set.
+    ///
+    /// Among the cells that lead to them, only those whose causes are recorded
+    /// are included. The other known cells, such as those outside the search
+    /// range, are fixed by the world. The given cells are always included.
+    fn chain(&self, cells: Vec<CellRef<'a, R>>) -> Vec<Deduction> {
+        // The position in the `set_stack` and the cause of each set cell.
+        let causes = self
+            .set_stack
+            .iter()
+            .enumerate()
+            .map(|(i, set)| {
+                (
+                    set.cell.coord,
+                    (i, set.cause.as_deref().copied().unwrap_or(Cause::Known)),
+                )
+            })
+            .collect::<HashMap<_, _>>();
+        let mut deductions = Vec::new();
+        let mut visited = HashSet::new();
+        let mut stack = cells;
+        while let Some(cell) = stack.pop() {
+            if !visited.insert(cell.coord) {
+                continue;
+            }
+            let state = match cell.state.get() {
+                Some(state) => state,
+                None => continue,
+            };
+            let (index, cause) = causes
+                .get(&cell.coord)
+                .copied()
+                .unwrap_or((0, Cause::Known));
+            for premise in self.premises(cause) {
+                if premise.state.get().is_some()
+                    && matches!(causes.get(&premise.coord), Some(&(i, _)) if i < index)
+                {
+                    stack.push(premise);
+                }
+            }
+            deductions.push((
+                index,
+                Deduction {
+                    coord: cell.coord,
+                    state,
+                    cause,
+                },
+            ));
+        }
+        deductions.sort_by_key(|&(index, _)| index);
+        deductions
+            .into_iter()
+            .map(|(_, deduction)| deduction)
+            .collect()
+    }
+
+    /// Explains why a cell has its state.
+    ///
+    /// Returns the chain of deductions that leads to the cell,
+    /// back to the known cells and the decisions, in the order they are set.
+    /// The cell itself comes last.
+    ///
+    /// Returns an error if the causes are not recorded, i.e., `explain`
+    /// is not set in the configuration, or if the cell is unknown.
+    pub fn explain(&self, coord: Coord) -> Result<Vec<Deduction>, Error> {
+        if !self.config.explain {
+            return Err(Error::ExplainError);
+        }
+        let cell = self
+            .find_cell(self.config.translate(coord))
+            .ok_or(Error::GetCellError(coord))?;
+        if cell.state.get().is_none() {
+            return Err(Error::UnknownCellError(coord));
+        }
+        Ok(self.chain(vec![cell]))
+    }
+
+    /// Records the explanation of a conflict found before any decision,
+    /// while the cells involved are still known.
+    ///
+    /// The conflict is found where the `deducer` was deducing.
+    pub(crate) fn record_root_conflict(&mut self) {
+        if !self.config.explain || self.depth() > 0 {
+            return;
+        }
+        let cells = match self.deducer {
+            Some(Deducer::Symmetry(cell)) => {
+                let mut cells = vec![cell];
+                cells.extend(cell.sym.iter().copied());
+                cells
+            }
+            Some(Deducer::Rule(cell)) => rule_cells(cell),
+            None => return,
+        };
+        self.root_conflict = Some(self.chain(cells));
+    }
+}
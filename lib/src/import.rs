@@ -0,0 +1,224 @@
+//! Importing search problems from other search programs.
+
+use crate::{
+    cells::{ALIVE, DEAD},
+    config::{Config, KnownCell, Symmetry},
+    error::Error,
+};
+use std::collections::HashMap;
+
+impl Config {
+    /// Imports a search problem in the input format of Oscar Cunningham's
+    /// [Logic Life Search](https://github.com/OscarCunningham/logic-life-search).
+    ///
+    /// The input is a list of grids, one for each generation, separated by
+    /// blank lines. Cells are separated by whitespaces or commas. `0` and `1`
+    /// are dead and living cells, `*` is an unknown cell. The size of the grids
+    /// becomes the size of the world, and the number of grids becomes the period.
+    ///
+    /// A variable which appears only once (with or without a leading `-`) is
+    /// simply an unknown cell. Variables which appear more than once constrain
+    /// several cells to be equal, which cannot be represented, and are rejected.
+    /// So are cells marked with `'`.
+    ///
+    /// The rule and other options of Logic Life Search are given in its
+    /// command line, so they are left as default, except that the front
+    /// is not forced to be nonempty.
+    pub fn from_lls(input: &str) -> Result<Self, Error> {
+        let mut grids: Vec<Vec<Vec<&str>>> = Vec::new();
+        let mut new_grid = true;
+        for line in input.lines().map(str::trim) {
+            if line.is_empty() {
+                new_grid = true;
+                continue;
+            }
+            if line.starts_with('#') {
+                continue;
+            }
+            let row = line
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|token| !token.is_empty())
+                .collect();
+            if new_grid {
+                grids.push(Vec::new());
+                new_grid = false;
+            }
+            grids.last_mut().unwrap().push(row);
+        }
+        if grids.is_empty() {
+            return Err(Error::ImportError(String::from("no grid")));
+        }
+
+        let height = grids[0].len();
+        let width = grids[0][0].len();
+        let mut occurrences = HashMap::new();
+        let mut known_cells = Vec::new();
+        for (t, grid) in grids.iter().enumerate() {
+            if grid.len() != height || grid.iter().any(|row| row.len() != width) {
+                return Err(Error::ImportError(format!(
+                    "the grid of generation {} is not {} × {}",
+                    t, width, height
+                )));
+            }
+            for (y, row) in grid.iter().enumerate() {
+                for (x, &token) in row.iter().enumerate() {
+                    let coord = (x as isize, y as isize, t as isize);
+                    match token {
+                        "0" => known_cells.push(KnownCell { coord, state: DEAD }),
+                        "1" => known_cells.push(KnownCell {
+                            coord,
+                            state: ALIVE,
+                        }),
+                        "*" => (),
+                        _ if token.contains('\'') => {
+                            return Err(Error::ImportError(format!(
+                                "marked cell {} at {:?} is not supported",
+                                token, coord
+                            )))
+                        }
+                        _ => {
+                            let name = token.trim_start_matches('-');
+                            if name.is_empty()
+                                || !name.starts_with(|c: char| c.is_ascii_alphabetic())
+                                || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+                            {
+                                return Err(Error::ImportError(format!(
+                                    "invalid cell {} at {:?}",
+                                    token, coord
+                                )));
+                            }
+                            if let Some(first) = occurrences.insert(name, coord) {
+                                return Err(Error::ImportError(format!(
+                                    "variable {} appears at both {:?} and {:?}, \
+                                     constraints between cells are not supported",
+                                    name, first, coord
+                                )));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(
+            Config::new(width as isize, height as isize, grids.len() as isize)
+                .set_non_empty_front(false)
+                .set_known_cells(known_cells),
+        )
+    }
+
+    /// Imports a search problem from a save file of
+    /// [WinLifeSearch](https://github.com/jsummers/winlifesearch/) or
+    /// [lifesrc](https://github.com/DavidKinder/Xlife/tree/master/Xlife35/source/lifesearch).
+    ///
+    /// The file is line-based, each line starting with a letter:
+    ///
+    /// * `V version`: the version of the file, which is ignored;
+    /// * `R rule`: the rule string;
+    /// * `I status rows cols gens rowtrans coltrans rowsym colsym pointsym fwdsym bwdsym ...`:
+    ///   the size, the translation and the symmetry of the search,
+    ///   where extra fields are ignored;
+    /// * `S row col gen state free`: a set cell, where the rows and columns
+    ///   start from 1, the state is `0` or `1`, and `free` is `1` if the cell
+    ///   is chosen by the search;
+    /// * `T ...` and `E`: the search status and the end of the file,
+    ///   which are ignored.
+    ///
+    /// Lines starting with `#` are comments.
+    ///
+    /// Only the cells set before the first cell chosen by the search are
+    /// imported as known cells. The rest of the search status is dropped,
+    /// so the search starts over.
+    ///
+    /// Other kinds of lines, and combinations of symmetries which cannot be
+    /// represented, are rejected.
+    pub fn from_lss(input: &str) -> Result<Self, Error> {
+        let mut config: Option<Config> = None;
+        let mut rule_string = None;
+        let mut known_cells = Vec::new();
+        let mut decided = false;
+        for line in input.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid = || Error::ImportError(format!("invalid line: {}", line));
+            let (kind, rest) = line.split_at(line.chars().next().unwrap().len_utf8());
+            let numbers = || {
+                rest.split_whitespace()
+                    .map(str::parse::<isize>)
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|_| invalid())
+            };
+            match kind {
+                "V" | "T" | "E" => (),
+                "R" => rule_string = Some(rest.trim().to_string()),
+                "I" => {
+                    let numbers = numbers()?;
+                    if numbers.len() < 11 {
+                        return Err(invalid());
+                    }
+                    let (rows, cols, gens) = (numbers[1], numbers[2], numbers[3]);
+                    if rows <= 0 || cols <= 0 || gens <= 0 {
+                        return Err(invalid());
+                    }
+                    let flags = [numbers[6], numbers[7], numbers[8], numbers[9], numbers[10]]
+                        .iter()
+                        .map(|&n| n != 0)
+                        .collect::<Vec<_>>();
+                    let symmetry = match flags.as_slice() {
+                        [false, false, false, false, false] => Symmetry::C1,
+                        [true, false, false, false, false] => Symmetry::D2Row,
+                        [false, true, false, false, false] => Symmetry::D2Col,
+                        [false, false, true, false, false] => Symmetry::C2,
+                        [false, false, false, true, false] => Symmetry::D2Antidiag,
+                        [false, false, false, false, true] => Symmetry::D2Diag,
+                        [true, true, _, false, false] => Symmetry::D4Ortho,
+                        [false, false, _, true, true] => Symmetry::D4Diag,
+                        [true, true, _, true, true] => Symmetry::D8,
+                        _ => {
+                            return Err(Error::ImportError(format!(
+                                "unsupported combination of symmetries: {}",
+                                line
+                            )))
+                        }
+                    };
+                    config = Some(
+                        Config::new(cols, rows, gens)
+                            .set_translate(numbers[5], numbers[4])
+                            .set_symmetry(symmetry)
+                            .set_non_empty_front(false),
+                    );
+                }
+                "S" => {
+                    let numbers = numbers()?;
+                    if numbers.len() < 5 {
+                        return Err(invalid());
+                    }
+                    decided |= numbers[4] != 0;
+                    if decided {
+                        continue;
+                    }
+                    let state = match numbers[3] {
+                        0 => DEAD,
+                        1 => ALIVE,
+                        _ => {
+                            return Err(Error::ImportError(format!(
+                                "unsupported cell state: {}",
+                                line
+                            )))
+                        }
+                    };
+                    let coord = (numbers[1] - 1, numbers[0] - 1, numbers[2]);
+                    known_cells.push(KnownCell { coord, state });
+                }
+                _ => return Err(Error::ImportError(format!("unsupported line: {}", line))),
+            }
+        }
+        let mut config =
+            config.ok_or_else(|| Error::ImportError(String::from("missing `I` line")))?;
+        if let Some(rule_string) = rule_string {
+            config.rule_string = rule_string;
+        }
+        Ok(config.set_known_cells(known_cells))
+    }
+}
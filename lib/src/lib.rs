@@ -46,10 +46,19 @@
 //! ```
 
 mod cells;
+mod cnf;
 mod config;
 mod error;
+mod explain;
+mod import;
+mod pattern;
+mod proof;
+mod rle;
 pub mod rules;
+mod sat;
 mod search;
+mod simulator;
+mod sweep;
 mod traits;
 mod world;
 
@@ -57,11 +66,20 @@ mod world;
 mod save;
 
 pub use cells::{State, ALIVE, DEAD};
-pub use config::{Config, NewState, SearchOrder, Symmetry, Transform};
+pub use cnf::Cnf;
+pub use config::{
+    Config, Engine, KnownCell, NewState, Objective, Restart, SearchOrder, Symmetry, Transform,
+};
 pub use error::Error;
+pub use explain::{Cause, Deduction, RuleCase, Transition};
+pub use pattern::Pattern;
+pub use proof::{LeafEnd, Proof, ProofLeaf, ProofSummary};
+pub use sat::SatWorld;
 pub use search::Status;
+pub use simulator::Simulator;
+pub use sweep::{Sweep, SweepPoint, SweepStatus};
 pub use traits::Search;
 pub use world::World;
 
 #[cfg(feature = "serialize")]
-pub use save::WorldSer;
+pub use save::{SaveHeader, WorldSer, SAVE_VERSION};
@@ -0,0 +1,439 @@
+//! Patterns, and parsers for some common pattern formats.
+
+use crate::{
+    cells::{State, ALIVE, DEAD},
+    config::Transform,
+    error::Error,
+};
+use std::str::FromStr;
+
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
+
+/// Maximal width and height of a pattern in RLE format.
+///
+/// It bounds the memory used by the run counts in untrusted input.
+const MAX_RLE_SIZE: usize = 1 << 16;
+
+/// Maximal number of cells of a pattern in RLE format with a header.
+///
+/// It bounds the memory used by the sizes in untrusted headers.
+const MAX_RLE_AREA: usize = 1 << 24;
+
+/// A rectangular pattern.
+///
+/// The state of each cell is represented by `Option<State>`,
+/// where `None` means that the state of the cell is unknown.
+/// Cells outside the pattern are considered dead.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct Pattern {
+    /// Width.
+    width: isize,
+
+    /// Height.
+    height: isize,
+
+    /// States of the cells, row by row.
+    cells: Vec<Option<State>>,
+
+    /// The rule string of the cellular automaton, if known.
+    rule_string: Option<String>,
+}
+
+impl Pattern {
+    /// Creates a new pattern of the given size, where all cells are dead.
+    ///
+    /// A negative width or height is treated as 0.
+    pub fn new(width: isize, height: isize) -> Self {
+        let (width, height) = (width.max(0), height.max(0));
+        Pattern {
+            width,
+            height,
+            cells: vec![Some(DEAD); (width * height) as usize],
+            rule_string: None,
+        }
+    }
+
+    /// Width.
+    pub fn width(&self) -> isize {
+        self.width
+    }
+
+    /// Height.
+    pub fn height(&self) -> isize {
+        self.height
+    }
+
+    /// The rule string of the cellular automaton, if known.
+    pub fn rule_string(&self) -> Option<&str> {
+        self.rule_string.as_deref()
+    }
+
+    /// Sets the rule string.
+    pub fn set_rule_string(&mut self, rule_string: Option<String>) {
+        self.rule_string = rule_string;
+    }
+
+    /// Gets the state of a cell.
+    ///
+    /// `None` means that the state of the cell is unknown.
+    /// Cells outside the pattern are dead.
+    pub fn get_cell_state(&self, x: isize, y: isize) -> Option<State> {
+        if 0 <= x && x < self.width && 0 <= y && y < self.height {
+            self.cells[(y * self.width + x) as usize]
+        } else {
+            Some(DEAD)
+        }
+    }
+
+    /// Sets the state of a cell.
+    ///
+    /// Cells outside the pattern are ignored.
+    pub fn set_cell_state(&mut self, x: isize, y: isize, state: Option<State>) {
+        if 0 <= x && x < self.width && 0 <= y && y < self.height {
+            self.cells[(y * self.width + x) as usize] = state;
+        }
+    }
+
+    /// An iterator over the coordinates and states of all known cells.
+    pub fn known_cells(&self) -> impl Iterator<Item = ((isize, isize), State)> + '_ {
+        let width = self.width;
+        self.cells.iter().enumerate().filter_map(move |(i, state)| {
+            let i = i as isize;
+            state.map(|state| ((i % width, i / width), state))
+        })
+    }
+
+    /// Number of living cells.
+    ///
+    /// For Generations rules, dying cells are not counted.
+    pub fn population(&self) -> usize {
+        self.cells
+            .iter()
+            .filter(|&&state| state == Some(ALIVE))
+            .count()
+    }
+
+    /// Applies a transformation to the pattern.
+    ///
+    /// The transformations are the same as in `Transform`,
+    /// but the pattern need not be square: the width and the height
+    /// are swapped by rotations by 90° or 270° and by diagonal reflections.
+    pub fn transform(&self, transform: Transform) -> Self {
+        let (width, height) = (self.width, self.height);
+        let (new_width, new_height) = match transform {
+            Transform::Id | Transform::Rotate180 | Transform::FlipRow | Transform::FlipCol => {
+                (width, height)
+            }
+            _ => (height, width),
+        };
+        let mut pattern = Pattern::new(new_width, new_height);
+        pattern.rule_string = self.rule_string.clone();
+        for y in 0..height {
+            for x in 0..width {
+                let (new_x, new_y) = match transform {
+                    Transform::Id => (x, y),
+                    Transform::Rotate90 => (y, width - 1 - x),
+                    Transform::Rotate180 => (width - 1 - x, height - 1 - y),
+                    Transform::Rotate270 => (height - 1 - y, x),
+                    Transform::FlipRow => (x, height - 1 - y),
+                    Transform::FlipCol => (width - 1 - x, y),
+                    Transform::FlipDiag => (y, x),
+                    Transform::FlipAntidiag => (height - 1 - y, width - 1 - x),
+                };
+                pattern.set_cell_state(new_x, new_y, self.get_cell_state(x, y));
+            }
+        }
+        pattern
+    }
+
+    /// Crops the pattern to the rectangle of the given size
+    /// whose upper left corner is at `(x, y)`.
+    ///
+    /// Cells outside the original pattern are dead.
+    pub fn crop(&self, x: isize, y: isize, width: isize, height: isize) -> Self {
+        let mut pattern = Pattern::new(width, height);
+        pattern.rule_string = self.rule_string.clone();
+        for new_y in 0..height {
+            for new_x in 0..width {
+                pattern.set_cell_state(new_x, new_y, self.get_cell_state(x + new_x, y + new_y));
+            }
+        }
+        pattern
+    }
+
+    /// The smallest rectangle containing all cells that are not dead,
+    /// as `(x, y, width, height)`.
+    ///
+    /// Returns `None` if all cells are dead.
+    pub fn bounding_box(&self) -> Option<(isize, isize, isize, isize)> {
+        let (min_x, min_y, max_x, max_y) = self
+            .cells
+            .iter()
+            .enumerate()
+            .filter(|&(_, &state)| state != Some(DEAD))
+            .map(|(i, _)| (i as isize % self.width, i as isize / self.width))
+            .fold(
+                (isize::MAX, isize::MAX, isize::MIN, isize::MIN),
+                |(min_x, min_y, max_x, max_y), (x, y)| {
+                    (min_x.min(x), min_y.min(y), max_x.max(x), max_y.max(y))
+                },
+            );
+        if min_x > max_x {
+            None
+        } else {
+            Some((min_x, min_y, max_x - min_x + 1, max_y - min_y + 1))
+        }
+    }
+
+    /// Crops the pattern to its bounding box.
+    ///
+    /// Returns an empty pattern if all cells are dead.
+    pub fn trim(&self) -> Self {
+        let (x, y, width, height) = self.bounding_box().unwrap_or((0, 0, 0, 0));
+        self.crop(x, y, width, height)
+    }
+
+    /// Creates a pattern from rows of cells.
+    ///
+    /// Shorter rows are padded with dead cells. The size is at least
+    /// `min_width` × `min_height`.
+    fn from_rows(rows: Vec<Vec<Option<State>>>, min_width: isize, min_height: isize) -> Self {
+        let width = rows
+            .iter()
+            .map(|row| row.len() as isize)
+            .max()
+            .unwrap_or(0)
+            .max(min_width);
+        let height = (rows.len() as isize).max(min_height);
+        let mut pattern = Pattern::new(width, height);
+        for (y, row) in rows.into_iter().enumerate() {
+            for (x, state) in row.into_iter().enumerate() {
+                pattern.set_cell_state(x as isize, y as isize, state);
+            }
+        }
+        pattern
+    }
+
+    /// Parses a pattern in [RLE](https://conwaylife.com/wiki/Rle) format.
+    ///
+    /// Both 2-state (`b` and `o`) and multistate (`.`, `A` to `X`,
+    /// `pA` to `yO`) notations are supported. `?` means an unknown cell.
+    ///
+    /// The output of `Search::rle_gen` can also be parsed.
+    /// Runs of cells or rows are limited by the size in the header,
+    /// or by `MAX_RLE_SIZE` if there is no header. The size in the header
+    /// is limited by `MAX_RLE_SIZE` and `MAX_RLE_AREA`.
+    pub fn from_rle(input: &str) -> Result<Self, Error> {
+        let mut width = None;
+        let mut height = None;
+        let mut rule_string = None;
+        let mut lines = input
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .peekable();
+
+        if let Some(header) = lines.peek() {
+            if header.starts_with('x') {
+                // The rule string may contain commas, so it is split off first.
+                let (sizes, rule) = match header.find("rule") {
+                    Some(i) => (&header[..i], Some(&header[i..])),
+                    None => (*header, None),
+                };
+                if let Some(rule) = rule {
+                    let value = rule.split_once('=').map_or("", |(_, value)| value).trim();
+                    rule_string = Some(value.to_owned());
+                }
+                for item in sizes.split(',') {
+                    let mut kv = item.splitn(2, '=').map(str::trim);
+                    let key = kv.next().unwrap_or("");
+                    let value = kv.next().unwrap_or("");
+                    let parse_size = |value: &str| {
+                        value
+                            .parse::<isize>()
+                            .ok()
+                            .filter(|&n| n >= 0 && n as usize <= MAX_RLE_SIZE)
+                            .ok_or_else(|| {
+                                Error::ParsePatternError(format!(
+                                    "invalid size in header: {}",
+                                    value
+                                ))
+                            })
+                    };
+                    match key {
+                        "x" => width = Some(parse_size(value)?),
+                        "y" => height = Some(parse_size(value)?),
+                        _ => (),
+                    }
+                }
+                if width.unwrap_or(0) as usize * height.unwrap_or(0) as usize > MAX_RLE_AREA {
+                    return Err(Error::ParsePatternError(String::from(
+                        "size in header too large",
+                    )));
+                }
+                lines.next();
+            }
+        }
+
+        let max_width = width.map_or(MAX_RLE_SIZE, |width| width as usize);
+        let max_height = height.map_or(MAX_RLE_SIZE, |height| height as usize);
+        let too_large = || Error::ParsePatternError(String::from("pattern larger than its size"));
+        let mut rows = vec![Vec::new()];
+        let mut count: Option<usize> = None;
+        'outer: for line in lines {
+            let mut chars = line.chars().peekable();
+            while let Some(c) = chars.next() {
+                if let Some(digit) = c.to_digit(10) {
+                    count = Some(
+                        count
+                            .unwrap_or(0)
+                            .checked_mul(10)
+                            .and_then(|n| n.checked_add(digit as usize))
+                            .ok_or_else(too_large)?,
+                    );
+                    continue;
+                }
+                if c.is_whitespace() {
+                    continue;
+                }
+                let n = count.take().unwrap_or(1);
+                let state = match c {
+                    '!' => break 'outer,
+                    '$' => {
+                        // A trailing `$` may start an empty row just below the pattern.
+                        if rows.len() + n > max_height + 1 {
+                            return Err(too_large());
+                        }
+                        for _ in 0..n {
+                            rows.push(Vec::new());
+                        }
+                        continue;
+                    }
+                    'b' | '.' => Some(DEAD),
+                    '?' => None,
+                    'A'..='X' => Some(State((c as u8 - b'A') as usize + 1)),
+                    'p'..='y' if matches!(chars.peek(), Some(c) if c.is_ascii_uppercase()) => {
+                        let letter = chars.next().unwrap();
+                        if letter > 'X' {
+                            return Err(Error::ParsePatternError(format!(
+                                "invalid state: {}{}",
+                                c, letter
+                            )));
+                        }
+                        let i = (c as u8 - b'p') as usize * 24 + (letter as u8 - b'A') as usize;
+                        Some(State(i + 25))
+                    }
+                    c if c.is_ascii_lowercase() => Some(ALIVE),
+                    c => {
+                        return Err(Error::ParsePatternError(format!(
+                            "invalid character: {}",
+                            c
+                        )))
+                    }
+                };
+                if rows.len() > max_height {
+                    return Err(too_large());
+                }
+                let row = rows.last_mut().unwrap();
+                if row.len() + n > max_width {
+                    return Err(too_large());
+                }
+                for _ in 0..n {
+                    row.push(state);
+                }
+            }
+        }
+
+        if rows.len() > 1 && rows.last().unwrap().is_empty() {
+            rows.pop();
+        }
+        let mut pattern = Pattern::from_rows(rows, width.unwrap_or(0), height.unwrap_or(0));
+        pattern.rule_string = rule_string;
+        Ok(pattern)
+    }
+
+    /// Parses a pattern in [Plaintext](https://conwaylife.com/wiki/Plaintext) format.
+    ///
+    /// `.` means a dead cell, `O`, `o` or `*` means a living cell,
+    /// and `?` means an unknown cell. Lines starting with `!` are comments.
+    pub fn from_plaintext(input: &str) -> Result<Self, Error> {
+        let mut rows = Vec::new();
+        for line in input.lines().filter(|line| !line.starts_with('!')) {
+            let row = line
+                .trim_end()
+                .chars()
+                .map(|c| match c {
+                    '.' => Ok(Some(DEAD)),
+                    'O' | 'o' | '*' => Ok(Some(ALIVE)),
+                    '?' => Ok(None),
+                    c => Err(Error::ParsePatternError(format!(
+                        "invalid character: {}",
+                        c
+                    ))),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            rows.push(row);
+        }
+        while matches!(rows.last(), Some(row) if row.is_empty()) {
+            rows.pop();
+        }
+        Ok(Pattern::from_rows(rows, 0, 0))
+    }
+
+    /// Parses a pattern in [Life 1.06](https://conwaylife.com/wiki/Life_1.06) format.
+    ///
+    /// The pattern is moved so that its bounding box starts at `(0, 0)`.
+    pub fn from_life106(input: &str) -> Result<Self, Error> {
+        let mut coords = Vec::new();
+        for line in input
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        {
+            let invalid = || Error::ParsePatternError(format!("invalid line: {}", line));
+            let mut numbers = line.split_whitespace().map(str::parse::<isize>);
+            match (numbers.next(), numbers.next(), numbers.next()) {
+                (Some(Ok(x)), Some(Ok(y)), None) => coords.push((x, y)),
+                _ => return Err(invalid()),
+            }
+        }
+        let min_x = coords.iter().map(|&(x, _)| x).min().unwrap_or(0);
+        let min_y = coords.iter().map(|&(_, y)| y).min().unwrap_or(0);
+        let max_x = coords.iter().map(|&(x, _)| x).max().unwrap_or(-1);
+        let max_y = coords.iter().map(|&(_, y)| y).max().unwrap_or(-1);
+        let mut pattern = Pattern::new(max_x - min_x + 1, max_y - min_y + 1);
+        for (x, y) in coords {
+            pattern.set_cell_state(x - min_x, y - min_y, Some(ALIVE));
+        }
+        Ok(pattern)
+    }
+}
+
+/// Parses a pattern in RLE, Plaintext or Life 1.06 format.
+///
+/// The format is detected automatically.
+impl FromStr for Pattern {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let first_line = input
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .unwrap_or("");
+        if first_line.starts_with("#Life 1.06") {
+            Pattern::from_life106(input)
+        } else if first_line.starts_with('!') {
+            Pattern::from_plaintext(input)
+        } else if first_line.starts_with('#')
+            || first_line.starts_with('x')
+            || input.contains(['$', '!'])
+        {
+            Pattern::from_rle(input)
+        } else {
+            Pattern::from_plaintext(input)
+        }
+    }
+}
@@ -0,0 +1,477 @@
+//! Proofs that a search is exhaustive.
+//!
+//! A proof records the leaves of the search tree. It can be checked
+//! by unit propagation over the encoding of the problem as a SAT problem,
+//! independently of the search.
+
+use crate::{
+    cells::{Coord, State, ALIVE, DEAD},
+    cnf::{Cnf, Lit},
+    config::Config,
+    error::Error,
+    rules::Rule,
+    sat::solver::Solver,
+    search::Reason,
+    world::World,
+};
+use std::{
+    fmt::{self, Display, Formatter},
+    mem,
+    str::FromStr,
+};
+
+/// How a leaf of the search tree ends.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LeafEnd {
+    /// A conflict is found when examining the cell with these coordinates.
+    Conflict(Coord),
+    /// All cells are known, but the pattern is rejected,
+    /// because it is empty or has a smaller period.
+    Trivial,
+    /// All cells are known, and the pattern is a result.
+    Found,
+}
+
+/// A leaf of the search tree.
+///
+/// The branch to a leaf consists of the cells whose states are chosen,
+/// or switched by backtracking, in the order they are set.
+/// The cells deduced from them are not recorded.
+///
+/// A branch always shares its beginning with the branch to the previous leaf,
+/// so only the length of the shared part and the remaining cells are recorded.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProofLeaf {
+    /// Number of cells at the beginning of the branch to the previous leaf
+    /// that are also on this branch.
+    pub keep: usize,
+
+    /// The remaining cells on the branch, and their states.
+    pub cells: Vec<(Coord, State)>,
+
+    /// How the leaf ends.
+    pub end: LeafEnd,
+}
+
+/// A proof that a search is exhaustive.
+///
+/// It lists the leaves of the search tree in the order they are reached.
+/// It is recorded when `proof` is set in the configuration,
+/// and can be checked by `Config::check_proof`.
+///
+/// In text form, each leaf takes a line, containing `keep`,
+/// the cells as `x,y,t=state`, and the end of the leaf as `!x,y,t`
+/// for a conflict, `!trivial` or `!found`, separated by spaces.
+/// Empty lines and lines starting with `#` are ignored.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Proof {
+    /// The leaves of the search tree.
+    pub leaves: Vec<ProofLeaf>,
+}
+
+/// A summary of a proof that passes the check.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ProofSummary {
+    /// Number of leaves.
+    pub leaves: usize,
+
+    /// Number of leaves that are results.
+    ///
+    /// If this is zero, there are no such patterns.
+    pub results: usize,
+
+    /// Number of cells on the longest branch.
+    pub depth: usize,
+}
+
+impl Display for ProofLeaf {
+    /// Displays the leaf as a line of the proof, without the line break.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.keep)?;
+        for &((x, y, t), State(state)) in self.cells.iter() {
+            write!(f, " {},{},{}={}", x, y, t, state)?;
+        }
+        match self.end {
+            LeafEnd::Conflict((x, y, t)) => write!(f, " !{},{},{}", x, y, t),
+            LeafEnd::Trivial => write!(f, " !trivial"),
+            LeafEnd::Found => write!(f, " !found"),
+        }
+    }
+}
+
+impl Display for Proof {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        for leaf in self.leaves.iter() {
+            writeln!(f, "{}", leaf)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses coordinates in the form `x,y,t`.
+fn parse_coord(s: &str) -> Option<Coord> {
+    let mut iter = s.split(',').map(|n| n.parse().ok());
+    match (iter.next(), iter.next(), iter.next(), iter.next()) {
+        (Some(Some(x)), Some(Some(y)), Some(Some(t)), None) => Some((x, y, t)),
+        _ => None,
+    }
+}
+
+impl FromStr for ProofLeaf {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let words = s.split_whitespace().collect::<Vec<_>>();
+        let (&first, rest) = words
+            .split_first()
+            .ok_or_else(|| String::from("empty leaf"))?;
+        let keep = first
+            .parse()
+            .map_err(|_| format!("invalid number of kept cells {:?}", first))?;
+        let (&last, rest) = rest
+            .split_last()
+            .ok_or_else(|| String::from("expected the end of the leaf"))?;
+        let end = match last.strip_prefix('!') {
+            Some("trivial") => LeafEnd::Trivial,
+            Some("found") => LeafEnd::Found,
+            Some(coord) => LeafEnd::Conflict(
+                parse_coord(coord).ok_or_else(|| format!("invalid end {:?}", last))?,
+            ),
+            None => return Err(String::from("expected the end of the leaf")),
+        };
+        let cells = rest
+            .iter()
+            .map(|word| {
+                word.split_once('=')
+                    .and_then(|(coord, state)| {
+                        Some((parse_coord(coord)?, State(state.parse().ok()?)))
+                    })
+                    .ok_or_else(|| format!("invalid cell {:?}", word))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(ProofLeaf { keep, cells, end })
+    }
+}
+
+impl FromStr for Proof {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let leaves = s
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+            .map(|(i, line)| {
+                line.parse()
+                    .map_err(|e| Error::ParseProofError(format!("line {}: {}", i + 1, e)))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Proof { leaves })
+    }
+}
+
+impl<'a, R: Rule> World<'a, R> {
+    /// Records a leaf of the search tree in the proof, if `proof` is set.
+    ///
+    /// The branch to the leaf consists of the cells in the `set_stack`
+    /// that are not deduced.
+    pub(crate) fn record_leaf(&mut self, end: LeafEnd) {
+        if let Some(proof) = self.proof.as_mut() {
+            let branch = self
+                .set_stack
+                .iter()
+                .filter(|set| set.reason != Reason::Deduce)
+                .map(|set| (set.cell.coord, set.cell.state.get().unwrap()))
+                .collect::<Vec<_>>();
+            let keep = branch
+                .iter()
+                .zip(self.proof_branch.iter())
+                .take_while(|(new, old)| new == old)
+                .count();
+            proof.leaves.push(ProofLeaf {
+                keep,
+                cells: branch[keep..].to_vec(),
+                end,
+            });
+            self.proof_branch = branch;
+        }
+    }
+}
+
+/// Unit propagation over the clauses of a `Cnf`.
+///
+/// It only assigns the literals that are forced by a single clause,
+/// so it does not share any deduction with the search.
+struct Propagator {
+    /// The clauses. The first two literals of a clause are watched.
+    clauses: Vec<Vec<i32>>,
+
+    /// The clauses in which each literal is watched,
+    /// at index `lit_index(lit)`.
+    watches: Vec<Vec<usize>>,
+
+    /// The value of each variable, starting from 1.
+    values: Vec<Option<bool>>,
+
+    /// The assigned literals, in the order they are assigned.
+    trail: Vec<i32>,
+
+    /// The position in the `trail` of the next literal to propagate.
+    head: usize,
+}
+
+/// The index of a literal in `Propagator::watches`.
+fn lit_index(lit: i32) -> usize {
+    2 * lit.unsigned_abs() as usize + (lit < 0) as usize
+}
+
+impl Propagator {
+    fn new(cnf: &Cnf) -> Self {
+        let clauses = cnf.clauses().to_vec();
+        let mut watches = vec![Vec::new(); 2 * cnf.var_count() + 2];
+        for (i, clause) in clauses.iter().enumerate() {
+            for &lit in clause.iter().take(2) {
+                watches[lit_index(lit)].push(i);
+            }
+        }
+        Propagator {
+            clauses,
+            watches,
+            values: vec![None; cnf.var_count() + 1],
+            trail: Vec::new(),
+            head: 0,
+        }
+    }
+
+    fn value(&self, lit: i32) -> Option<bool> {
+        self.values[lit.unsigned_abs() as usize].map(|value| value == (lit > 0))
+    }
+
+    /// Makes a literal true.
+    ///
+    /// Returns `false` if it is already false.
+    fn assign(&mut self, lit: i32) -> bool {
+        match self.value(lit) {
+            Some(value) => value,
+            None => {
+                self.values[lit.unsigned_abs() as usize] = Some(lit > 0);
+                self.trail.push(lit);
+                true
+            }
+        }
+    }
+
+    /// Assigns the literals of the unit clauses, and propagates them.
+    ///
+    /// Returns `false` if there is a conflict.
+    fn init(&mut self) -> bool {
+        for i in 0..self.clauses.len() {
+            let consistent = match self.clauses[i][..] {
+                [] => false,
+                [lit] => self.assign(lit),
+                _ => true,
+            };
+            if !consistent {
+                return false;
+            }
+        }
+        self.propagate()
+    }
+
+    /// Assigns the last unassigned literal of each clause whose other
+    /// literals are false, until there are no such clauses.
+    ///
+    /// Returns `false` if all literals of some clause are false.
+    fn propagate(&mut self) -> bool {
+        while let Some(&lit) = self.trail.get(self.head) {
+            self.head += 1;
+            let false_lit = -lit;
+            let mut watches = mem::take(&mut self.watches[lit_index(false_lit)]);
+            let mut consistent = true;
+            let mut k = 0;
+            while k < watches.len() {
+                let i = watches[k];
+                let clause = &mut self.clauses[i];
+                if clause[0] == false_lit {
+                    clause.swap(0, 1);
+                }
+                let first = clause[0];
+                if self.values[first.unsigned_abs() as usize] == Some(first > 0) {
+                    k += 1;
+                    continue;
+                }
+                let values = &self.values;
+                let other = clause[2..]
+                    .iter()
+                    .position(|&other| values[other.unsigned_abs() as usize] != Some(other < 0));
+                if let Some(j) = other {
+                    clause.swap(1, j + 2);
+                    let new_lit = clause[1];
+                    self.watches[lit_index(new_lit)].push(i);
+                    watches.swap_remove(k);
+                    continue;
+                }
+                k += 1;
+                if !self.assign(first) {
+                    consistent = false;
+                    break;
+                }
+            }
+            self.watches[lit_index(false_lit)] = watches;
+            if !consistent {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Unassigns the literals after the first `len` ones in the `trail`.
+    fn backtrack(&mut self, len: usize) {
+        for lit in self.trail.drain(len..) {
+            self.values[lit.unsigned_abs() as usize] = None;
+        }
+        self.head = self.head.min(len);
+    }
+}
+
+/// Checks a proof against the encoding of the problem.
+///
+/// For each leaf, assigns the cells on its branch one by one,
+/// followed by unit propagation. A leaf that ends in a conflict or
+/// a rejected pattern must lead to a conflict, possibly before the
+/// last cell. A result must have all the cell variables assigned,
+/// and the assignment must extend to a model of the clauses.
+///
+/// When a branch leaves the previous one, its first remaining cell
+/// must be the cell at the same position of the previous branch,
+/// with a state that has not been tried there. When the branches
+/// below a position are finished, both states of the cell at that
+/// position must have been tried.
+fn check_proof_with_cnf(cnf: &Cnf, proof: &Proof) -> Result<ProofSummary, Error> {
+    let config = cnf.config();
+    let mut propagator = Propagator::new(cnf);
+    // For each cell on the current branch: the length of the `trail` and
+    // whether there is a conflict before it is set, its coordinates,
+    // and the states tried at its position.
+    let mut branch: Vec<(usize, bool, Coord, Vec<State>)> = Vec::new();
+    let mut conflict = !propagator.init();
+    let mut summary = ProofSummary::default();
+    let incomplete = |i: usize, coord: Coord| {
+        Error::ProofError(i, format!("not all states of cell {:?} are tried", coord))
+    };
+    if proof.leaves.is_empty() {
+        return Err(Error::ParseProofError(String::from("no leaves")));
+    }
+    for (i, leaf) in proof.leaves.iter().enumerate() {
+        let i = i + 1;
+        let err = |msg: String| Error::ProofError(i, msg);
+        if i > 1 && leaf.keep >= branch.len() || i == 1 && leaf.keep > 0 {
+            return Err(err(format!("keeps {} cells", leaf.keep)));
+        }
+        if i > 1 && leaf.cells.is_empty() {
+            return Err(err(String::from("no new cells on the branch")));
+        }
+        if let Some(&(mark, old_conflict, _, _)) = branch.get(leaf.keep) {
+            for (_, _, coord, states) in branch.drain(leaf.keep + 1..) {
+                if states.len() < 2 {
+                    return Err(incomplete(i, coord));
+                }
+            }
+            propagator.backtrack(mark);
+            conflict = old_conflict;
+        }
+        for (j, &(coord, state)) in leaf.cells.iter().enumerate() {
+            let (x, y, t) = coord;
+            if x < 0
+                || x >= config.width
+                || y < 0
+                || y >= config.height
+                || t < 0
+                || t >= config.period
+            {
+                return Err(err(format!("cell {:?} is outside the search range", coord)));
+            }
+            if state != DEAD && state != ALIVE {
+                return Err(err(format!("invalid state for cell {:?}", coord)));
+            }
+            if j == 0 && leaf.keep < branch.len() {
+                let (_, _, old_coord, states) = &mut branch[leaf.keep];
+                if coord != *old_coord {
+                    return Err(err(format!(
+                        "cell {:?} is not the cell {:?} on the previous branch",
+                        coord, old_coord
+                    )));
+                }
+                if states.contains(&state) {
+                    return Err(err(format!("state of cell {:?} is tried twice", coord)));
+                }
+                states.push(state);
+            } else {
+                branch.push((propagator.trail.len(), conflict, coord, vec![state]));
+            }
+            // The cells after a conflict do not matter.
+            if conflict {
+                continue;
+            }
+            conflict = match cnf.cell_lit(coord) {
+                None => return Err(err(format!("cell {:?} is already known", coord))),
+                Some(Lit::Const(alive)) => alive != (state == ALIVE),
+                Some(Lit::Var(var)) => {
+                    let lit = if state == ALIVE { var } else { -var };
+                    !(propagator.assign(lit) && propagator.propagate())
+                }
+            };
+        }
+        match leaf.end {
+            LeafEnd::Conflict(_) | LeafEnd::Trivial => {
+                if !conflict {
+                    return Err(err(String::from("no conflict is found")));
+                }
+            }
+            LeafEnd::Found => {
+                if conflict {
+                    return Err(err(String::from("unexpected conflict")));
+                }
+                let mut solver = Solver::new(cnf.var_count());
+                for clause in cnf.clauses() {
+                    solver.add_clause(clause);
+                }
+                for (k, &coord) in cnf.cell_vars().iter().enumerate() {
+                    let var = k as i32 + 1;
+                    match propagator.value(var) {
+                        Some(alive) => solver.add_clause(&[if alive { var } else { -var }]),
+                        None => return Err(err(format!("cell {:?} is unknown", coord))),
+                    }
+                }
+                if solver.solve(None) != Some(true) {
+                    return Err(err(String::from("the pattern is not a result")));
+                }
+                summary.results += 1;
+            }
+        }
+        summary.leaves += 1;
+        summary.depth = summary.depth.max(branch.len());
+    }
+    if let Some((_, _, coord, _)) = branch.iter().find(|(_, _, _, states)| states.len() < 2) {
+        return Err(incomplete(proof.leaves.len(), *coord));
+    }
+    Ok(summary)
+}
+
+impl Config {
+    /// Checks a proof recorded by a search with this configuration.
+    ///
+    /// The proof is checked by unit propagation over the clauses of
+    /// [`cnf`](#method.cnf), independently of the deduction of the search.
+    /// See `check_proof_with_cnf` for the details of the check.
+    ///
+    /// Returns a summary of the proof if it passes the check.
+    /// If no results are found, such patterns do not exist.
+    pub fn check_proof(&self, proof: &Proof) -> Result<ProofSummary, Error> {
+        if !self.can_record_proof() {
+            return Err(Error::ProofConfigError);
+        }
+        check_proof_with_cnf(&self.cnf()?, proof)
+    }
+}
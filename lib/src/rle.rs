@@ -0,0 +1,143 @@
+//! Writing patterns in the standard [RLE](https://conwaylife.com/wiki/Rle) format.
+
+use crate::cells::State;
+use std::fmt::Write;
+
+/// Maximal length of a line in the RLE.
+const LINE_WIDTH: usize = 70;
+
+/// A writer that encodes a pattern row by row in RLE format.
+pub(crate) struct RleWriter {
+    /// Whether the rule is a Generations rule.
+    ///
+    /// Generations rules use the multistate notation,
+    /// otherwise `b` and `o` are used.
+    is_gen: bool,
+
+    /// The finished lines.
+    lines: String,
+
+    /// The current line.
+    line: String,
+
+    /// The last token that has not been written,
+    /// and the number of times it is repeated.
+    run: Option<(String, usize)>,
+
+    /// Number of pending `$` at the end of the pattern.
+    newlines: usize,
+
+    /// Number of pending dead cells at the end of the current row.
+    deads: usize,
+}
+
+impl RleWriter {
+    /// Creates a new writer with a header line and some `#C` comment lines.
+    pub(crate) fn new(
+        width: isize,
+        height: isize,
+        rule_string: &str,
+        is_gen: bool,
+        comments: &[String],
+    ) -> Self {
+        let mut lines = String::new();
+        for comment in comments {
+            writeln!(lines, "#C {}", comment).unwrap();
+        }
+        writeln!(
+            lines,
+            "x = {}, y = {}, rule = {}",
+            width, height, rule_string
+        )
+        .unwrap();
+        RleWriter {
+            is_gen,
+            lines,
+            line: String::new(),
+            run: None,
+            newlines: 0,
+            deads: 0,
+        }
+    }
+
+    /// The symbol of a state.
+    fn symbol(&self, state: State) -> String {
+        match state {
+            State(0) => String::from(if self.is_gen { "." } else { "b" }),
+            State(1) if !self.is_gen => String::from("o"),
+            State(i) if i <= 24 => ((b'A' + i as u8 - 1) as char).to_string(),
+            State(i) => {
+                let prefix = (b'p' + ((i - 25) / 24) as u8) as char;
+                let letter = (b'A' + ((i - 25) % 24) as u8) as char;
+                format!("{}{}", prefix, letter)
+            }
+        }
+    }
+
+    /// Appends a token to the current line,
+    /// starting a new line if it would be too long.
+    fn write_token(&mut self, token: &str, count: usize) {
+        let token = if count == 1 {
+            token.to_owned()
+        } else {
+            format!("{}{}", count, token)
+        };
+        if self.line.len() + token.len() > LINE_WIDTH {
+            self.lines.push_str(&self.line);
+            self.lines.push('\n');
+            self.line.clear();
+        }
+        self.line.push_str(&token);
+    }
+
+    /// Pushes a token to the current run.
+    fn push_token(&mut self, token: String, count: usize) {
+        match self.run.take() {
+            Some((last, n)) if last == token => self.run = Some((last, n + count)),
+            Some((last, n)) => {
+                self.write_token(&last, n);
+                self.run = Some((token, count));
+            }
+            None => self.run = Some((token, count)),
+        }
+    }
+
+    /// Appends a cell to the current row.
+    pub(crate) fn push_cell(&mut self, state: State) {
+        if state == State(0) {
+            self.deads += 1;
+            return;
+        }
+        if self.newlines > 0 {
+            self.push_token(String::from("$"), self.newlines);
+            self.newlines = 0;
+        }
+        if self.deads > 0 {
+            let dead = self.symbol(State(0));
+            self.push_token(dead, self.deads);
+            self.deads = 0;
+        }
+        let token = self.symbol(state);
+        self.push_token(token, 1);
+    }
+
+    /// Ends the current row.
+    ///
+    /// Dead cells at the end of a row and empty rows at the end
+    /// of the pattern are omitted.
+    pub(crate) fn end_row(&mut self) {
+        self.deads = 0;
+        self.newlines += 1;
+    }
+
+    /// Ends the pattern, and returns the RLE.
+    pub(crate) fn finish(mut self) -> String {
+        if let Some((last, n)) = self.run.take() {
+            self.write_token(&last, n);
+        }
+        self.write_token("!", 1);
+        self.lines.push_str(&self.line);
+        self.lines.push('\n');
+        self.lines
+    }
+}
@@ -79,6 +79,11 @@ impl_rule! {
         DEAD => 0x80,
     }
 
+    fn nbhd_desc(alives) {
+        let alives = alives.iter().filter(|&&alive| alive).count() as u16;
+        (8 - alives) << 8 | alives << 4
+    }
+
     fn update_desc(cell, state, new, change_num) {
         let state_num = match state {
             Some(ALIVE) => 0x01,
@@ -86,14 +91,15 @@ impl_rule! {
             None => 0,
         };
         for &neigh in cell.nbhd.iter() {
-            let neigh = neigh.unwrap();
-            let mut desc = neigh.desc.get();
-            if new {
-                desc.0 += state_num << 4;
-            } else {
-                desc.0 -= state_num << 4;
+            if let Some(neigh) = neigh {
+                let mut desc = neigh.desc.get();
+                if new {
+                    desc.0 += state_num << 4;
+                } else {
+                    desc.0 -= state_num << 4;
+                }
+                neigh.desc.set(desc);
             }
-            neigh.desc.set(desc);
         }
     }
 
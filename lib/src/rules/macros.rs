@@ -23,6 +23,8 @@ macro_rules! impl_rule {
             DEAD => $dead_desc:expr,
         }
 
+        fn nbhd_desc($alives:ident) $nbhd_desc_body:block
+
         fn update_desc(
             $cell:ident,
             $state:ident,
@@ -46,7 +48,15 @@ macro_rules! impl_rule {
         #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
         pub struct NbhdDesc($desc_type);
 
+        /// Generates the part of a neighborhood descriptor that describes
+        /// the neighbors, when the states of all neighbors are known.
+        ///
+        /// `alives` says whether each neighbor is alive,
+        /// in the same order as `LifeCell::nbhd`.
+        fn nbhd_desc($alives: [bool; 8]) -> $desc_type $nbhd_desc_body
+
         $(#[$doc])*
+        #[derive(Clone)]
         pub struct $rule {
             /// Whether the rule contains `B0`.
             b0: bool,
@@ -82,14 +92,15 @@ macro_rules! impl_rule {
                 2
             }
 
-            fn new_desc(state: State, succ_state: State) -> Self::Desc {
+            fn new_desc(state: State, succ_state: Option<State>) -> Self::Desc {
                 let nbhd_state = match state {
                     ALIVE => $alive_desc,
                     _ => $dead_desc,
                 };
                 let succ_state = match succ_state {
-                    ALIVE => 0b01,
-                    _ => 0b10,
+                    Some(ALIVE) => 0b01,
+                    Some(_) => 0b10,
+                    None => 0,
                 };
                 let state = match state {
                     ALIVE => 0b01,
@@ -119,6 +130,19 @@ macro_rules! impl_rule {
                 $cell.desc.set(desc);
             }
 
+            fn transition(&self, state: State, nbhd: [State; 8]) -> State {
+                let mut alives = [false; 8];
+                for (alive, &neigh) in alives.iter_mut().zip(nbhd.iter()) {
+                    *alive = neigh == ALIVE;
+                }
+                let desc = nbhd_desc(alives) | if state == ALIVE { 0b01 } else { 0b10 };
+                if self.impl_table[desc as usize].contains(ImplFlags::SUCC_ALIVE) {
+                    ALIVE
+                } else {
+                    DEAD
+                }
+            }
+
             fn consistify<$a>($world: &mut World<$a, Self>, $cell_cons: CellRef<$a, Self>) -> bool {
                 let $flags = $world.rule.impl_table[$cell_cons.desc.get().0 as usize];
                 if $flags.is_empty() {
@@ -133,8 +157,10 @@ macro_rules! impl_rule {
                     } else {
                         ALIVE
                     };
-                    let succ = $cell_cons.succ.unwrap();
-                    return $world.set_cell(succ, state, Reason::Deduce);
+                    return match $cell_cons.succ {
+                        Some(succ) => $world.set_cell(succ, state, Reason::Deduce),
+                        None => true,
+                    };
                 }
                 if $flags.intersects(ImplFlags::SELF) {
                     let state = if $flags.contains(ImplFlags::SELF_DEAD) {
@@ -161,6 +187,7 @@ macro_rules! impl_rule {
         pub struct NbhdDescGen ($desc_type, Option<State>);
 
         $(#[$doc_gen])*
+        #[derive(Clone)]
         pub struct $rule_gen {
             /// Whether the rule contains `B0`.
             b0: bool,
@@ -222,9 +249,9 @@ macro_rules! impl_rule {
                 self.gen
             }
 
-            fn new_desc(state: State, succ_state: State) -> Self::Desc {
+            fn new_desc(state: State, succ_state: Option<State>) -> Self::Desc {
                 let desc = $rule::new_desc(state, succ_state);
-                NbhdDescGen(desc.0, Some(succ_state))
+                NbhdDescGen(desc.0, succ_state)
             }
 
             fn update_desc(
@@ -249,6 +276,32 @@ macro_rules! impl_rule {
                 $cell.desc.set(desc);
             }
 
+            fn transition(&self, state: State, nbhd: [State; 8]) -> State {
+                let mut alives = [false; 8];
+                for (alive, &neigh) in alives.iter_mut().zip(nbhd.iter()) {
+                    *alive = neigh == ALIVE;
+                }
+                match state {
+                    DEAD => {
+                        let desc = nbhd_desc(alives) | 0b10;
+                        if self.impl_table[desc as usize].contains(ImplFlags::SUCC_ALIVE) {
+                            ALIVE
+                        } else {
+                            DEAD
+                        }
+                    }
+                    ALIVE => {
+                        let desc = nbhd_desc(alives) | 0b01;
+                        if self.impl_table[desc as usize].contains(ImplFlags::SUCC_ALIVE) {
+                            ALIVE
+                        } else {
+                            State(2 % self.gen)
+                        }
+                    }
+                    State(i) => State((i + 1) % self.gen),
+                }
+            }
+
             fn consistify<$a_gen>(
                 $world_gen: &mut World<$a_gen, Self>,
                 $cell_cons_gen: CellRef<$a_gen, Self>,
@@ -269,8 +322,10 @@ macro_rules! impl_rule {
                             } else {
                                 ALIVE
                             };
-                            let succ = $cell_cons_gen.succ.unwrap();
-                            return $world_gen.set_cell(succ, state, Reason::Deduce);
+                            return match $cell_cons_gen.succ {
+                                Some(succ) => $world_gen.set_cell(succ, state, Reason::Deduce),
+                                None => true,
+                            };
                         }
                     }
                     Some(ALIVE) => {
@@ -285,8 +340,10 @@ macro_rules! impl_rule {
                             } else {
                                 ALIVE
                             };
-                            let succ = $cell_cons_gen.succ.unwrap();
-                            return $world_gen.set_cell(succ, state, Reason::Deduce);
+                            return match $cell_cons_gen.succ {
+                                Some(succ) => $world_gen.set_cell(succ, state, Reason::Deduce),
+                                None => true,
+                            };
                         }
                     }
                     Some(State(i)) => {
@@ -294,8 +351,12 @@ macro_rules! impl_rule {
                         if let Some(State(j)) = desc.1 {
                             return j == (i + 1) % gen;
                         } else {
-                            let succ = $cell_cons_gen.succ.unwrap();
-                            return $world_gen.set_cell(succ, State((i + 1) % gen), Reason::Deduce);
+                            return match $cell_cons_gen.succ {
+                                Some(succ) => {
+                                    $world_gen.set_cell(succ, State((i + 1) % gen), Reason::Deduce)
+                                }
+                                None => true,
+                            };
                         }
                     }
                     None => match desc.1 {
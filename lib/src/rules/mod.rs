@@ -16,7 +16,7 @@ pub use life::{Life, LifeGen};
 pub use ntlife::{NtLife, NtLifeGen};
 
 /// A cellular automaton rule.
-pub trait Rule: Sized {
+pub trait Rule: Sized + Clone {
     /// The type of neighborhood descriptor of the rule.
     ///
     /// It describes the states of the successor and neighbors of a cell,
@@ -37,7 +37,9 @@ pub trait Rule: Sized {
 
     /// Generates a neighborhood descriptor which says that all neighboring
     /// cells have states `state`, and the successor has state `succ_state`.
-    fn new_desc(state: State, succ_state: State) -> Self::Desc;
+    ///
+    /// `None` means that the state of the successor is unknown.
+    fn new_desc(state: State, succ_state: Option<State>) -> Self::Desc;
 
     /// Updates the neighborhood descriptors of all neighbors and the predecessor
     /// when the state of one cell is changed.
@@ -46,6 +48,12 @@ pub trait Rule: Sized {
     /// the old state when `new` is false.
     fn update_desc(cell: CellRef<Self>, state: Option<State>, new: bool);
 
+    /// Computes the state of a cell in the next generation,
+    /// given its state and the states of its eight neighbors.
+    ///
+    /// The neighbors are in the same order as `LifeCell::nbhd`.
+    fn transition(&self, state: State, nbhd: [State; 8]) -> State;
+
     /// Consistifies a cell.
     ///
     /// Examines the state and the neighborhood descriptor of the cell,
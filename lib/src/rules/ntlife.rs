@@ -78,6 +78,16 @@ impl_rule! {
         DEAD => 0xff00,
     }
 
+    fn nbhd_desc(alives) {
+        let alives = alives
+            .iter()
+            .rev()
+            .enumerate()
+            .filter(|(_, &alive)| alive)
+            .fold(0, |acc, (i, _)| acc | 1 << i);
+        (0xff & !alives) << 12 | alives << 4
+    }
+
     fn update_desc(cell, state, _new, change_num) {
         let nbhd_change_num = match state {
             Some(ALIVE) => 0x0001,
@@ -85,10 +95,11 @@ impl_rule! {
             _ => 0x0000,
         };
         for (i, &neigh) in cell.nbhd.iter().rev().enumerate() {
-            let neigh = neigh.unwrap();
-            let mut desc = neigh.desc.get();
-            desc.0 ^= nbhd_change_num << i << 4;
-            neigh.desc.set(desc);
+            if let Some(neigh) = neigh {
+                let mut desc = neigh.desc.get();
+                desc.0 ^= nbhd_change_num << i << 4;
+                neigh.desc.set(desc);
+            }
         }
     }
 
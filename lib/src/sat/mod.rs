@@ -0,0 +1,317 @@
+//! Solving the search problem with an embedded SAT solver.
+
+pub(crate) mod solver;
+
+use crate::{
+    cells::{Coord, State},
+    cnf::{two_state_rule, Cnf},
+    config::{Config, NewState},
+    error::Error,
+    explain::Deduction,
+    pattern::Pattern,
+    proof::Proof,
+    rules::{NtLife, Rule},
+    search::Status,
+    traits::Search,
+    world::World,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use solver::Solver;
+
+#[cfg(feature = "serialize")]
+use crate::save::WorldSer;
+
+/// A search engine which encodes the problem with
+/// [`Config::cnf`](struct.Config.html#method.cnf)
+/// and solves it with an embedded CDCL SAT solver.
+///
+/// It is created by [`Config::world`](struct.Config.html#method.world)
+/// when the engine in the configuration is `Engine::Sat`,
+/// and used through the same `Search` trait as `World`.
+///
+/// Before the first result is found, and after there are no more results,
+/// only the known cells are known. After a result is found, all cells are
+/// known. Each result is excluded from the later searches, so calling
+/// `search` again gives a different result.
+pub struct SatWorld {
+    /// World configuration.
+    config: Config,
+
+    /// The encoded problem.
+    cnf: Cnf,
+
+    /// The solver.
+    solver: Solver,
+
+    /// The clauses excluding the results found so far.
+    blocks: Vec<Vec<i32>>,
+
+    /// The world shown by the `Search` methods.
+    ///
+    /// Either the initial world or the last result.
+    world: World<'static, NtLife>,
+
+    /// Number of conflicts of the solvers replaced by `set_max_cell_count`.
+    old_conflicts: u64,
+
+    /// The value of the objective in the last result,
+    /// when `reduce_max` is set.
+    best: Option<usize>,
+}
+
+impl SatWorld {
+    /// Creates a new `SatWorld` from the configuration.
+    ///
+    /// Returns an error for Generations rules.
+    pub fn new(config: &Config) -> Result<Self, Error> {
+        let cnf = config.cnf()?;
+        let solver = SatWorld::solver(&cnf, &[]);
+        let world = SatWorld::initial_world(config)?;
+        Ok(SatWorld {
+            config: config.clone(),
+            cnf,
+            solver,
+            blocks: Vec::new(),
+            world,
+            old_conflicts: 0,
+            best: None,
+        })
+    }
+
+    /// Restores a search from a save, with the results found so far
+    /// excluded by `blocks`.
+    #[cfg(feature = "serialize")]
+    pub(crate) fn restore(
+        config: &Config,
+        blocks: Vec<Vec<i32>>,
+        conflicts: u64,
+        best: Option<usize>,
+    ) -> Result<Self, Error> {
+        let cell_count = config.cnf()?.cell_vars().len() as i32;
+        if blocks
+            .iter()
+            .flatten()
+            .any(|&lit| lit == 0 || lit.abs() > cell_count)
+        {
+            return Err(Error::SaveBlockError);
+        }
+        let mut world = SatWorld::new(config)?;
+        for block in &blocks {
+            world.solver.add_clause(block);
+        }
+        world.blocks = blocks;
+        world.old_conflicts = conflicts;
+        world.best = best;
+        Ok(world)
+    }
+
+    /// The clauses excluding the results found so far.
+    #[cfg(feature = "serialize")]
+    pub(crate) fn blocks(&self) -> &[Vec<i32>] {
+        &self.blocks
+    }
+
+    /// The world with only the known cells.
+    fn initial_world(config: &Config) -> Result<World<'static, NtLife>, Error> {
+        World::new(config, two_state_rule(&config.rule_string)?).init_known_cells()
+    }
+
+    /// Creates a solver for the problem, with some results excluded.
+    ///
+    /// The first decision on each cell follows `new_state`.
+    fn solver(cnf: &Cnf, blocks: &[Vec<i32>]) -> Solver {
+        let mut solver = Solver::new(cnf.var_count());
+        for clause in cnf.clauses().iter().chain(blocks) {
+            solver.add_clause(clause);
+        }
+        let b0 = two_state_rule(&cnf.config().rule_string).unwrap().has_b0();
+        let config = cnf.config();
+        let mut rng = config
+            .seed
+            .map_or_else(StdRng::from_entropy, StdRng::seed_from_u64);
+        let density = config.density.map_or(50, |density| density.min(100));
+        for (i, &(_, _, t)) in cnf.cell_vars().iter().enumerate() {
+            let background = b0 && t % 2 == 1;
+            let phase = match config.new_state {
+                NewState::ChooseDead => background,
+                NewState::ChooseAlive => !background,
+                NewState::Random => rng.gen_ratio(density, 100),
+            };
+            solver.set_phase(i + 1, phase);
+        }
+        solver
+    }
+
+    /// The search function.
+    ///
+    /// `max_step` limits the number of decisions and conflicts of the solver.
+    ///
+    /// If `reduce_max` is set, each result tightens the bound
+    /// of the objective, whose clauses are added to the solver.
+    pub fn search(&mut self, max_step: Option<u64>) -> Status {
+        if matches!(self.best, Some(best) if self.config.objective.is_optimum(best)) {
+            self.world = SatWorld::initial_world(&self.config).unwrap();
+            return Status::None;
+        }
+        match self.solver.solve(max_step) {
+            None => Status::Searching,
+            Some(false) => {
+                self.world = SatWorld::initial_world(&self.config).unwrap();
+                Status::None
+            }
+            Some(true) => {
+                let solver = &self.solver;
+                self.world = self
+                    .cnf
+                    .world_from_model(|var| solver.value(var))
+                    .expect("the model of the solver is not a solution");
+                let block = (1..=self.cnf.cell_vars().len())
+                    .map(|var| {
+                        if self.solver.value(var) == Some(true) {
+                            -(var as i32)
+                        } else {
+                            var as i32
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                self.solver.add_clause(&block);
+                self.blocks.push(block);
+                if self.config.reduce_max {
+                    let value = self.world.objective_value();
+                    self.config.tighten_bound(value);
+                    self.best = Some(value);
+                    self.add_bounds();
+                }
+                Status::Found
+            }
+        }
+    }
+
+    /// Set the max cell counts.
+    ///
+    /// A smaller max cell count is added to the solver. Otherwise the problem
+    /// is encoded again, so the learnt clauses are lost, but the results found
+    /// so far are still excluded.
+    pub fn set_max_cell_count(&mut self, max_cell_count: Option<usize>) {
+        let old_max = self.config.max_cell_count;
+        self.config.max_cell_count = max_cell_count;
+        match (old_max, max_cell_count) {
+            (old_max, max) if old_max == max => (),
+            (_, None) => self.encode_again(),
+            (Some(old_max), Some(max)) if max > old_max => self.encode_again(),
+            _ => self.add_bounds(),
+        }
+    }
+
+    /// Adds the clauses for the tightened bounds in the configuration
+    /// to the solver.
+    fn add_bounds(&mut self) {
+        match self.cnf.tighten(&self.config) {
+            Ok(old_count) => {
+                self.solver.set_var_count(self.cnf.var_count());
+                for clause in &self.cnf.clauses()[old_count..] {
+                    self.solver.add_clause(clause);
+                }
+            }
+            // The known cells already break the bounds.
+            Err(_) => self.solver.add_clause(&[]),
+        }
+    }
+
+    /// Encodes the problem again after the configuration changes.
+    fn encode_again(&mut self) {
+        self.old_conflicts += self.solver.conflicts;
+        self.cnf = self.config.cnf().unwrap();
+        self.solver = SatWorld::solver(&self.cnf, &self.blocks);
+    }
+}
+
+/// The `Search` methods other than `search` are those of the shown world.
+impl Search for SatWorld {
+    fn search(&mut self, max_step: Option<u64>) -> Status {
+        self.search(max_step)
+    }
+
+    fn get_cell_state(&self, coord: Coord) -> Result<Option<State>, Error> {
+        self.world.get_cell_state(coord)
+    }
+
+    fn pattern(&self, t: isize) -> Pattern {
+        self.world.pattern(t)
+    }
+
+    fn config(&self) -> &Config {
+        &self.config
+    }
+
+    fn is_gen_rule(&self) -> bool {
+        false
+    }
+
+    fn cell_count_gen(&self, t: isize) -> usize {
+        self.world.cell_count[t as usize]
+    }
+
+    fn cell_count(&self) -> usize {
+        self.world.cell_count()
+    }
+
+    fn conflicts(&self) -> u64 {
+        self.old_conflicts + self.solver.conflicts
+    }
+
+    /// Number of decisions on the cell variables on the current trail
+    /// of the solver.
+    ///
+    /// Decisions on the auxiliary variables of the encoding are not counted,
+    /// so that it measures the same thing as the depth of the backtracking
+    /// search. But the solver learns clauses and backjumps, so the two depths
+    /// of the same problem still differ.
+    fn depth(&self) -> usize {
+        self.solver.decisions_up_to(self.cnf.cell_vars().len())
+    }
+
+    /// The partial assignments of the solver are not recorded.
+    fn deepest_pattern(&self, _t: isize) -> Option<Pattern> {
+        None
+    }
+
+    fn optimum(&self) -> Option<usize> {
+        self.best
+    }
+
+    /// Proofs are not recorded by the SAT solver.
+    fn proof(&self) -> Option<&Proof> {
+        None
+    }
+
+    fn take_proof(&mut self) -> Option<Proof> {
+        None
+    }
+
+    /// The SAT solver does not record why a cell has its state.
+    fn explain(&self, _coord: Coord) -> Result<Vec<Deduction>, Error> {
+        Err(Error::ExplainError)
+    }
+
+    fn explain_root_conflict(&self) -> Option<&[Deduction]> {
+        None
+    }
+
+    fn conflict_map(&self, _t: isize) -> Option<Vec<Vec<u64>>> {
+        None
+    }
+
+    fn set_max_cell_count(&mut self, max_cell_count: Option<usize>) {
+        self.set_max_cell_count(max_cell_count)
+    }
+
+    fn verify(&self) -> Result<(), Error> {
+        self.world.verify()
+    }
+
+    #[cfg(feature = "serialize")]
+    fn ser(&self) -> WorldSer {
+        self.ser()
+    }
+}
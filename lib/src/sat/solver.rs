@@ -0,0 +1,855 @@
+//! A small CDCL SAT solver.
+//!
+//! It uses two watched literals, first-UIP clause learning,
+//! VSIDS decision heuristic with phase saving, Luby restarts,
+//! and reduction of learnt clauses by their LBD.
+
+use std::ops::Not;
+
+/// A literal.
+///
+/// Variable `v` (0-indexed) is `2 * v` when positive, `2 * v + 1` when negative.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Lit(u32);
+
+impl Lit {
+    fn new(var: usize, positive: bool) -> Self {
+        Lit((var as u32) << 1 | !positive as u32)
+    }
+
+    /// Converts a literal in DIMACS format.
+    fn from_dimacs(lit: i32) -> Self {
+        Lit::new(lit.unsigned_abs() as usize - 1, lit > 0)
+    }
+
+    fn var(self) -> usize {
+        (self.0 >> 1) as usize
+    }
+
+    fn positive(self) -> bool {
+        self.0 & 1 == 0
+    }
+
+    fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl Not for Lit {
+    type Output = Lit;
+
+    fn not(self) -> Self::Output {
+        Lit(self.0 ^ 1)
+    }
+}
+
+/// The value of a variable or a literal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Value {
+    False,
+    True,
+    Undef,
+}
+
+/// The value of a literal, given the values of the variables.
+fn value_of(values: &[Value], lit: Lit) -> Value {
+    match (values[lit.var()], lit.positive()) {
+        (Value::Undef, _) => Value::Undef,
+        (Value::True, true) | (Value::False, false) => Value::True,
+        _ => Value::False,
+    }
+}
+
+/// A clause in the watch list of a literal.
+#[derive(Clone, Copy)]
+struct Watch {
+    clause: usize,
+    /// Another literal in the clause. If it is true, the clause is
+    /// satisfied, and can be skipped without reading it.
+    blocker: Lit,
+}
+
+/// A clause.
+///
+/// The first two literals are watched. If the clause is the reason
+/// of an assignment, the first literal is the assigned one.
+struct Clause {
+    lits: Vec<Lit>,
+    learnt: bool,
+    /// Literal block distance, i.e., the number of different
+    /// decision levels in the clause when it is learnt.
+    lbd: usize,
+}
+
+/// A binary heap of variables ordered by their activities.
+///
+/// Variables with the same activity are ordered by their indices,
+/// so that the cell variables, which come first in the encoding,
+/// are decided before the auxiliary ones at the beginning.
+#[derive(Default)]
+struct VarHeap {
+    heap: Vec<usize>,
+    /// The position of each variable in the heap.
+    indices: Vec<Option<usize>>,
+}
+
+impl VarHeap {
+    /// Whether `a` should be popped before `b`.
+    fn before(a: usize, b: usize, activity: &[f64]) -> bool {
+        activity[a] > activity[b] || (activity[a] == activity[b] && a < b)
+    }
+
+    fn contains(&self, var: usize) -> bool {
+        self.indices[var].is_some()
+    }
+
+    fn push(&mut self, var: usize, activity: &[f64]) {
+        if !self.contains(var) {
+            self.indices[var] = Some(self.heap.len());
+            self.heap.push(var);
+            self.up(self.heap.len() - 1, activity);
+        }
+    }
+
+    fn pop(&mut self, activity: &[f64]) -> Option<usize> {
+        let var = *self.heap.first()?;
+        let last = self.heap.pop().unwrap();
+        self.indices[var] = None;
+        if !self.heap.is_empty() {
+            self.heap[0] = last;
+            self.indices[last] = Some(0);
+            self.down(0, activity);
+        }
+        Some(var)
+    }
+
+    /// Moves a variable up after its activity is increased.
+    fn increase(&mut self, var: usize, activity: &[f64]) {
+        if let Some(i) = self.indices[var] {
+            self.up(i, activity);
+        }
+    }
+
+    fn up(&mut self, mut i: usize, activity: &[f64]) {
+        let var = self.heap[i];
+        while i > 0 {
+            let parent = (i - 1) / 2;
+            if !VarHeap::before(var, self.heap[parent], activity) {
+                break;
+            }
+            self.heap[i] = self.heap[parent];
+            self.indices[self.heap[i]] = Some(i);
+            i = parent;
+        }
+        self.heap[i] = var;
+        self.indices[var] = Some(i);
+    }
+
+    fn down(&mut self, mut i: usize, activity: &[f64]) {
+        let var = self.heap[i];
+        loop {
+            let left = 2 * i + 1;
+            if left >= self.heap.len() {
+                break;
+            }
+            let right = left + 1;
+            let child = if right < self.heap.len()
+                && VarHeap::before(self.heap[right], self.heap[left], activity)
+            {
+                right
+            } else {
+                left
+            };
+            if !VarHeap::before(self.heap[child], var, activity) {
+                break;
+            }
+            self.heap[i] = self.heap[child];
+            self.indices[self.heap[i]] = Some(i);
+            i = child;
+        }
+        self.heap[i] = var;
+        self.indices[var] = Some(i);
+    }
+}
+
+/// The `i`-th element (0-indexed) of the Luby sequence `1, 1, 2, 1, 1, 2, 4, ...`.
+pub(crate) fn luby(mut i: u64) -> u64 {
+    let mut size = 1;
+    let mut power = 1;
+    while size < i + 1 {
+        size = 2 * size + 1;
+        power *= 2;
+    }
+    while size - 1 != i {
+        size = (size - 1) / 2;
+        power /= 2;
+        i %= size;
+    }
+    power
+}
+
+/// Number of conflicts in the first restart interval.
+const RESTART_BASE: u64 = 100;
+
+/// Decay factor of the variable activities.
+const VAR_DECAY: f64 = 0.95;
+
+/// A CDCL SAT solver.
+///
+/// The solver is incremental: clauses can be added after a model is found,
+/// and the search can be interrupted and continued.
+pub(crate) struct Solver {
+    clauses: Vec<Clause>,
+    /// The clauses in which each literal is watched.
+    watches: Vec<Vec<Watch>>,
+    values: Vec<Value>,
+    levels: Vec<usize>,
+    /// The clause that implied each variable.
+    reasons: Vec<Option<usize>>,
+    trail: Vec<Lit>,
+    /// The position in the trail where each decision level starts.
+    trail_lim: Vec<usize>,
+    /// The position in the trail of the next literal to propagate.
+    qhead: usize,
+    activity: Vec<f64>,
+    var_inc: f64,
+    heap: VarHeap,
+    /// The last value of each variable, used as the next decision.
+    phases: Vec<bool>,
+    seen: Vec<bool>,
+    /// Number of conflicts.
+    pub(crate) conflicts: u64,
+    restarts: u64,
+    /// Number of conflicts when the next restart happens.
+    next_restart: u64,
+    learnt_count: usize,
+    max_learnts: usize,
+    /// Whether the clauses are known to be unsatisfiable.
+    unsat: bool,
+}
+
+impl Solver {
+    /// Creates a new solver with `var_count` variables and no clauses.
+    pub(crate) fn new(var_count: usize) -> Self {
+        let mut heap = VarHeap {
+            heap: Vec::with_capacity(var_count),
+            indices: vec![None; var_count],
+        };
+        let activity = vec![0.0; var_count];
+        for var in 0..var_count {
+            heap.push(var, &activity);
+        }
+        Solver {
+            clauses: Vec::new(),
+            watches: vec![Vec::new(); 2 * var_count],
+            values: vec![Value::Undef; var_count],
+            levels: vec![0; var_count],
+            reasons: vec![None; var_count],
+            trail: Vec::with_capacity(var_count),
+            trail_lim: Vec::new(),
+            qhead: 0,
+            activity,
+            var_inc: 1.0,
+            heap,
+            phases: vec![false; var_count],
+            seen: vec![false; var_count],
+            conflicts: 0,
+            restarts: 0,
+            next_restart: RESTART_BASE,
+            learnt_count: 0,
+            max_learnts: 10000,
+            unsat: false,
+        }
+    }
+
+    /// Adds new variables, so that there are `var_count` variables.
+    pub(crate) fn set_var_count(&mut self, var_count: usize) {
+        let old_count = self.values.len();
+        if var_count <= old_count {
+            return;
+        }
+        self.watches.resize(2 * var_count, Vec::new());
+        self.values.resize(var_count, Value::Undef);
+        self.levels.resize(var_count, 0);
+        self.reasons.resize(var_count, None);
+        self.activity.resize(var_count, 0.0);
+        self.heap.indices.resize(var_count, None);
+        for var in old_count..var_count {
+            self.heap.push(var, &self.activity);
+        }
+        self.phases.resize(var_count, false);
+        self.seen.resize(var_count, false);
+    }
+
+    /// Sets the value tried first when deciding a variable (1-indexed).
+    pub(crate) fn set_phase(&mut self, var: usize, phase: bool) {
+        self.phases[var - 1] = phase;
+    }
+
+    /// The value of a variable (1-indexed) in the current assignment.
+    pub(crate) fn value(&self, var: usize) -> Option<bool> {
+        match self.values[var - 1] {
+            Value::True => Some(true),
+            Value::False => Some(false),
+            Value::Undef => None,
+        }
+    }
+
+    fn lit_value(&self, lit: Lit) -> Value {
+        value_of(&self.values, lit)
+    }
+
+    /// Number of decisions on the current trail.
+    fn decision_level(&self) -> usize {
+        self.trail_lim.len()
+    }
+
+    /// Number of decisions on the current trail
+    /// on the variables (1-indexed) up to `max_var`.
+    pub(crate) fn decisions_up_to(&self, max_var: usize) -> usize {
+        self.trail_lim
+            .iter()
+            .filter(|&&start| self.trail[start].var() < max_var)
+            .count()
+    }
+
+    fn enqueue(&mut self, lit: Lit, reason: Option<usize>) {
+        let var = lit.var();
+        self.values[var] = if lit.positive() {
+            Value::True
+        } else {
+            Value::False
+        };
+        self.levels[var] = self.decision_level();
+        self.reasons[var] = reason;
+        self.trail.push(lit);
+    }
+
+    fn cancel_until(&mut self, level: usize) {
+        if self.decision_level() <= level {
+            return;
+        }
+        let start = self.trail_lim[level];
+        for i in (start..self.trail.len()).rev() {
+            let lit = self.trail[i];
+            let var = lit.var();
+            self.phases[var] = lit.positive();
+            self.values[var] = Value::Undef;
+            self.reasons[var] = None;
+            self.heap.push(var, &self.activity);
+        }
+        self.trail.truncate(start);
+        self.trail_lim.truncate(level);
+        self.qhead = start;
+    }
+
+    /// Watches the first two literals of a clause.
+    fn watch(&mut self, index: usize) {
+        let (first, second) = (self.clauses[index].lits[0], self.clauses[index].lits[1]);
+        self.watches[first.index()].push(Watch {
+            clause: index,
+            blocker: second,
+        });
+        self.watches[second.index()].push(Watch {
+            clause: index,
+            blocker: first,
+        });
+    }
+
+    fn attach(&mut self, clause: Clause) -> usize {
+        let index = self.clauses.len();
+        if clause.learnt {
+            self.learnt_count += 1;
+        }
+        self.clauses.push(clause);
+        self.watch(index);
+        index
+    }
+
+    /// Adds a clause in DIMACS format.
+    ///
+    /// The current assignment is dropped.
+    pub(crate) fn add_clause(&mut self, clause: &[i32]) {
+        if self.unsat {
+            return;
+        }
+        self.cancel_until(0);
+        let mut lits: Vec<Lit> = Vec::with_capacity(clause.len());
+        for &lit in clause {
+            let lit = Lit::from_dimacs(lit);
+            match self.lit_value(lit) {
+                Value::True => return,
+                Value::False => (),
+                Value::Undef => {
+                    if lits.contains(&!lit) {
+                        return;
+                    }
+                    if !lits.contains(&lit) {
+                        lits.push(lit);
+                    }
+                }
+            }
+        }
+        match lits.len() {
+            0 => self.unsat = true,
+            1 => {
+                self.enqueue(lits[0], None);
+                if self.propagate().is_some() {
+                    self.unsat = true;
+                }
+            }
+            _ => {
+                self.attach(Clause {
+                    lits,
+                    learnt: false,
+                    lbd: 0,
+                });
+            }
+        }
+    }
+
+    /// Propagates the assignments in the trail.
+    ///
+    /// Returns the conflicting clause if there is a conflict.
+    fn propagate(&mut self) -> Option<usize> {
+        while self.qhead < self.trail.len() {
+            let false_lit = !self.trail[self.qhead];
+            self.qhead += 1;
+            let mut watches = std::mem::take(&mut self.watches[false_lit.index()]);
+            let mut conflict = None;
+            let mut i = 0;
+            let mut j = 0;
+            while i < watches.len() {
+                let watch = watches[i];
+                i += 1;
+                if value_of(&self.values, watch.blocker) == Value::True {
+                    watches[j] = watch;
+                    j += 1;
+                    continue;
+                }
+                let lits = &mut self.clauses[watch.clause].lits;
+                if lits[0] == false_lit {
+                    lits.swap(0, 1);
+                }
+                let first = lits[0];
+                let first_value = value_of(&self.values, first);
+                let kept = Watch {
+                    clause: watch.clause,
+                    blocker: first,
+                };
+                if first_value == Value::True {
+                    watches[j] = kept;
+                    j += 1;
+                    continue;
+                }
+                let values = &self.values;
+                let new_watch = lits[2..]
+                    .iter()
+                    .position(|&lit| value_of(values, lit) != Value::False);
+                if let Some(k) = new_watch {
+                    lits.swap(1, k + 2);
+                    self.watches[lits[1].index()].push(kept);
+                    continue;
+                }
+                watches[j] = kept;
+                j += 1;
+                if first_value == Value::False {
+                    conflict = Some(watch.clause);
+                    while i < watches.len() {
+                        watches[j] = watches[i];
+                        j += 1;
+                        i += 1;
+                    }
+                } else {
+                    self.enqueue(first, Some(watch.clause));
+                }
+            }
+            watches.truncate(j);
+            self.watches[false_lit.index()] = watches;
+            if conflict.is_some() {
+                self.qhead = self.trail.len();
+                return conflict;
+            }
+        }
+        None
+    }
+
+    fn bump(&mut self, var: usize) {
+        self.activity[var] += self.var_inc;
+        if self.activity[var] > 1e100 {
+            for activity in self.activity.iter_mut() {
+                *activity *= 1e-100;
+            }
+            self.var_inc *= 1e-100;
+        }
+        self.heap.increase(var, &self.activity);
+    }
+
+    /// Analyzes a conflict, and returns the learnt clause
+    /// and the level to backtrack to.
+    ///
+    /// The first literal of the learnt clause is the asserting literal,
+    /// and the second one has the highest level among the rest.
+    fn analyze(&mut self, mut conflict: usize) -> (Vec<Lit>, usize) {
+        let mut learnt = vec![Lit(0)];
+        let mut path = 0;
+        let mut index = self.trail.len();
+        let mut skip_first = false;
+        let level = self.decision_level();
+        loop {
+            for k in skip_first as usize..self.clauses[conflict].lits.len() {
+                let lit = self.clauses[conflict].lits[k];
+                let var = lit.var();
+                if !self.seen[var] && self.levels[var] > 0 {
+                    self.seen[var] = true;
+                    self.bump(var);
+                    if self.levels[var] >= level {
+                        path += 1;
+                    } else {
+                        learnt.push(lit);
+                    }
+                }
+            }
+            loop {
+                index -= 1;
+                if self.seen[self.trail[index].var()] {
+                    break;
+                }
+            }
+            let lit = self.trail[index];
+            self.seen[lit.var()] = false;
+            path -= 1;
+            if path == 0 {
+                learnt[0] = !lit;
+                break;
+            }
+            conflict = self.reasons[lit.var()].unwrap();
+            skip_first = true;
+        }
+
+        // Removes the literals implied by the other literals.
+        let mut minimized = vec![learnt[0]];
+        for &lit in &learnt[1..] {
+            let redundant = match self.reasons[lit.var()] {
+                Some(reason) => self.clauses[reason].lits[1..]
+                    .iter()
+                    .all(|l| self.seen[l.var()] || self.levels[l.var()] == 0),
+                None => false,
+            };
+            if !redundant {
+                minimized.push(lit);
+            }
+        }
+        for lit in &learnt[1..] {
+            self.seen[lit.var()] = false;
+        }
+
+        let mut backtrack_level = 0;
+        if minimized.len() > 1 {
+            let mut max = 1;
+            for k in 2..minimized.len() {
+                if self.levels[minimized[k].var()] > self.levels[minimized[max].var()] {
+                    max = k;
+                }
+            }
+            minimized.swap(1, max);
+            backtrack_level = self.levels[minimized[1].var()];
+        }
+        (minimized, backtrack_level)
+    }
+
+    /// Removes about half of the learnt clauses with the highest LBD.
+    ///
+    /// Must be called at decision level 0.
+    fn reduce(&mut self) {
+        let mut lbds = self
+            .clauses
+            .iter()
+            .filter(|clause| clause.learnt && clause.lbd > 2)
+            .map(|clause| clause.lbd)
+            .collect::<Vec<_>>();
+        if lbds.is_empty() {
+            return;
+        }
+        lbds.sort_unstable();
+        let threshold = lbds[lbds.len() / 2];
+        let mut removed = 0;
+        self.clauses.retain(|clause| {
+            let keep = !clause.learnt || clause.lbd < threshold || clause.lbd <= 2;
+            if !keep {
+                removed += 1;
+            }
+            keep
+        });
+        if removed == 0 {
+            return;
+        }
+        self.learnt_count -= removed;
+        for reason in self.reasons.iter_mut() {
+            *reason = None;
+        }
+        for watches in self.watches.iter_mut() {
+            watches.clear();
+        }
+        for index in 0..self.clauses.len() {
+            self.watch(index);
+        }
+    }
+
+    /// Searches for a model.
+    ///
+    /// Returns `Some(true)` if a model is found, which can be read by `value`,
+    /// `Some(false)` if there is no model, and `None` if the number of
+    /// decisions and conflicts exceeds `max_step`.
+    pub(crate) fn solve(&mut self, max_step: Option<u64>) -> Option<bool> {
+        if self.unsat {
+            return Some(false);
+        }
+        let mut step = 0;
+        loop {
+            if let Some(conflict) = self.propagate() {
+                self.conflicts += 1;
+                step += 1;
+                if self.decision_level() == 0 {
+                    self.unsat = true;
+                    return Some(false);
+                }
+                let levels = self.trail_lim.len();
+                let (learnt, backtrack_level) = self.analyze(conflict);
+                self.cancel_until(backtrack_level);
+                if learnt.len() == 1 {
+                    self.enqueue(learnt[0], None);
+                } else {
+                    let mut seen_levels = vec![false; levels + 1];
+                    let lbd = learnt
+                        .iter()
+                        .filter(|lit| {
+                            !std::mem::replace(&mut seen_levels[self.levels[lit.var()]], true)
+                        })
+                        .count();
+                    let asserting = learnt[0];
+                    let index = self.attach(Clause {
+                        lits: learnt,
+                        learnt: true,
+                        lbd,
+                    });
+                    self.enqueue(asserting, Some(index));
+                }
+                self.var_inc /= VAR_DECAY;
+            } else {
+                if self.conflicts >= self.next_restart {
+                    self.restarts += 1;
+                    self.next_restart = self.conflicts + RESTART_BASE * luby(self.restarts);
+                    self.cancel_until(0);
+                    if self.learnt_count > self.max_learnts {
+                        self.reduce();
+                        self.max_learnts += self.max_learnts / 10;
+                    }
+                }
+                if matches!(max_step, Some(max) if step > max) {
+                    return None;
+                }
+                step += 1;
+                let var = loop {
+                    match self.heap.pop(&self.activity) {
+                        Some(var) if self.values[var] == Value::Undef => break Some(var),
+                        Some(_) => (),
+                        None => break None,
+                    }
+                };
+                match var {
+                    Some(var) => {
+                        self.trail_lim.push(self.trail.len());
+                        let lit = Lit::new(var, self.phases[var]);
+                        self.enqueue(lit, None);
+                    }
+                    None => return Some(true),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    /// A random CNF where each clause has three different variables.
+    fn random_cnf(rng: &mut StdRng, var_count: usize, clause_count: usize) -> Vec<Vec<i32>> {
+        (0..clause_count)
+            .map(|_| {
+                let mut clause: Vec<i32> = Vec::new();
+                while clause.len() < 3 {
+                    let var = rng.gen_range(1, var_count as i32 + 1);
+                    if clause.iter().all(|lit| lit.abs() != var) {
+                        clause.push(if rng.gen() { var } else { -var });
+                    }
+                }
+                clause
+            })
+            .collect()
+    }
+
+    /// The pigeonhole problem, which puts `n + 1` pigeons into `n` holes.
+    fn pigeonhole(n: usize) -> Vec<Vec<i32>> {
+        let var = |pigeon: usize, hole: usize| (pigeon * n + hole + 1) as i32;
+        let mut clauses = (0..=n)
+            .map(|pigeon| (0..n).map(|hole| var(pigeon, hole)).collect())
+            .collect::<Vec<_>>();
+        for hole in 0..n {
+            for i in 0..=n {
+                for j in 0..i {
+                    clauses.push(vec![-var(i, hole), -var(j, hole)]);
+                }
+            }
+        }
+        clauses
+    }
+
+    fn satisfies(values: &[bool], clauses: &[Vec<i32>]) -> bool {
+        clauses.iter().all(|clause| {
+            clause
+                .iter()
+                .any(|&lit| values[lit.unsigned_abs() as usize - 1] == (lit > 0))
+        })
+    }
+
+    /// Number of models, by trying all assignments.
+    fn count_models(var_count: usize, clauses: &[Vec<i32>]) -> usize {
+        (0..1 << var_count)
+            .filter(|bits| {
+                let values = (0..var_count)
+                    .map(|var| bits >> var & 1 == 1)
+                    .collect::<Vec<_>>();
+                satisfies(&values, clauses)
+            })
+            .count()
+    }
+
+    fn new_solver(var_count: usize, clauses: &[Vec<i32>]) -> Solver {
+        let mut solver = Solver::new(var_count);
+        for clause in clauses {
+            solver.add_clause(clause);
+        }
+        solver
+    }
+
+    fn model(solver: &Solver, var_count: usize) -> Vec<bool> {
+        (1..=var_count)
+            .map(|var| solver.value(var).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn luby_sequence() {
+        let sequence = (0..15).map(luby).collect::<Vec<_>>();
+        assert_eq!(sequence, [1, 1, 2, 1, 1, 2, 4, 1, 1, 2, 1, 1, 2, 4, 8]);
+    }
+
+    #[test]
+    fn random_against_brute_force() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..500 {
+            let var_count = rng.gen_range(3, 12);
+            let clause_count = rng.gen_range(1, 6 * var_count);
+            let clauses = random_cnf(&mut rng, var_count, clause_count);
+            let mut solver = new_solver(var_count, &clauses);
+            let sat = solver.solve(None).unwrap();
+            assert_eq!(sat, count_models(var_count, &clauses) > 0, "{:?}", clauses);
+            if sat {
+                assert!(satisfies(&model(&solver, var_count), &clauses));
+            }
+        }
+    }
+
+    #[test]
+    fn enumerate_models() {
+        let mut rng = StdRng::seed_from_u64(2);
+        for _ in 0..100 {
+            let var_count = rng.gen_range(3, 9);
+            let clause_count = rng.gen_range(1, 4 * var_count);
+            let clauses = random_cnf(&mut rng, var_count, clause_count);
+            let mut solver = new_solver(var_count, &clauses);
+            let mut models = Vec::new();
+            while solver.solve(None).unwrap() {
+                let values = model(&solver, var_count);
+                assert!(satisfies(&values, &clauses));
+                assert!(!models.contains(&values));
+                let block = values
+                    .iter()
+                    .enumerate()
+                    .map(|(var, &value)| {
+                        if value {
+                            -(var as i32 + 1)
+                        } else {
+                            var as i32 + 1
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                solver.add_clause(&block);
+                models.push(values);
+            }
+            assert_eq!(models.len(), count_models(var_count, &clauses));
+        }
+    }
+
+    #[test]
+    fn unsat() {
+        for n in 1..=6 {
+            let clauses = pigeonhole(n);
+            let mut solver = new_solver(n * (n + 1), &clauses);
+            assert_eq!(solver.solve(None), Some(false));
+            assert!(solver.conflicts > 0 || n == 1);
+            // It stays unsatisfiable.
+            assert_eq!(solver.solve(None), Some(false));
+        }
+
+        let mut solver = new_solver(2, &[vec![1, 2], vec![-1]]);
+        solver.add_clause(&[-2]);
+        assert_eq!(solver.solve(None), Some(false));
+        let mut solver = Solver::new(1);
+        solver.add_clause(&[]);
+        assert_eq!(solver.solve(None), Some(false));
+    }
+
+    #[test]
+    fn reduce_with_learnt_clauses() {
+        let mut rng = StdRng::seed_from_u64(3);
+        for k in 0..20 {
+            let (var_count, clauses) = if k % 4 == 0 {
+                (42, pigeonhole(6))
+            } else {
+                (50, random_cnf(&mut rng, 50, 213))
+            };
+            let expected = new_solver(var_count, &clauses).solve(None).unwrap();
+            // Reduces the learnt clauses at almost every restart,
+            // when some of them are the reasons of the assignments
+            // at decision level 0.
+            let mut solver = new_solver(var_count, &clauses);
+            solver.max_learnts = 10;
+            let sat = solver.solve(None).unwrap();
+            assert_eq!(sat, expected);
+            if k % 4 == 0 {
+                assert!(solver.max_learnts > 10);
+            }
+            if sat {
+                assert!(satisfies(&model(&solver, var_count), &clauses));
+            }
+        }
+    }
+
+    #[test]
+    fn new_vars() {
+        let mut solver = new_solver(2, &[vec![1, 2], vec![-1, 2]]);
+        assert_eq!(solver.solve(None), Some(true));
+        assert_eq!(solver.value(2), Some(true));
+        solver.set_var_count(4);
+        solver.add_clause(&[-2, 3]);
+        solver.add_clause(&[-3, -4]);
+        assert_eq!(solver.solve(None), Some(true));
+        assert_eq!(model(&solver, 4)[1..], [true, true, false]);
+        solver.add_clause(&[4, -3]);
+        assert_eq!(solver.solve(None), Some(false));
+    }
+}
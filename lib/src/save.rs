@@ -3,15 +3,46 @@
 
 use crate::{
     cells::{Coord, State},
-    config::Config,
+    config::{Config, Engine},
     error::Error,
     rules::{Life, LifeGen, NtLife, NtLifeGen, Rule},
+    sat::SatWorld,
     search::{Reason, SetCell},
     traits::Search,
     world::World,
 };
 use serde::{Deserialize, Serialize};
 
+/// The current version of the save format.
+///
+/// Version 0 is the unversioned format of rlifesrc 0.3,
+/// which stores the whole `set_stack`.
+pub const SAVE_VERSION: u32 = 1;
+
+/// The header of a save, which identifies the format.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SaveHeader {
+    /// Version of the save format.
+    pub version: u32,
+
+    /// Version of the crate which writes the save.
+    pub crate_version: String,
+
+    /// The rule string of the saved world.
+    pub rule_string: String,
+}
+
+impl SaveHeader {
+    /// The header of a save written by this version of the crate.
+    fn new(rule_string: &str) -> Self {
+        SaveHeader {
+            version: SAVE_VERSION,
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+            rule_string: rule_string.to_string(),
+        }
+    }
+}
+
 /// A representation of `SetCell` which can be easily serialized.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 struct SetCellSer {
@@ -36,57 +67,149 @@ impl<'a, R: Rule> SetCell<'a, R> {
 }
 
 /// A representation of the world which can be easily serialized.
+///
+/// Only the cells which are not deduced are saved.
+/// The deduced cells are recovered by replaying the deduction.
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct WorldSer {
+    /// The header of the save.
+    ///
+    /// It is missing in saves of version 0.
+    #[serde(default)]
+    header: SaveHeader,
+
     /// World configuration.
     config: Config,
 
     /// Number of conflicts during the search.
     conflicts: u64,
 
-    /// A stack to records the cells whose values are set during the search.
+    /// The cells in the `set_stack` which are set by choice or by backtracking,
+    /// in the order they are set.
     ///
-    /// The cells in this table always have known states.
-    ///
-    /// It is used in the backtracking.
-    set_stack: Vec<SetCellSer>,
+    /// Saves of version 0 store the whole `set_stack` here.
+    #[serde(alias = "set_stack")]
+    decisions: Vec<SetCellSer>,
+
+    /// Number of cells at the end of `decisions` whose consequences
+    /// are not yet deduced.
+    #[serde(default)]
+    pending: usize,
 
     /// The position in the `set_stack` of the next cell to be examined.
     ///
-    /// See `proceed` for details.
-    check_index: usize,
+    /// Only in saves of version 0. It is turned into `pending` by `migrate`.
+    #[serde(default, skip_serializing)]
+    check_index: Option<usize>,
 
     /// The position in the `search_list` of the last decided cell.
     search_index: usize,
+
+    /// The value of the objective in the last result,
+    /// when `reduce_max` is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    best: Option<usize>,
+
+    /// Whether a result has been found, so that the search
+    /// no longer restarts.
+    #[serde(default)]
+    found: bool,
+
+    /// The clauses excluding the results found so far,
+    /// when the engine is `Engine::Sat`.
+    ///
+    /// The variables are those of [`Config::cnf`](struct.Config.html#method.cnf).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    blocks: Vec<Vec<i32>>,
 }
 
 impl WorldSer {
+    /// The header of the save.
+    pub fn header(&self) -> &SaveHeader {
+        &self.header
+    }
+
+    /// Upgrades a save of an older version to the current version.
+    pub fn migrate(mut self) -> Result<Self, Error> {
+        match self.header.version {
+            0 => {
+                // The whole `set_stack` can be replayed as decisions,
+                // the deduced cells are simply skipped. The cells from
+                // the `check_index` on are not examined yet.
+                self.header = SaveHeader::new(&self.config.rule_string);
+                let check_index = self.check_index.take().unwrap_or(0);
+                self.pending = self.decisions.len().saturating_sub(check_index);
+                Ok(self)
+            }
+            SAVE_VERSION => Ok(self),
+            version => Err(Error::SaveVersionError(version)),
+        }
+    }
+
     /// Restores the world from the `WorldSer`, with the given rule.
+    ///
+    /// The `WorldSer` must be of the current version.
     fn world_with_rule<'a, R: Rule>(&self, rule: R) -> Result<World<'a, R>, Error> {
-        let mut world = World::new(&self.config, rule);
-        for &SetCellSer {
-            coord,
-            state,
-            reason,
-        } in self.set_stack.iter()
+        let mut world = World::new(&self.config, rule).init_known_cells()?;
+        let settled = self.decisions.len().saturating_sub(self.pending);
+        for (
+            i,
+            &SetCellSer {
+                coord,
+                state,
+                reason,
+            },
+        ) in self.decisions.iter().enumerate()
         {
-            let cell = world.find_cell(coord).ok_or(Error::SetCellError(coord))?;
-            if let Some(old_state) = cell.state.get() {
-                if old_state != state {
-                    return Err(Error::SetCellError(coord));
+            let (x, y, t) = coord;
+            if x < 0
+                || x >= self.config.width
+                || y < 0
+                || y >= self.config.height
+                || t < 0
+                || t >= self.config.period
+            {
+                return Err(Error::SaveCellError(coord));
+            }
+            if state.0 >= world.rule.gen() {
+                return Err(Error::SaveStateError(coord));
+            }
+            let cell = world.find_cell(coord).ok_or(Error::SaveCellError(coord))?;
+            let consistent = match cell.state.get() {
+                Some(old_state) if old_state != state => {
+                    return Err(Error::SaveConflictError(coord));
                 }
-            } else {
-                world.set_cell(cell, state, reason);
+                Some(_) => true,
+                None => world.set_cell(cell, state, reason),
+            };
+            let consistent = consistent && (i >= settled || world.proceed());
+            // A conflict caused by the last cell is left for the search to handle.
+            if !consistent && i + 1 < self.decisions.len() {
+                return Err(Error::SaveConflictError(coord));
             }
         }
         world.conflicts = self.conflicts;
-        world.check_index = self.check_index;
         world.search_index = self.search_index;
+        world.best = self.best;
+        world.found = self.found;
         Ok(world)
     }
 
     /// Restores the world from the `WorldSer`.
+    ///
+    /// Saves of older versions are migrated first.
     pub fn world(&self) -> Result<Box<dyn Search>, Error> {
+        if self.header.version != SAVE_VERSION {
+            return self.clone().migrate()?.world();
+        }
+        if self.header.rule_string != self.config.rule_string {
+            return Err(Error::SaveRuleError(self.header.rule_string.clone()));
+        }
+        if self.config.engine == Engine::Sat {
+            let world =
+                SatWorld::restore(&self.config, self.blocks.clone(), self.conflicts, self.best)?;
+            return Ok(Box::new(world));
+        }
         if let Ok(rule) = self.config.rule_string.parse::<Life>() {
             let world = self.world_with_rule(rule)?;
             Ok(Box::new(world))
@@ -122,13 +245,51 @@ impl WorldSer {
 
 impl<'a, R: Rule> World<'a, R> {
     /// Saves the world as a `WorldSer`.
+    ///
+    /// Cells deduced from other cells are omitted.
     pub fn ser(&self) -> WorldSer {
+        let mut decisions = Vec::new();
+        let mut pending = 0;
+        for (i, set_cell) in self.set_stack.iter().enumerate() {
+            if set_cell.reason != Reason::Deduce {
+                decisions.push(set_cell.ser());
+                if i >= self.check_index {
+                    pending += 1;
+                }
+            }
+        }
         WorldSer {
+            header: SaveHeader::new(&self.config.rule_string),
             config: self.config.clone(),
             conflicts: self.conflicts,
-            set_stack: self.set_stack.iter().map(|s| s.ser()).collect(),
-            check_index: self.check_index,
+            decisions,
+            pending,
+            check_index: None,
             search_index: self.search_index,
+            best: self.best,
+            found: self.found,
+            blocks: Vec::new(),
+        }
+    }
+}
+
+impl SatWorld {
+    /// Saves the search as a `WorldSer`.
+    ///
+    /// The solver itself is not saved, so the restored search starts over,
+    /// but the results found so far are still excluded.
+    pub fn ser(&self) -> WorldSer {
+        WorldSer {
+            header: SaveHeader::new(&self.config().rule_string),
+            config: self.config().clone(),
+            conflicts: self.conflicts(),
+            decisions: Vec::new(),
+            pending: 0,
+            check_index: None,
+            search_index: 0,
+            best: self.optimum(),
+            found: false,
+            blocks: self.blocks().to_vec(),
         }
     }
 }
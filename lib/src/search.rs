@@ -1,11 +1,13 @@
 //! The search process.
 use crate::{
-    cells::{CellRef, State},
+    cells::{CellRef, State, ALIVE, DEAD},
     config::NewState,
+    explain::{Cause, Deducer},
+    proof::LeafEnd,
     rules::Rule,
     world::World,
 };
-use rand::{thread_rng, Rng};
+use rand::Rng;
 
 #[cfg(feature = "serialize")]
 use serde::{Deserialize, Serialize};
@@ -43,22 +45,39 @@ pub(crate) enum Reason {
     /// Remembers its position in the `search_list` of the world,
     /// and the number of remaining states to try.
     TryAnother(usize, usize),
+
+    /// Switches a decided cell to its last remaining state,
+    /// after all the other states lead to conflicts.
+    ///
+    /// Behaves like `Deduce` when backtracking, but unlike `Deduce`,
+    /// it cannot be recovered by deduction from the earlier cells.
+    Backtrack,
 }
 
 /// Records the cells whose values are set and their reasons.
-#[derive(Clone, Copy)]
+#[derive(Clone)]
 pub(crate) struct SetCell<'a, R: Rule> {
     /// The set cell.
     pub(crate) cell: CellRef<'a, R>,
 
     /// The reason for setting a cell.
     pub(crate) reason: Reason,
+
+    /// Why the cell has its state, if `explain` is set in the configuration.
+    ///
+    /// `None` is considered `Cause::Known`. Boxed to keep the stack small
+    /// when `explain` is not set.
+    pub(crate) cause: Option<Box<Cause>>,
 }
 
 impl<'a, R: Rule> SetCell<'a, R> {
     /// Get a reference to the set cell.
-    pub(crate) fn new(cell: CellRef<'a, R>, reason: Reason) -> Self {
-        SetCell { cell, reason }
+    pub(crate) fn new(cell: CellRef<'a, R>, reason: Reason, cause: Option<Box<Cause>>) -> Self {
+        SetCell {
+            cell,
+            reason,
+            cause,
+        }
     }
 }
 
@@ -72,8 +91,17 @@ impl<'a, R: Rule> World<'a, R> {
     ///
     /// Returns `false` if there is a conflict,
     /// `true` if the cells are consistent.
-    fn consistify(&mut self, cell: CellRef<'a, R>) -> bool {
-        Rule::consistify(self, cell)
+    ///
+    /// Cells in a growing world which involve the cells after the open
+    /// boundary are not consistified. See `is_loose` for details.
+    ///
+    /// If `explain` is set, the cells deduced here are recorded
+    /// as determined by the rule applied at this cell.
+    pub(crate) fn consistify(&mut self, cell: CellRef<'a, R>) -> bool {
+        if self.config.explain {
+            self.deducer = Some(Deducer::Rule(cell));
+        }
+        (self.config.grow && self.is_loose(cell)) || Rule::consistify(self, cell)
     }
 
     /// Consistifies a cell, its neighbors, and its predecessor.
@@ -89,22 +117,28 @@ impl<'a, R: Rule> World<'a, R> {
                     true
                 }
             }
-            && cell
-                .nbhd
-                .iter()
-                .all(|&neigh| self.consistify(neigh.unwrap()))
+            && cell.nbhd.iter().all(|&neigh| {
+                if let Some(neigh) = neigh {
+                    self.consistify(neigh)
+                } else {
+                    true
+                }
+            })
     }
 
     /// Deduces all the consequences by `consistify` and symmetry.
     ///
     /// Returns `false` if there is a conflict,
     /// `true` if the cells are consistent.
-    fn proceed(&mut self) -> bool {
+    pub(crate) fn proceed(&mut self) -> bool {
         while self.check_index < self.set_stack.len() {
             let cell = self.set_stack[self.check_index].cell;
             let state = cell.state.get().unwrap();
 
             // Determines some cells by symmetry.
+            if self.config.explain {
+                self.deducer = Some(Deducer::Symmetry(cell));
+            }
             for &sym in cell.sym.iter() {
                 if let Some(old_state) = sym.state.get() {
                     if state != old_state {
@@ -122,6 +156,7 @@ impl<'a, R: Rule> World<'a, R> {
 
             self.check_index += 1;
         }
+        self.deducer = None;
         true
     }
 
@@ -131,7 +166,11 @@ impl<'a, R: Rule> World<'a, R> {
     /// Returns `true` if it backtracks successfully,
     /// `false` if it goes back to the time before the first cell is set.
     fn backup(&mut self) -> bool {
+        self.save_deepest();
         while let Some(set_cell) = self.set_stack.pop() {
+            if self.config.grow {
+                self.shrink_to(self.set_stack.len());
+            }
             let cell = set_cell.cell;
             match set_cell.reason {
                 Reason::Decide(i) => {
@@ -144,12 +183,14 @@ impl<'a, R: Rule> World<'a, R> {
                         if self.set_cell(cell, state, Reason::TryAnother(i, self.rule.gen() - 2)) {
                             return true;
                         }
+                        self.record_leaf(LeafEnd::Conflict(cell.coord));
                     } else {
                         let state = !cell.state.get().unwrap();
                         self.clear_cell(cell);
-                        if self.set_cell(cell, state, Reason::Deduce) {
+                        if self.set_cell(cell, state, Reason::Backtrack) {
                             return true;
                         }
+                        self.record_leaf(LeafEnd::Conflict(cell.coord));
                     }
                 }
                 Reason::TryAnother(i, n) => {
@@ -159,15 +200,16 @@ impl<'a, R: Rule> World<'a, R> {
                     let state = State((j + 1) % self.rule.gen());
                     self.clear_cell(cell);
                     let reason = if n == 1 {
-                        Reason::Deduce
+                        Reason::Backtrack
                     } else {
                         Reason::TryAnother(i, n - 1)
                     };
                     if self.set_cell(cell, state, reason) {
                         return true;
                     }
+                    self.record_leaf(LeafEnd::Conflict(cell.coord));
                 }
-                Reason::Deduce => {
+                Reason::Deduce | Reason::Backtrack => {
                     self.clear_cell(cell);
                 }
             }
@@ -194,6 +236,10 @@ impl<'a, R: Rule> World<'a, R> {
                 return true;
             } else {
                 self.conflicts += 1;
+                self.record_root_conflict();
+                let coord = self.set_stack[self.check_index].cell.coord;
+                self.record_cell_conflict(coord);
+                self.record_leaf(LeafEnd::Conflict(coord));
                 if !self.backup() {
                     return false;
                 }
@@ -214,7 +260,7 @@ impl<'a, R: Rule> World<'a, R> {
             let state = match self.config.new_state {
                 NewState::ChooseDead => cell.background,
                 NewState::ChooseAlive => !cell.background,
-                NewState::Random => State(thread_rng().gen_range(0, self.rule.gen())),
+                NewState::Random => self.random_state(),
             };
             Some(self.set_cell(cell, state, Reason::Decide(i)))
         } else {
@@ -222,29 +268,132 @@ impl<'a, R: Rule> World<'a, R> {
         }
     }
 
+    /// Chooses a random state for an unknown cell.
+    ///
+    /// If `density` is set, chooses `ALIVE` with that probability,
+    /// and the other states with equal probabilities.
+    fn random_state(&mut self) -> State {
+        let gen = self.rule.gen();
+        match self.config.density {
+            None => State(self.rng.gen_range(0, gen)),
+            Some(density) => {
+                if self.rng.gen_ratio(density.min(100), 100) {
+                    ALIVE
+                } else {
+                    match self.rng.gen_range(0, gen - 1) {
+                        0 => DEAD,
+                        j => State(j + 1),
+                    }
+                }
+            }
+        }
+    }
+
+    /// Whether the search should restart,
+    /// according to the restart policy in the configuration.
+    ///
+    /// It never restarts once a result is found.
+    fn should_restart(&self) -> bool {
+        !self.found
+            && matches!(
+                self.config.restart.interval(self.restarts),
+                Some(interval) if self.conflicts - self.restart_conflicts >= interval
+            )
+    }
+
+    /// Restarts the search, discarding all decisions.
+    ///
+    /// The cells set before the first decision are kept,
+    /// including those set by backtracking, since they do not depend
+    /// on any decision.
+    fn restart(&mut self) {
+        self.save_deepest();
+        if let Some(first) = self
+            .set_stack
+            .iter()
+            .position(|set| matches!(set.reason, Reason::Decide(_) | Reason::TryAnother(..)))
+        {
+            while self.set_stack.len() > first {
+                let set_cell = self.set_stack.pop().unwrap();
+                if self.config.grow {
+                    self.shrink_to(self.set_stack.len());
+                }
+                self.clear_cell(set_cell.cell);
+            }
+        }
+        self.check_index = self.check_index.min(self.set_stack.len());
+        self.search_index = 0;
+        self.restarts += 1;
+        self.restart_conflicts = self.conflicts;
+    }
+
+    /// Number of decisions on the current branch of the search,
+    /// i.e., cells in the `set_stack` whose states are chosen
+    /// rather than deduced.
+    pub(crate) fn depth(&self) -> usize {
+        self.set_stack
+            .iter()
+            .filter(|set| matches!(set.reason, Reason::Decide(_) | Reason::TryAnother(..)))
+            .count()
+    }
+
     /// The search function.
     ///
     /// Returns `Found` if a result is found,
     /// `None` if such pattern does not exist,
     /// `Searching` if the number of steps exceeds `max_step`
     /// and no results are found.
+    ///
+    /// If `reduce_max` is set, each result tightens the bound
+    /// of the objective, and records its value in `best`.
     pub fn search(&mut self, max_step: Option<u64>) -> Status {
         let mut step_count = 0;
+        if matches!(self.best, Some(best) if self.config.objective.is_optimum(best)) {
+            while self.backup() {}
+            return Status::None;
+        }
         if self.get_unknown(0).is_none() && !self.backup() {
             return Status::None;
         }
         while self.go(&mut step_count) {
+            if self.should_restart() {
+                self.restart();
+                continue;
+            }
+            if self.should_grow() {
+                if !self.grow() {
+                    self.conflicts += 1;
+                    if !self.backup() {
+                        return Status::None;
+                    }
+                }
+                continue;
+            }
+            self.record_deepest();
             if let Some(result) = self.decide() {
-                if !result && !self.backup() {
-                    return Status::None;
+                if !result {
+                    let coord = self.set_stack.last().unwrap().cell.coord;
+                    self.record_leaf(LeafEnd::Conflict(coord));
+                    if !self.backup() {
+                        return Status::None;
+                    }
                 }
-            } else if self.nontrivial() {
+            } else if self.nontrivial() && self.close() {
+                self.record_leaf(LeafEnd::Found);
                 if self.config.reduce_max {
-                    self.config.max_cell_count = Some(self.cell_count() - 1);
+                    let value = self.objective_value();
+                    self.config.tighten_bound(value);
+                    self.best = Some(value);
                 }
+                self.deepest = None;
+                self.deeper = None;
+                self.found = true;
                 return Status::Found;
-            } else if !self.backup() {
-                return Status::None;
+            } else {
+                self.record_leaf(LeafEnd::Trivial);
+                if !self.backup() {
+                    return Status::None;
+                }
             }
 
             if let Some(max) = max_step {
@@ -0,0 +1,255 @@
+//! Simulating patterns on the unbounded plane.
+
+use crate::{
+    cells::{Coord, State, DEAD, NBHD},
+    error::Error,
+    rules::Rule,
+    world::World,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{self, Debug, Formatter},
+};
+
+/// A simulator that evolves a pattern on the unbounded plane.
+///
+/// Only the cells whose states differ from the background are stored.
+/// The background itself also evolves under the rule,
+/// so for rules with `B0` it alternates between generations.
+pub struct Simulator<'r, R: Rule> {
+    /// The rule of the cellular automaton.
+    rule: &'r R,
+
+    /// The background state of the current generation.
+    background: State,
+
+    /// The cells whose states differ from the background.
+    cells: HashMap<(isize, isize), State>,
+
+    /// The current generation.
+    gen: isize,
+}
+
+impl<'r, R: Rule> Clone for Simulator<'r, R> {
+    fn clone(&self) -> Self {
+        Simulator {
+            rule: self.rule,
+            background: self.background,
+            cells: self.cells.clone(),
+            gen: self.gen,
+        }
+    }
+}
+
+/// The rule is not shown.
+impl<'r, R: Rule> Debug for Simulator<'r, R> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("Simulator")
+            .field("background", &self.background)
+            .field("cells", &self.cells)
+            .field("gen", &self.gen)
+            .finish()
+    }
+}
+
+impl<'r, R: Rule> Simulator<'r, R> {
+    /// Creates an empty pattern with a dead background in generation 0.
+    pub fn new(rule: &'r R) -> Self {
+        Simulator {
+            rule,
+            background: DEAD,
+            cells: HashMap::new(),
+            gen: 0,
+        }
+    }
+
+    /// The current generation.
+    pub fn gen(&self) -> isize {
+        self.gen
+    }
+
+    /// The background state of the current generation.
+    pub fn background(&self) -> State {
+        self.background
+    }
+
+    /// Gets the state of a cell.
+    pub fn get_cell_state(&self, pos: (isize, isize)) -> State {
+        self.cells.get(&pos).copied().unwrap_or(self.background)
+    }
+
+    /// Sets the state of a cell.
+    pub fn set_cell_state(&mut self, pos: (isize, isize), state: State) {
+        if state == self.background {
+            self.cells.remove(&pos);
+        } else {
+            self.cells.insert(pos, state);
+        }
+    }
+
+    /// An iterator over the cells whose states differ from the background.
+    pub fn cells(&self) -> impl Iterator<Item = ((isize, isize), State)> + '_ {
+        self.cells.iter().map(|(&pos, &state)| (pos, state))
+    }
+
+    /// Evolves the pattern by one generation.
+    pub fn step(&mut self) {
+        let background = self.rule.transition(self.background, [self.background; 8]);
+        let candidates = self
+            .cells
+            .keys()
+            .flat_map(|&(x, y)| {
+                NBHD.iter()
+                    .map(move |(nx, ny)| (x + nx, y + ny))
+                    .chain(Some((x, y)))
+            })
+            .collect::<HashSet<_>>();
+        let mut cells = HashMap::new();
+        for (x, y) in candidates {
+            let mut nbhd = [self.background; 8];
+            for (state, (nx, ny)) in nbhd.iter_mut().zip(NBHD.iter()) {
+                *state = self.get_cell_state((x + nx, y + ny));
+            }
+            let state = self.rule.transition(self.get_cell_state((x, y)), nbhd);
+            if state != background {
+                cells.insert((x, y), state);
+            }
+        }
+        self.background = background;
+        self.cells = cells;
+        self.gen += 1;
+    }
+
+    /// Evolves the pattern by `n` generations.
+    pub fn run(&mut self, n: isize) {
+        for _ in 0..n {
+            self.step();
+        }
+    }
+
+    /// Whether the pattern is the same as another pattern up to translation.
+    ///
+    /// The generations are not compared.
+    pub fn eq_up_to_translation(&self, other: &Self) -> bool {
+        if self.background != other.background || self.cells.len() != other.cells.len() {
+            return false;
+        }
+        let first = |cells: &HashMap<(isize, isize), State>| {
+            cells.keys().min_by_key(|&&(x, y)| (y, x)).copied()
+        };
+        match (first(&self.cells), first(&other.cells)) {
+            (Some((x0, y0)), Some((x1, y1))) => {
+                let (dx, dy) = (x1 - x0, y1 - y0);
+                self.cells
+                    .iter()
+                    .all(|(&(x, y), &state)| other.cells.get(&(x + dx, y + dy)) == Some(&state))
+            }
+            _ => true,
+        }
+    }
+}
+
+impl<'r, R: Rule> PartialEq for Simulator<'r, R> {
+    /// The generations are not compared.
+    fn eq(&self, other: &Self) -> bool {
+        self.background == other.background && self.cells == other.cells
+    }
+}
+
+impl<'a, R: Rule> World<'a, R> {
+    /// Verifies the result on the unbounded plane.
+    ///
+    /// Evolves generation 0 of the world in a `Simulator`, and checks that:
+    ///
+    /// * every generation matches the world, i.e., all cells outside
+    ///   the search range stay in the background state;
+    /// * after `period` generations, the pattern returns to generation 0,
+    ///   applying the transformation and the translation;
+    /// * the pattern does not have a smaller period.
+    ///
+    /// The last two are skipped if the configuration is not periodic.
+    ///
+    /// In a catalyst search, it also checks that the catalyst is a still life,
+    /// and returns to its generation 0 in the last generation.
+    ///
+    /// All cells in the world must be known.
+    pub fn verify(&self) -> Result<(), Error> {
+        let (width, height, period) = (self.config.width, self.config.height, self.config.period);
+        let mut sim = Simulator::new(&self.rule);
+        for x in 0..width {
+            for y in 0..height {
+                let state = self
+                    .get_cell_state((x, y, 0))?
+                    .ok_or(Error::UnknownCellError((x, y, 0)))?;
+                sim.set_cell_state((x, y), state);
+            }
+        }
+
+        let mut gens = Vec::with_capacity(period as usize);
+        for t in 0..period {
+            if sim
+                .cells()
+                .any(|((x, y), _)| x < -1 || x > width || y < -1 || y > height)
+            {
+                return Err(Error::EvolutionError(t));
+            }
+            for x in -1..=width {
+                for y in -1..=height {
+                    let coord: Coord = (x, y, t);
+                    let state = self
+                        .get_cell_state(coord)?
+                        .ok_or(Error::UnknownCellError(coord))?;
+                    if sim.get_cell_state((x, y)) != state {
+                        return Err(Error::EvolutionError(t));
+                    }
+                }
+            }
+            gens.push(sim.clone());
+            sim.step();
+        }
+        if self.config.catalyst {
+            let active = self.config.active_cells();
+            let mut still = Simulator::new(&self.rule);
+            for (pos, state) in gens[0].cells() {
+                if !active.contains(&pos) {
+                    still.set_cell_state(pos, state);
+                }
+            }
+            let mut next = still.clone();
+            next.step();
+            if next != still {
+                return Err(Error::StillLifeError);
+            }
+            let last = &gens[period as usize - 1];
+            for x in -1..=width {
+                for y in -1..=height {
+                    if !active.contains(&(x, y))
+                        && last.get_cell_state((x, y)) != still.get_cell_state((x, y))
+                    {
+                        return Err(Error::RecoveryError(period - 1));
+                    }
+                }
+            }
+        }
+        if !self.config.periodic {
+            return Ok(());
+        }
+
+        let mut expected = Simulator::new(&self.rule);
+        expected.background = gens[0].background;
+        for ((x, y), state) in gens[0].cells() {
+            let (new_x, new_y, _) = self.config.translate((x, y, -period));
+            expected.set_cell_state((new_x, new_y), state);
+        }
+        if sim != expected {
+            return Err(Error::PeriodError(period));
+        }
+
+        for t in 1..period {
+            if period % t == 0 && gens[t as usize].eq_up_to_translation(&gens[0]) {
+                return Err(Error::SubperiodError(t));
+            }
+        }
+        Ok(())
+    }
+}
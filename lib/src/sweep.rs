@@ -0,0 +1,226 @@
+//! Sweeping the size and the period of the search.
+
+use crate::{config::Config, search::Status, traits::Search};
+use std::{
+    ops::RangeInclusive,
+    time::{Duration, Instant},
+};
+
+#[cfg(feature = "serialize")]
+use serde::{Deserialize, Serialize};
+
+/// Number of steps between two checks of the budget.
+const CHECK_FREQ: u64 = 100000;
+
+/// The outcome of the search at a point of a sweep.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub enum SweepStatus {
+    /// A result is found.
+    Found,
+    /// Such pattern does not exist.
+    None,
+    /// The budget runs out before the search ends.
+    TimedOut,
+    /// The world of this point cannot be created,
+    /// e.g., when the known cells do not fit in it.
+    ///
+    /// Holds the error message.
+    Invalid(String),
+}
+
+/// A point of a sweep, and the outcome of the search there.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(Serialize, Deserialize))]
+pub struct SweepPoint {
+    /// Width of the world.
+    pub width: isize,
+    /// Height of the world.
+    pub height: isize,
+    /// Period of the pattern.
+    pub period: isize,
+    /// The outcome of the search.
+    pub status: SweepStatus,
+    /// Number of conflicts during the search.
+    pub conflicts: u64,
+    /// Time spent on the search.
+    pub time: Duration,
+}
+
+/// A sweep over ranges of widths, heights and periods.
+///
+/// Each point of the sweep is searched with the same configuration,
+/// except for the width, the height and the period,
+/// until the first result is found, or there are no results,
+/// or the budget runs out.
+///
+/// The points are searched in the order of periods, then widths,
+/// then heights, from small to large. Points whose width and height
+/// differ are skipped if the transformation or the symmetry
+/// is only valid for square worlds.
+#[derive(Clone, Debug)]
+pub struct Sweep {
+    /// The configuration shared by all points.
+    ///
+    /// Its width, height and period are ignored.
+    pub config: Config,
+
+    /// Widths of the world.
+    pub widths: RangeInclusive<isize>,
+
+    /// Heights of the world.
+    pub heights: RangeInclusive<isize>,
+
+    /// Periods of the pattern.
+    pub periods: RangeInclusive<isize>,
+
+    /// The maximal number of steps at each point.
+    ///
+    /// `None` means that there is no limit.
+    pub max_step: Option<u64>,
+
+    /// The maximal time spent at each point.
+    ///
+    /// `None` means that there is no limit.
+    pub time_limit: Option<Duration>,
+
+    /// Whether to stop the sweep at the first point where a result is found.
+    pub stop_at_first: bool,
+}
+
+impl Sweep {
+    /// Sets up a sweep containing only the size and the period
+    /// of the configuration.
+    pub fn new(config: Config) -> Self {
+        Sweep {
+            widths: config.width..=config.width,
+            heights: config.height..=config.height,
+            periods: config.period..=config.period,
+            config,
+            max_step: None,
+            time_limit: None,
+            stop_at_first: false,
+        }
+    }
+
+    /// Sets the widths.
+    pub fn set_widths(mut self, widths: RangeInclusive<isize>) -> Self {
+        self.widths = widths;
+        self
+    }
+
+    /// Sets the heights.
+    pub fn set_heights(mut self, heights: RangeInclusive<isize>) -> Self {
+        self.heights = heights;
+        self
+    }
+
+    /// Sets the periods.
+    pub fn set_periods(mut self, periods: RangeInclusive<isize>) -> Self {
+        self.periods = periods;
+        self
+    }
+
+    /// Sets the maximal number of steps at each point.
+    pub fn set_max_step(mut self, max_step: Option<u64>) -> Self {
+        self.max_step = max_step;
+        self
+    }
+
+    /// Sets the maximal time spent at each point.
+    pub fn set_time_limit(mut self, time_limit: Option<Duration>) -> Self {
+        self.time_limit = time_limit;
+        self
+    }
+
+    /// Sets whether to stop the sweep at the first point
+    /// where a result is found.
+    pub fn set_stop_at_first(mut self, stop_at_first: bool) -> Self {
+        self.stop_at_first = stop_at_first;
+        self
+    }
+
+    /// The configurations of all points of the sweep, in the search order.
+    pub fn configs(&self) -> Vec<Config> {
+        let square = self.config.transform.square_world() || self.config.symmetry.square_world();
+        let mut configs = Vec::new();
+        for period in self.periods.clone() {
+            for width in self.widths.clone() {
+                for height in self.heights.clone() {
+                    if square && width != height {
+                        continue;
+                    }
+                    let mut config = self.config.clone();
+                    config.width = width;
+                    config.height = height;
+                    config.period = period;
+                    configs.push(config);
+                }
+            }
+        }
+        configs
+    }
+
+    /// Runs the sweep, and returns the outcomes of all searched points.
+    ///
+    /// `f` is called after each point is searched, with the point
+    /// and the world, which contains the result if one is found.
+    ///
+    /// A point whose world cannot be created is recorded as
+    /// `SweepStatus::Invalid` without calling `f`, and the sweep goes on.
+    pub fn run<F>(&self, mut f: F) -> Vec<SweepPoint>
+    where
+        F: FnMut(&SweepPoint, &dyn Search),
+    {
+        let mut points = Vec::new();
+        for config in self.configs() {
+            let start_time = Instant::now();
+            let mut search = match config.world() {
+                Ok(search) => search,
+                Err(e) => {
+                    points.push(SweepPoint {
+                        width: config.width,
+                        height: config.height,
+                        period: config.period,
+                        status: SweepStatus::Invalid(e.to_string()),
+                        conflicts: 0,
+                        time: start_time.elapsed(),
+                    });
+                    continue;
+                }
+            };
+            let mut step = 0;
+            let status = loop {
+                let max_step = self
+                    .max_step
+                    .map_or(CHECK_FREQ, |max_step| (max_step - step).min(CHECK_FREQ));
+                match search.search(Some(max_step)) {
+                    Status::Found => break SweepStatus::Found,
+                    Status::None => break SweepStatus::None,
+                    _ => (),
+                }
+                step += max_step;
+                if matches!(self.max_step, Some(max_step) if step >= max_step)
+                    || matches!(self.time_limit, Some(limit) if start_time.elapsed() >= limit)
+                {
+                    break SweepStatus::TimedOut;
+                }
+            };
+            let point = SweepPoint {
+                width: config.width,
+                height: config.height,
+                period: config.period,
+                status,
+                conflicts: search.conflicts(),
+                time: start_time.elapsed(),
+            };
+            f(&point, search.as_ref());
+            let found = point.status == SweepStatus::Found;
+            points.push(point);
+            if self.stop_at_first && found {
+                break;
+            }
+        }
+        points
+    }
+}
@@ -3,15 +3,55 @@ use crate::{
     cells::{Coord, State, ALIVE, DEAD},
     config::Config,
     error::Error,
+    explain::Deduction,
+    pattern::Pattern,
+    proof::Proof,
+    rle::RleWriter,
     rules::Rule,
     search::Status,
     world::World,
 };
-use std::fmt::Write;
+use std::{fmt::Write, mem};
 
 #[cfg(feature = "serialize")]
 use crate::save::WorldSer;
 
+/// Displays a pattern in the format of `Search::rle_gen`.
+fn mixed_rle(pattern: &Pattern, config: &Config, is_gen_rule: bool) -> String {
+    let mut str = String::new();
+    writeln!(
+        str,
+        "x = {}, y = {}, rule = {}",
+        pattern.width(),
+        pattern.height(),
+        config.rule_string
+    )
+    .unwrap();
+    for y in 0..pattern.height() {
+        for x in 0..pattern.width() {
+            match pattern.get_cell_state(x, y) {
+                Some(DEAD) => str.push('.'),
+                Some(ALIVE) => {
+                    if is_gen_rule {
+                        str.push('A')
+                    } else {
+                        str.push('o')
+                    }
+                }
+                Some(State(i)) => str.push((b'A' + i as u8 - 1) as char),
+                _ => str.push('?'),
+            };
+        }
+        if y == pattern.height() - 1 {
+            str.push('!')
+        } else {
+            str.push('$')
+        };
+        str.push('\n');
+    }
+    str
+}
+
 /// A trait for `World`.
 ///
 /// So that we can switch between different rule types using trait objects.
@@ -27,6 +67,11 @@ pub trait Search {
     /// Gets the state of a cell. Returns `Err(())` if there is no such cell.
     fn get_cell_state(&self, coord: Coord) -> Result<Option<State>, Error>;
 
+    /// The whole world in some generation as a `Pattern`.
+    ///
+    /// The rule string of the pattern is that of the world.
+    fn pattern(&self, t: isize) -> Pattern;
+
     /// World configuration.
     fn config(&self) -> &Config;
 
@@ -46,12 +91,81 @@ pub trait Search {
     /// Number of conflicts during the search.
     fn conflicts(&self) -> u64;
 
+    /// The whole world in some generation as a `Pattern`,
+    /// at the time when the most cells were known since the last result.
+    ///
+    /// Returns `None` if no such time is recorded.
+    fn deepest_pattern(&self, t: isize) -> Option<Pattern>;
+
+    /// Number of decisions on the current branch of the search.
+    ///
+    /// It grows when the search goes deeper, and shrinks when it backtracks.
+    /// For the SAT engine, it is the number of decisions on the cells
+    /// on the current trail of the solver.
+    fn depth(&self) -> usize;
+
+    /// The value of the objective in the last result,
+    /// if `reduce_max` is set and some result is found.
+    ///
+    /// Each result is better than the last one, so after the search
+    /// ends with `Status::None`, this is the proven optimum.
+    fn optimum(&self) -> Option<usize>;
+
+    /// The proof recorded so far, if `proof` is set in the configuration.
+    ///
+    /// After the search ends with `Status::None`, it can be checked
+    /// by `Config::check_proof`.
+    ///
+    /// The leaves taken by `take_proof` are not included.
+    fn proof(&self) -> Option<&Proof>;
+
+    /// Takes the leaves of the proof recorded since the last call,
+    /// if `proof` is set in the configuration.
+    ///
+    /// This lets a long search write the proof to a file as it goes on,
+    /// instead of keeping it in memory. Each leaf only records its
+    /// difference from the previous one, so the leaves must be written
+    /// in the order they are taken.
+    fn take_proof(&mut self) -> Option<Proof>;
+
+    /// Explains why a cell has its state, if `explain` is set
+    /// in the configuration.
+    ///
+    /// Returns the chain of deductions that leads to the cell,
+    /// back to the known cells and the decisions, in the order they are set.
+    /// The cell itself comes last.
+    fn explain(&self, coord: Coord) -> Result<Vec<Deduction>, Error>;
+
+    /// Explains the last conflict found before any decision,
+    /// if `explain` is set in the configuration.
+    ///
+    /// Returns the chains of deductions that lead to the cells involved
+    /// in the conflict. When a search fails immediately, this shows which
+    /// known cells cannot coexist.
+    fn explain_root_conflict(&self) -> Option<&[Deduction]>;
+
+    /// Number of conflicts found at each cell in some generation, row by row.
+    ///
+    /// A conflict is found at a cell when the deduction from it fails.
+    /// Cells where the search keeps failing suggest a better box shape,
+    /// search order, or known cells.
+    ///
+    /// Returns `None` for the SAT solver, which does not record them.
+    fn conflict_map(&self, t: isize) -> Option<Vec<Vec<u64>>>;
+
     /// Set the max cell counts.
     ///
     /// Currently this is the only parameter that you can change
     /// during the search.
     fn set_max_cell_count(&mut self, max_cell_count: Option<usize>);
 
+    /// Verifies the result on the unbounded plane.
+    ///
+    /// Checks that the cells outside the search range stay in the background
+    /// state, and that the pattern has the given period, translation and
+    /// transformation. All cells in the world must be known.
+    fn verify(&self) -> Result<(), Error>;
+
     #[cfg(feature = "serialize")]
     /// Saves the world as a `WorldSer`,
     /// which can be easily serialized.
@@ -69,39 +183,165 @@ pub trait Search {
     /// * Each line is ended with `$`;
     /// * The whole pattern is ended with `!`.
     fn rle_gen(&self, t: isize) -> String {
-        let mut str = String::new();
-        writeln!(
-            str,
-            "x = {}, y = {}, rule = {}",
-            self.config().width,
-            self.config().height,
-            self.config().rule_string
-        )
-        .unwrap();
-        for y in 0..self.config().height {
-            for x in 0..self.config().width {
-                let state = self.get_cell_state((x, y, t)).unwrap();
-                match state {
-                    Some(DEAD) => str.push('.'),
-                    Some(ALIVE) => {
-                        if self.is_gen_rule() {
-                            str.push('A')
-                        } else {
-                            str.push('o')
-                        }
-                    }
-                    Some(State(i)) => str.push((b'A' + i as u8 - 1) as char),
-                    _ => str.push('?'),
+        mixed_rle(&self.pattern(t), self.config(), self.is_gen_rule())
+    }
+
+    /// Displays the whole world in some generation, at the time when the
+    /// most cells were known since the last result, in the same format
+    /// as `rle_gen`.
+    ///
+    /// After a search fails, this shows how far it has reached.
+    ///
+    /// Returns `None` if no such time is recorded.
+    fn rle_deepest_gen(&self, t: isize) -> Option<String> {
+        let pattern = self.deepest_pattern(t)?;
+        Some(mixed_rle(&pattern, self.config(), self.is_gen_rule()))
+    }
+
+    /// Displays the whole world in some generation in the standard
+    /// [RLE](https://conwaylife.com/wiki/Rle) format.
+    ///
+    /// * **Dead** cells are represented by `b` for rules with 2 states,
+    ///   `.` for rules with more states;
+    /// * **Living** cells are represented by `o` for rules with 2 states,
+    ///   `A` for rules with more states;
+    /// * **Dying** cells are represented by uppercase letters from `B` to `X`,
+    ///   and then `pA` to `pX`, `qA` to `qX`, etc.;
+    /// * Repeated symbols are run-length encoded;
+    /// * Lines are at most 70 characters long;
+    /// * The search configuration is written in `#C` comment lines.
+    ///
+    /// Returns an error if some cell is unknown.
+    /// Partial results can be displayed by `rle_gen`.
+    fn rle_std_gen(&self, t: isize) -> Result<String, Error> {
+        let config = self.config();
+        let pattern = self.pattern(t);
+        let mut writer = RleWriter::new(
+            pattern.width(),
+            pattern.height(),
+            &config.rule_string,
+            self.is_gen_rule(),
+            &config.rle_comments(format!("generation = {}", t)),
+        );
+        for y in 0..pattern.height() {
+            for x in 0..pattern.width() {
+                let state = pattern
+                    .get_cell_state(x, y)
+                    .ok_or(Error::UnknownCellError((x, y, t)))?;
+                writer.push_cell(state);
+            }
+            writer.end_row();
+        }
+        Ok(writer.finish())
+    }
+
+    /// Displays all generations of the world side by side in the standard
+    /// [RLE](https://conwaylife.com/wiki/Rle) format.
+    ///
+    /// Generation 0 is on the left. Adjacent generations are separated
+    /// by a column of dead cells. The symbols are the same as `rle_std_gen`.
+    ///
+    /// Returns an error if some cell is unknown.
+    fn rle_std_phases(&self) -> Result<String, Error> {
+        let config = self.config();
+        let patterns = (0..config.period)
+            .map(|t| self.pattern(t))
+            .collect::<Vec<_>>();
+        let mut writer = RleWriter::new(
+            config.period * (config.width + 1) - 1,
+            config.height,
+            &config.rule_string,
+            self.is_gen_rule(),
+            &config.rle_comments(format!(
+                "generations 0 to {}, side by side",
+                config.period - 1
+            )),
+        );
+        for y in 0..config.height {
+            for (t, pattern) in patterns.iter().enumerate() {
+                if t > 0 {
+                    writer.push_cell(DEAD);
+                }
+                for x in 0..config.width {
+                    let state = pattern
+                        .get_cell_state(x, y)
+                        .ok_or(Error::UnknownCellError((x, y, t as isize)))?;
+                    writer.push_cell(state);
+                }
+            }
+            writer.end_row();
+        }
+        Ok(writer.finish())
+    }
+
+    /// Displays generation 0 of the world in
+    /// [LifeHistory](https://conwaylife.com/wiki/LifeHistory) format,
+    /// marking the cells by comparing all generations.
+    ///
+    /// A cell is considered on in some generation if it is alive. Then:
+    ///
+    /// * **Background** cells, i.e., cells that are never on,
+    ///   are represented by state 0 (`.`);
+    /// * **Rotor** cells that are on in generation 0 are represented
+    ///   by state 1 (`A`);
+    /// * **Rotor** cells that are off in generation 0 are represented
+    ///   by state 2 (`B`), the history envelope;
+    /// * **Stator** cells, i.e., cells that are on in all generations,
+    ///   are represented by state 3 (`C`), marked on;
+    /// * The cells just outside the search range, which are always off,
+    ///   are represented by state 6 (`F`), the boundary.
+    ///
+    /// These states are kept when the pattern runs in LifeHistory:
+    /// rotor cells switch between states 1 and 2, and the stator stays
+    /// in state 3. States 4 and 5 are not used.
+    ///
+    /// The cells are compared at the same position in the world,
+    /// so stator and rotor only make sense for oscillators.
+    ///
+    /// Returns an error if the rule is not Conway's Game of Life,
+    /// or if some cell is unknown.
+    fn rle_history(&self) -> Result<String, Error> {
+        let config = self.config();
+        if self.is_gen_rule() || !config.is_life() {
+            return Err(Error::HistoryRuleError(config.rule_string.clone()));
+        }
+        let patterns = (0..config.period)
+            .map(|t| self.pattern(t))
+            .collect::<Vec<_>>();
+        let mut writer = RleWriter::new(
+            config.width + 2,
+            config.height + 2,
+            "LifeHistory",
+            true,
+            &config.rle_comments(String::from("generation = 0, with history")),
+        );
+        for y in -1..=config.height {
+            for x in -1..=config.width {
+                if x < 0 || x >= config.width || y < 0 || y >= config.height {
+                    writer.push_cell(State(6));
+                    continue;
+                }
+                let mut on = Vec::with_capacity(patterns.len());
+                for (t, pattern) in patterns.iter().enumerate() {
+                    let state = pattern
+                        .get_cell_state(x, y)
+                        .ok_or(Error::UnknownCellError((x, y, t as isize)))?;
+                    on.push(state == ALIVE);
+                }
+                let state = if on.iter().all(|&on| on) {
+                    State(3)
+                } else if on[0] {
+                    ALIVE
+                } else if on.iter().any(|&on| on) {
+                    State(2)
+                } else {
+                    DEAD
                 };
+                writer.push_cell(state);
             }
-            if y == self.config().height - 1 {
-                str.push('!')
-            } else {
-                str.push('$')
-            };
-            str.push('\n');
+            writer.end_row();
         }
-        str
+        Ok(writer.finish())
     }
 
     /// Displays the whole world in some generation in
@@ -111,11 +351,11 @@ pub trait Search {
     /// * **Living** and **Dying** cells are represented by `o`;
     /// * **Unknown** cells are represented by `?`.
     fn plaintext_gen(&self, t: isize) -> String {
+        let pattern = self.pattern(t);
         let mut str = String::new();
-        for y in 0..self.config().height {
-            for x in 0..self.config().width {
-                let state = self.get_cell_state((x, y, t)).unwrap();
-                match state {
+        for y in 0..pattern.height() {
+            for x in 0..pattern.width() {
+                match pattern.get_cell_state(x, y) {
                     Some(DEAD) => str.push('.'),
                     Some(_) => str.push('o'),
                     None => str.push('?'),
@@ -137,6 +377,10 @@ impl<'a, R: Rule> Search for World<'a, R> {
         self.get_cell_state(coord)
     }
 
+    fn pattern(&self, t: isize) -> Pattern {
+        self.pattern(t)
+    }
+
     fn config(&self) -> &Config {
         &self.config
     }
@@ -157,10 +401,46 @@ impl<'a, R: Rule> Search for World<'a, R> {
         self.conflicts
     }
 
+    fn depth(&self) -> usize {
+        self.depth()
+    }
+
+    fn deepest_pattern(&self, t: isize) -> Option<Pattern> {
+        self.deepest_pattern(t)
+    }
+
+    fn optimum(&self) -> Option<usize> {
+        self.best
+    }
+
+    fn proof(&self) -> Option<&Proof> {
+        self.proof.as_ref()
+    }
+
+    fn take_proof(&mut self) -> Option<Proof> {
+        self.proof.as_mut().map(mem::take)
+    }
+
+    fn explain(&self, coord: Coord) -> Result<Vec<Deduction>, Error> {
+        self.explain(coord)
+    }
+
+    fn explain_root_conflict(&self) -> Option<&[Deduction]> {
+        self.root_conflict.as_deref()
+    }
+
+    fn conflict_map(&self, t: isize) -> Option<Vec<Vec<u64>>> {
+        Some(self.conflict_map(t))
+    }
+
     fn set_max_cell_count(&mut self, max_cell_count: Option<usize>) {
         self.set_max_cell_count(max_cell_count)
     }
 
+    fn verify(&self) -> Result<(), Error> {
+        self.verify()
+    }
+
     #[cfg(feature = "serialize")]
     fn ser(&self) -> WorldSer {
         self.ser()
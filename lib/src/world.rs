@@ -1,12 +1,17 @@
 //! The world.
 
 use crate::{
-    cells::{CellRef, Coord, LifeCell, State, ALIVE, DEAD},
-    config::{Config, SearchOrder, Symmetry, Transform},
+    cells::{CellRef, Coord, LifeCell, State, ALIVE, DEAD, NBHD},
+    config::{Config, KnownCell, Objective, SearchOrder, Symmetry, Transform},
     error::Error,
+    explain::{Deducer, Deduction},
+    pattern::Pattern,
+    proof::Proof,
     rules::Rule,
     search::{Reason, SetCell},
 };
+use rand::{rngs::StdRng, SeedableRng};
+use std::collections::HashSet;
 
 /// The world.
 pub struct World<'a, R: Rule> {
@@ -16,12 +21,28 @@ pub struct World<'a, R: Rule> {
     /// The rule of the cellular automaton.
     pub(crate) rule: R,
 
-    /// A vector that stores all the cells in the search range.
+    /// The cells in the search range and on its boundary, line by line
+    /// across the search order, i.e., a row for each `y` from `-1`
+    /// to `height` when the search order is `RowFirst`, and a column
+    /// for each `x` otherwise.
     ///
-    /// This vector will not be moved after its creation.
-    /// All the cells will live throughout the lifetime of the world.
+    /// In a growing world, each line is in its own vector. A line will
+    /// not be moved after its creation, and the world only adds lines
+    /// to the end or removes them from the end, so the cells live as long
+    /// as any reference to them. Otherwise, all cells are in a single
+    /// vector, which will not be moved after its creation.
     // So the unsafe code below is actually safe.
-    cells: Vec<LifeCell<'a, R>>,
+    cells: Vec<Vec<LifeCell<'a, R>>>,
+
+    /// The still life formed by the catalyst in a catalyst search.
+    ///
+    /// A cell for each position in the search range and on its boundary,
+    /// with time coordinate `-1`. Each cell is its own predecessor and
+    /// successor. Outside the active region, it equals the cell in
+    /// generation 0; in the active region, it is always dead.
+    ///
+    /// Empty in other searches. It will not be moved after its creation.
+    still: Vec<LifeCell<'a, R>>,
 
     /// A list of references to cells sorted by the search order.
     ///
@@ -36,9 +57,77 @@ pub struct World<'a, R: Rule> {
     /// Number of unknown or living cells on the first row or column.
     pub(crate) front_cell_count: usize,
 
+    /// Numbers of known cells that differ from the background
+    /// in each column of the search range, in all generations.
+    ///
+    /// Only counted when the objective is `MinBoundingBox`.
+    col_counts: Vec<usize>,
+
+    /// Numbers of known cells that differ from the background
+    /// in each row of the search range, in all generations.
+    ///
+    /// Only counted when the objective is `MinBoundingBox`.
+    row_counts: Vec<usize>,
+
+    /// Numbers of known cells at each position of the search range,
+    /// in all generations, grouped by their states.
+    ///
+    /// The states are compared with the background, so that
+    /// for rules with `B0`, a cell that always equals the background
+    /// is not in the rotor.
+    ///
+    /// Only counted when the objective is about the rotor.
+    state_counts: Vec<usize>,
+
+    /// Number of positions whose known cells are not all in the same state.
+    rotor_count: usize,
+
+    /// Number of positions whose cells are known in all generations,
+    /// and all in the same state.
+    stable_count: usize,
+
+    /// The value of the objective in the last result,
+    /// when `reduce_max` is set.
+    pub(crate) best: Option<usize>,
+
+    /// Number of known cells in the last row (or column) that differ
+    /// from the background, in all generations.
+    ///
+    /// Only counted when the world grows.
+    edge_count: usize,
+
+    /// The lengths of the `set_stack` each time the world grew.
+    ///
+    /// When the search backtracks beyond one of these times,
+    /// the world shrinks back to the size before that time.
+    grow_marks: Vec<usize>,
+
     /// Number of conflicts during the search.
     pub(crate) conflicts: u64,
 
+    /// Number of conflicts found at each cell, in the same order as
+    /// the cells in the lines of `cells`.
+    ///
+    /// A conflict is found at a cell when `proceed` fails on it.
+    pub(crate) cell_conflicts: Vec<u64>,
+
+    /// Number of restarts during the search.
+    pub(crate) restarts: u64,
+
+    /// Number of conflicts at the last restart.
+    pub(crate) restart_conflicts: u64,
+
+    /// Whether a result has been found.
+    ///
+    /// The search no longer restarts after that, because a restart
+    /// discards the cells set by backtracking, which keep the results
+    /// found so far from being found again.
+    pub(crate) found: bool,
+
+    /// The random number generator used by `NewState::Random`,
+    /// seeded by the `seed` in the configuration.
+    pub(crate) rng: StdRng,
+
     /// A stack to record the cells whose values are set during the search.
     ///
     /// The cells in this stack always have known states.
@@ -55,6 +144,34 @@ pub struct World<'a, R: Rule> {
     ///
     /// Cells before this position are all known.
     pub(crate) search_index: usize,
+
+    /// The states of all cells, in the same order as `cell_conflicts`,
+    /// when the most cells were known since the last result,
+    /// and the length of the `set_stack` at that time.
+    ///
+    /// See `deepest_pattern` for details.
+    pub(crate) deepest: Option<(usize, Vec<Option<State>>)>,
+
+    /// The length of the `set_stack` when the most cells were known
+    /// since the last result, if the search has not backtracked since then.
+    ///
+    /// The states at that time are the current states of the cells
+    /// in the first part of the `set_stack`. They are only copied to
+    /// `deepest` when the search backtracks.
+    pub(crate) deeper: Option<usize>,
+
+    /// The proof of the search, if `proof` is set in the configuration.
+    pub(crate) proof: Option<Proof>,
+
+    /// The branch to the last leaf recorded in the `proof`.
+    pub(crate) proof_branch: Vec<(Coord, State)>,
+
+    /// What the cells are being deduced from in `proceed`.
+    pub(crate) deducer: Option<Deducer<'a, R>>,
+
+    /// The explanation of the last conflict found before any decision,
+    /// if `explain` is set in the configuration.
+    pub(crate) root_conflict: Option<Vec<Deduction>>,
 }
 
 impl<'a, R: Rule> World<'a, R> {
@@ -67,11 +184,136 @@ impl<'a, R: Rule> World<'a, R> {
     /// After the last generation, the pattern will return to
     /// the first generation, applying the transformation first,
     /// and then the translation defined by `dx` and `dy`.
+    ///
+    /// If the configuration is not periodic, the first generation has
+    /// no predecessor, and the last generation has no successor.
     pub fn new(config: &Config, rule: R) -> Self {
         let search_order = config.auto_search_order();
 
-        let size = ((config.width + 2) * (config.height + 2) * config.period) as usize;
-        let mut cells = Vec::with_capacity(size);
+        let lines = match search_order {
+            SearchOrder::RowFirst => config.height,
+            SearchOrder::ColumnFirst => config.width,
+        };
+        let mut cells = (-1..=lines)
+            .map(|along| World::new_line(config, &rule, along))
+            .collect::<Vec<_>>();
+        let size = cells.iter().map(Vec::len).sum();
+        if !config.grow {
+            let mut all = Vec::with_capacity(size);
+            for line in cells.drain(..) {
+                all.extend(line);
+            }
+            cells.push(all);
+        }
+
+        // For rules with `B0`, the cells in odd generations are initially alive,
+        // so they must be counted before they are cleared in `init_state`.
+        let in_range = |cell: &&LifeCell<R>| {
+            let (x, y, _) = cell.coord;
+            0 <= x && x < config.width && 0 <= y && y < config.height
+        };
+        let mut cell_count = vec![0; config.period as usize];
+        let mut front_cell_count = 0;
+        for cell in cells.iter().flatten().filter(in_range) {
+            if cell.background == ALIVE {
+                cell_count[cell.coord.2 as usize] += 1;
+                if cell.is_front {
+                    front_cell_count += 1;
+                }
+            }
+        }
+
+        // All cells are known at first, so each position is stable,
+        // until its cells are cleared in `init_state`.
+        let positions = (config.width * config.height) as usize;
+        let mut state_counts = Vec::new();
+        let mut stable_count = 0;
+        if matches!(config.objective, Objective::MinRotor | Objective::MaxRotor) {
+            state_counts = vec![0; positions * rule.gen()];
+            for position in 0..positions {
+                state_counts[position * rule.gen()] = config.period as usize;
+            }
+            stable_count = positions;
+        }
+
+        // The still life of the catalyst.
+        let mut still = Vec::new();
+        if config.catalyst {
+            still.reserve_exact(((config.width + 2) * (config.height + 2)) as usize);
+            for x in -1..=config.width {
+                for y in -1..=config.height {
+                    still.push(LifeCell::new((x, y, -1), DEAD, Some(DEAD)));
+                }
+            }
+        }
+
+        // The search order is fixed when the world grows,
+        // so that it does not change with the size.
+        let mut world_config = config.clone();
+        if config.grow {
+            world_config.search_order = Some(search_order);
+        }
+
+        World {
+            config: world_config,
+            rule,
+            cells,
+            still,
+            search_list: Vec::with_capacity(size),
+            cell_count,
+            front_cell_count,
+            col_counts: vec![0; config.width as usize],
+            row_counts: vec![0; config.height as usize],
+            state_counts,
+            rotor_count: 0,
+            stable_count,
+            best: None,
+            edge_count: 0,
+            grow_marks: Vec::new(),
+            conflicts: 0,
+            cell_conflicts: vec![0; size],
+            restarts: 0,
+            restart_conflicts: 0,
+            found: false,
+            rng: config
+                .seed
+                .map_or_else(StdRng::from_entropy, StdRng::seed_from_u64),
+            set_stack: Vec::with_capacity(size),
+            check_index: 0,
+            search_index: 0,
+            deepest: None,
+            deeper: None,
+            proof: if config.proof {
+                Some(Proof::default())
+            } else {
+                None
+            },
+            proof_branch: Vec::new(),
+            deducer: None,
+            root_conflict: None,
+        }
+        .init_links()
+        .init_pred()
+        .init_sym()
+        .init_stator()
+        .init_catalyst()
+        .init_state()
+        .init_search_order(search_order)
+    }
+
+    /// Creates the cells of a line across the search order,
+    /// at the given position along the search order.
+    ///
+    /// The cells are dead, and those on the first row or column
+    /// are marked as the front.
+    ///
+    /// If the rule contains `B0`, then fills the odd generations
+    /// with living cells instead.
+    ///
+    /// Without periodicity, the front is not needed, and the successors
+    /// of the last generation are unknown.
+    fn new_line(config: &Config, rule: &R, along: isize) -> Vec<LifeCell<'a, R>> {
+        let search_order = config.auto_search_order();
 
         // Whether to consider only the first generation of the front.
         let front_gen0 = match search_order {
@@ -93,220 +335,323 @@ impl<'a, R: Rule> World<'a, R> {
             _ => front_gen0,
         };
 
-        // Fills the vector with dead cells,
-        // and checks whether it is on the first row or column.
-        //
-        // If the rule contains `B0`, then fills the odd generations
-        // with living cells instead.
-        for x in -1..=config.width {
-            for y in -1..=config.height {
-                for t in 0..config.period {
-                    let state = if rule.has_b0() && t % 2 == 1 {
-                        ALIVE
-                    } else {
-                        DEAD
-                    };
-                    let mut cell = LifeCell::new((x, y, t), state, rule.has_b0());
-                    match search_order {
-                        SearchOrder::ColumnFirst => {
-                            if front_gen0 {
-                                if x == (config.dx - 1).max(0)
-                                    && t == 0
-                                    && (!front_half || 2 * y < config.height)
-                                {
-                                    cell.is_front = true
-                                }
-                            } else if x == 0 {
+        let cross_size = match search_order {
+            SearchOrder::RowFirst => config.width,
+            SearchOrder::ColumnFirst => config.height,
+        };
+        let mut line = Vec::with_capacity(((cross_size + 2) * config.period) as usize);
+        for across in -1..=cross_size {
+            let (x, y) = match search_order {
+                SearchOrder::RowFirst => (across, along),
+                SearchOrder::ColumnFirst => (along, across),
+            };
+            for t in 0..config.period {
+                let state = if rule.has_b0() && t % 2 == 1 {
+                    ALIVE
+                } else {
+                    DEAD
+                };
+                let succ_state = if !config.periodic && t == config.period - 1 {
+                    None
+                } else if rule.has_b0() {
+                    Some(!state)
+                } else {
+                    Some(state)
+                };
+                let mut cell = LifeCell::new((x, y, t), state, succ_state);
+                match search_order {
+                    _ if !config.periodic => (),
+                    SearchOrder::ColumnFirst => {
+                        if front_gen0 {
+                            if x == (config.dx - 1).max(0)
+                                && t == 0
+                                && (!front_half || 2 * y < config.height)
+                            {
                                 cell.is_front = true
                             }
+                        } else if x == 0 {
+                            cell.is_front = true
                         }
-                        SearchOrder::RowFirst => {
-                            if front_gen0 {
-                                if y == (config.dy - 1).max(0)
-                                    && t == 0
-                                    && (!front_half || 2 * x < config.width)
-                                {
-                                    cell.is_front = true
-                                }
-                            } else if y == 0 {
+                    }
+                    SearchOrder::RowFirst => {
+                        if front_gen0 {
+                            if y == (config.dy - 1).max(0)
+                                && t == 0
+                                && (!front_half || 2 * x < config.width)
+                            {
                                 cell.is_front = true
                             }
+                        } else if y == 0 {
+                            cell.is_front = true
                         }
                     }
-                    cells.push(cell);
                 }
+                line.push(cell);
             }
         }
-
-        World {
-            config: config.clone(),
-            rule,
-            cells,
-            search_list: Vec::with_capacity(size),
-            cell_count: vec![0; config.period as usize],
-            front_cell_count: 0,
-            conflicts: 0,
-            set_stack: Vec::with_capacity(size),
-            check_index: 0,
-            search_index: 0,
-        }
-        .init_nbhd()
-        .init_pred_succ()
-        .init_sym()
-        .init_state()
-        .init_search_order(search_order)
+        line
     }
 
-    /// Links the cells to their neighbors.
-    ///
-    /// Note that for cells on the edges of the search range,
-    /// some neighbors might point to `None`.
-    fn init_nbhd(mut self) -> Self {
-        const NBHD: [(isize, isize); 8] = [
-            (-1, -1),
-            (-1, 0),
-            (-1, 1),
-            (0, -1),
-            (0, 1),
-            (1, -1),
-            (1, 0),
-            (1, 1),
-        ];
+    /// Links the cells to their neighbors, predecessors and successors.
+    fn init_links(mut self) -> Self {
         for x in -1..=self.config.width {
             for y in -1..=self.config.height {
                 for t in 0..self.config.period {
-                    let cell_ptr = self.find_cell_mut((x, y, t)).unwrap();
-                    for (i, (nx, ny)) in NBHD.iter().enumerate() {
-                        unsafe {
-                            let cell = cell_ptr.as_mut().unwrap();
-                            cell.nbhd[i] = self.find_cell((x + nx, y + ny, t));
-                        }
-                    }
+                    self.link((x, y, t));
                 }
             }
         }
         self
     }
 
-    /// Links a cell to its predecessor and successor.
+    /// Links a cell to its neighbors, its predecessor and its successor.
     ///
-    /// If the predecessor is out of the search range,
-    /// then marks the current cell as known.
+    /// Note that for cells on the edges of the search range,
+    /// some neighbors might point to `None`. So does the predecessor
+    /// or the successor if it is out of the search range.
     ///
-    /// If the successor is out of the search range,
-    /// then sets it to `None`.
-    fn init_pred_succ(mut self) -> Self {
-        for x in -1..=self.config.width {
-            for y in -1..=self.config.height {
-                for t in 0..self.config.period {
-                    let cell_ptr = self.find_cell_mut((x, y, t)).unwrap();
-                    let cell = self.find_cell((x, y, t)).unwrap();
-
-                    if t != 0 {
-                        unsafe {
-                            let cell = cell_ptr.as_mut().unwrap();
-                            cell.pred = self.find_cell((x, y, t - 1));
-                        }
-                    } else {
-                        let pred = self.find_cell(self.config.translate((x, y, t - 1)));
-                        if pred.is_some() {
-                            unsafe {
-                                let cell = cell_ptr.as_mut().unwrap();
-                                cell.pred = pred;
-                            }
-                        } else if 0 <= x
-                            && x < self.config.width
-                            && 0 <= y
-                            && y < self.config.height
-                            && !self.set_stack.iter().any(|s| s.cell == cell)
-                        {
-                            self.set_stack.push(SetCell::new(cell, Reason::Deduce));
-                        }
-                    }
+    /// If the configuration is not periodic, the cells in the first generation
+    /// have no predecessors, and those in the last generation have no successors.
+    fn link(&mut self, coord: Coord) {
+        let (x, y, t) = coord;
+        let cell_ptr = self.find_cell_mut(coord).unwrap();
+        let pred = if t != 0 {
+            self.find_cell((x, y, t - 1))
+        } else if self.config.periodic {
+            self.find_cell(self.config.translate((x, y, t - 1)))
+        } else {
+            None
+        };
+        let succ = if t != self.config.period - 1 {
+            self.find_cell((x, y, t + 1))
+        } else if self.config.periodic {
+            self.find_cell(self.config.translate((x, y, t + 1)))
+        } else {
+            None
+        };
+        unsafe {
+            let cell = cell_ptr.as_mut().unwrap();
+            for (i, (nx, ny)) in NBHD.iter().enumerate() {
+                cell.nbhd[i] = self.find_cell((x + nx, y + ny, t));
+            }
+            cell.pred = pred;
+            cell.succ = succ;
+        }
+    }
 
-                    if t != self.config.period - 1 {
-                        unsafe {
-                            let cell = cell_ptr.as_mut().unwrap();
-                            cell.succ = self.find_cell((x, y, t + 1));
-                        }
-                    } else {
-                        unsafe {
-                            let cell = cell_ptr.as_mut().unwrap();
-                            cell.succ = self.find_cell(self.config.translate((x, y, t + 1)));
-                        }
-                    }
+    /// Marks the cells in the search range without predecessors as known.
+    ///
+    /// See `lacks_pred` for details.
+    fn init_pred(mut self) -> Self {
+        for x in 0..self.config.width {
+            for y in 0..self.config.height {
+                let cell = self.find_cell((x, y, 0)).unwrap();
+                if self.lacks_pred(cell) && !self.set_stack.iter().any(|s| s.cell == cell) {
+                    self.set_stack
+                        .push(SetCell::new(cell, Reason::Deduce, None));
                 }
             }
         }
         self
     }
 
+    /// Whether a cell in the search range must keep its background state
+    /// because its predecessor is out of the search range.
+    ///
+    /// This is not the case if the predecessor is beyond the end
+    /// of a growing world, or if the configuration is not periodic.
+    fn lacks_pred(&self, cell: CellRef<'a, R>) -> bool {
+        let (x, y, t) = cell.coord;
+        if !self.config.periodic || t != 0 || cell.pred.is_some() {
+            return false;
+        }
+        let (pred_x, pred_y, _) = self.config.translate((x, y, t - 1));
+        !self.beyond_end((pred_x, pred_y))
+    }
+
     /// Links a cell to the symmetric cells.
     ///
     /// If some symmetric cell is out of the search range,
-    /// then  marks the current cell as known.
+    /// then marks the current cell as known.
     fn init_sym(mut self) -> Self {
         for x in -1..=self.config.width {
             for y in -1..=self.config.height {
                 for t in 0..self.config.period {
-                    let cell_ptr = self.find_cell_mut((x, y, t)).unwrap();
                     let cell = self.find_cell((x, y, t)).unwrap();
+                    if !self.link_sym((x, y, t))
+                        && 0 <= x
+                        && x < self.config.width
+                        && 0 <= y
+                        && y < self.config.height
+                        && !self.set_stack.iter().any(|s| s.cell == cell)
+                    {
+                        self.set_stack
+                            .push(SetCell::new(cell, Reason::Deduce, None));
+                    }
+                }
+            }
+        }
+        self
+    }
 
-                    let sym_coords = match self.config.symmetry {
-                        Symmetry::C1 => vec![],
-                        Symmetry::C2 => {
-                            vec![(self.config.width - 1 - x, self.config.height - 1 - y, t)]
-                        }
-                        Symmetry::C4 => vec![
-                            (y, self.config.width - 1 - x, t),
-                            (self.config.width - 1 - x, self.config.height - 1 - y, t),
-                            (self.config.height - 1 - y, x, t),
-                        ],
-                        Symmetry::D2Row => vec![(x, self.config.height - 1 - y, t)],
-                        Symmetry::D2Col => vec![(self.config.width - 1 - x, y, t)],
-                        Symmetry::D2Diag => vec![(y, x, t)],
-                        Symmetry::D2Antidiag => {
-                            vec![(self.config.height - 1 - y, self.config.width - 1 - x, t)]
-                        }
-                        Symmetry::D4Ortho => vec![
-                            (self.config.width - 1 - x, y, t),
-                            (x, self.config.height - 1 - y, t),
-                            (self.config.width - 1 - x, self.config.height - 1 - y, t),
-                        ],
-                        Symmetry::D4Diag => vec![
-                            (y, x, t),
-                            (self.config.height - 1 - y, self.config.width - 1 - x, t),
-                            (self.config.width - 1 - x, self.config.height - 1 - y, t),
-                        ],
-                        Symmetry::D8 => vec![
-                            (y, self.config.width - 1 - x, t),
-                            (self.config.height - 1 - y, x, t),
-                            (self.config.width - 1 - x, y, t),
-                            (x, self.config.height - 1 - y, t),
-                            (y, x, t),
-                            (self.config.height - 1 - y, self.config.width - 1 - x, t),
-                            (self.config.width - 1 - x, self.config.height - 1 - y, t),
-                        ],
-                    };
-                    for coord in sym_coords {
-                        if 0 <= coord.0
-                            && coord.0 < self.config.width
-                            && 0 <= coord.1
-                            && coord.1 < self.config.height
-                        {
-                            unsafe {
-                                let cell = cell_ptr.as_mut().unwrap();
-                                cell.sym.push(self.find_cell(coord).unwrap());
-                            }
-                        } else if 0 <= x
-                            && x < self.config.width
-                            && 0 <= y
-                            && y < self.config.height
-                            && !self.set_stack.iter().any(|s| s.cell == cell)
-                        {
-                            self.set_stack.push(SetCell::new(cell, Reason::Deduce));
+    /// Links a cell to the symmetric cells in the search range.
+    ///
+    /// Returns `false` if some symmetric cell is out of the search range.
+    fn link_sym(&mut self, coord: Coord) -> bool {
+        let (x, y, t) = coord;
+        let cell_ptr = self.find_cell_mut(coord).unwrap();
+        let sym_coords = match self.config.symmetry {
+            Symmetry::C1 => vec![],
+            Symmetry::C2 => {
+                vec![(self.config.width - 1 - x, self.config.height - 1 - y, t)]
+            }
+            Symmetry::C4 => vec![
+                (y, self.config.width - 1 - x, t),
+                (self.config.width - 1 - x, self.config.height - 1 - y, t),
+                (self.config.height - 1 - y, x, t),
+            ],
+            Symmetry::D2Row => vec![(x, self.config.height - 1 - y, t)],
+            Symmetry::D2Col => vec![(self.config.width - 1 - x, y, t)],
+            Symmetry::D2Diag => vec![(y, x, t)],
+            Symmetry::D2Antidiag => {
+                vec![(self.config.height - 1 - y, self.config.width - 1 - x, t)]
+            }
+            Symmetry::D4Ortho => vec![
+                (self.config.width - 1 - x, y, t),
+                (x, self.config.height - 1 - y, t),
+                (self.config.width - 1 - x, self.config.height - 1 - y, t),
+            ],
+            Symmetry::D4Diag => vec![
+                (y, x, t),
+                (self.config.height - 1 - y, self.config.width - 1 - x, t),
+                (self.config.width - 1 - x, self.config.height - 1 - y, t),
+            ],
+            Symmetry::D8 => vec![
+                (y, self.config.width - 1 - x, t),
+                (self.config.height - 1 - y, x, t),
+                (self.config.width - 1 - x, y, t),
+                (x, self.config.height - 1 - y, t),
+                (y, x, t),
+                (self.config.height - 1 - y, self.config.width - 1 - x, t),
+                (self.config.width - 1 - x, self.config.height - 1 - y, t),
+            ],
+        };
+        let mut result = true;
+        for coord in sym_coords {
+            if 0 <= coord.0
+                && coord.0 < self.config.width
+                && 0 <= coord.1
+                && coord.1 < self.config.height
+            {
+                unsafe {
+                    let cell = cell_ptr.as_mut().unwrap();
+                    cell.sym.push(self.find_cell(coord).unwrap());
+                }
+            } else {
+                result = false;
+            }
+        }
+        result
+    }
+
+    /// Links the cells in the stator to the cells
+    /// at the same position in other generations.
+    fn init_stator(mut self) -> Self {
+        if !self.config.stator {
+            return self;
+        }
+        let rotor = self.rotor();
+        for x in 0..self.config.width {
+            for y in 0..self.config.height {
+                self.link_stator((x, y), &rotor);
+            }
+        }
+        self
+    }
+
+    /// The positions of the known cells, which form the rotor
+    /// in a stator search.
+    fn rotor(&self) -> HashSet<(isize, isize)> {
+        self.config
+            .known_cells
+            .iter()
+            .map(|known| {
+                let (x, y, _) = self.config.translate(known.coord);
+                (x, y)
+            })
+            .collect()
+    }
+
+    /// Links the cells at a position to the cells at the same position
+    /// in other generations, if the position is in the stator.
+    ///
+    /// A position is in the stator if neither it nor any position equal to it
+    /// by the symmetry is in the `rotor`.
+    fn link_stator(&mut self, pos: (isize, isize), rotor: &HashSet<(isize, isize)>) {
+        let (x, y) = pos;
+        let cell = self.find_cell((x, y, 0)).unwrap();
+        if rotor.contains(&(x, y))
+            || cell
+                .sym
+                .iter()
+                .any(|sym| rotor.contains(&(sym.coord.0, sym.coord.1)))
+        {
+            return;
+        }
+        for t in 0..self.config.period {
+            let cell_ptr = self.find_cell_mut((x, y, t)).unwrap();
+            for other in 0..self.config.period {
+                if other != t {
+                    unsafe {
+                        let cell = cell_ptr.as_mut().unwrap();
+                        cell.sym.push(self.find_cell((x, y, other)).unwrap());
+                    }
+                }
+            }
+        }
+    }
+
+    /// Links the cells of the still life in a catalyst search.
+    ///
+    /// Outside the active region, the still life equals generation 0,
+    /// and the last generation also equals generation 0.
+    fn init_catalyst(mut self) -> Self {
+        if !self.config.catalyst {
+            return self;
+        }
+        let active = self.config.active_cells();
+        let last = self.config.period - 1;
+        for x in -1..=self.config.width {
+            for y in -1..=self.config.height {
+                let cell_ptr = self.find_still_mut((x, y)).unwrap();
+                let cell = self.find_still((x, y)).unwrap();
+                unsafe {
+                    let cell_mut = cell_ptr.as_mut().unwrap();
+                    cell_mut.pred = Some(cell);
+                    cell_mut.succ = Some(cell);
+                    for (i, (nx, ny)) in NBHD.iter().enumerate() {
+                        cell_mut.nbhd[i] = self.find_still((x + nx, y + ny));
+                    }
+                }
+                if 0 <= x
+                    && x < self.config.width
+                    && 0 <= y
+                    && y < self.config.height
+                    && !active.contains(&(x, y))
+                {
+                    let first_ptr = self.find_cell_mut((x, y, 0)).unwrap();
+                    let first = self.find_cell((x, y, 0)).unwrap();
+                    let last_ptr = self.find_cell_mut((x, y, last)).unwrap();
+                    let last = self.find_cell((x, y, last)).unwrap();
+                    unsafe {
+                        cell_ptr.as_mut().unwrap().sym.push(first);
+                        first_ptr.as_mut().unwrap().sym.push(cell);
+                        if first != last {
+                            first_ptr.as_mut().unwrap().sym.push(last);
+                            last_ptr.as_mut().unwrap().sym.push(first);
                         }
                     }
+                    self.clear_cell(cell);
                 }
             }
         }
@@ -316,13 +661,20 @@ impl<'a, R: Rule> World<'a, R> {
     /// Sets states for the cells.
     ///
     /// All cells are set to unknown unless they are on the boundary,
-    /// or are marked as known in `init_pred_succ` or `init_sym`.
+    /// or are marked as known in `init_pred` or `init_sym`.
+    ///
+    /// When the world grows, the cells on the boundary just beyond
+    /// the end of the search range are also unknown.
     fn init_state(mut self) -> Self {
-        for x in 0..self.config.width {
-            for y in 0..self.config.height {
+        for x in -1..=self.config.width {
+            for y in -1..=self.config.height {
                 for t in 0..self.config.period {
                     let cell = self.find_cell((x, y, t)).unwrap();
-                    if !self.set_stack.iter().any(|s| s.cell == cell) {
+                    let in_range =
+                        0 <= x && x < self.config.width && 0 <= y && y < self.config.height;
+                    if (in_range || self.is_open(cell))
+                        && !self.set_stack.iter().any(|s| s.cell == cell)
+                    {
                         self.clear_cell(cell);
                     }
                 }
@@ -331,6 +683,44 @@ impl<'a, R: Rule> World<'a, R> {
         self
     }
 
+    /// Sets the known cells in the configuration.
+    ///
+    /// Returns an error if a known cell contradicts the background
+    /// or another known cell, if its state is invalid for the rule,
+    /// or if it violates the max cell count, the bound of the objective
+    /// or the front.
+    pub(crate) fn init_known_cells(mut self) -> Result<Self, Error> {
+        for &KnownCell { coord, state } in self.config.known_cells.clone().iter() {
+            if state.0 >= self.rule.gen() {
+                return Err(Error::KnownStateError(coord));
+            }
+            let (x, y, t) = self.config.translate(coord);
+            if let Some(cell) = self.find_cell((x, y, t)) {
+                match cell.state.get() {
+                    Some(old_state) if old_state != state => {
+                        return Err(Error::SetCellError(coord))
+                    }
+                    Some(_) => (),
+                    None => {
+                        if !self.set_cell(cell, state, Reason::Deduce) {
+                            return Err(Error::KnownCellError(coord));
+                        }
+                    }
+                }
+            } else {
+                let background = if self.rule.has_b0() && t % 2 == 1 {
+                    ALIVE
+                } else {
+                    DEAD
+                };
+                if state != background {
+                    return Err(Error::SetCellError(coord));
+                }
+            }
+        }
+        Ok(self)
+    }
+
     /// Sets the search order.
     fn init_search_order(mut self, search_order: SearchOrder) -> Self {
         match search_order {
@@ -358,53 +748,111 @@ impl<'a, R: Rule> World<'a, R> {
         self
     }
 
-    /// Finds a cell by its coordinates. Returns a `CellRef`.
-    pub(crate) fn find_cell(&self, coord: Coord) -> Option<CellRef<'a, R>> {
+    /// The index of a cell by its coordinates, counting the cells
+    /// in the lines of `cells` one after another.
+    ///
+    /// It does not change when a line is added to or removed from the end.
+    fn cell_index(&self, coord: Coord) -> Option<usize> {
         let (x, y, t) = coord;
-        if x >= -1
-            && x <= self.config.width
-            && y >= -1
-            && y <= self.config.height
+        let (along, across, size, cross_size) = self.along_across((x, y));
+        if along >= -1
+            && along <= size
+            && across >= -1
+            && across <= cross_size
             && t >= 0
             && t < self.config.period
         {
-            let index = ((x + 1) * (self.config.height + 2) + y + 1) * self.config.period + t;
-            let cell = &self.cells[index as usize];
-            Some(cell.borrow())
+            let index = ((along + 1) * (cross_size + 2) + across + 1) * self.config.period + t;
+            Some(index as usize)
         } else {
             None
         }
     }
 
-    /// Finds a cell by its coordinates. Returns a mutable pointer.
-    fn find_cell_mut(&mut self, coord: Coord) -> Option<*mut LifeCell<'a, R>> {
-        let (x, y, t) = coord;
-        if x >= -1
+    /// Number of cells in a line of `cells`.
+    fn line_len(&self) -> usize {
+        let (_, _, _, cross_size) = self.along_across((0, 0));
+        ((cross_size + 2) * self.config.period) as usize
+    }
+
+    /// The index of a cell of the still life in `still` by its position.
+    fn still_index(&self, pos: (isize, isize)) -> Option<usize> {
+        let (x, y) = pos;
+        if !self.still.is_empty()
+            && x >= -1
             && x <= self.config.width
             && y >= -1
             && y <= self.config.height
-            && t >= 0
-            && t < self.config.period
         {
-            let index = ((x + 1) * (self.config.height + 2) + y + 1) * self.config.period + t;
-            Some(&mut self.cells[index as usize])
+            Some(((x + 1) * (self.config.height + 2) + y + 1) as usize)
         } else {
             None
         }
     }
 
+    /// Finds a cell of the still life by its position. Returns a `CellRef`.
+    pub(crate) fn find_still(&self, pos: (isize, isize)) -> Option<CellRef<'a, R>> {
+        self.still_index(pos)
+            .map(|index| self.still[index].borrow())
+    }
+
+    /// Finds a cell of the still life by its position. Returns a mutable pointer.
+    fn find_still_mut(&mut self, pos: (isize, isize)) -> Option<*mut LifeCell<'a, R>> {
+        self.still_index(pos)
+            .map(|index| &mut self.still[index] as *mut _)
+    }
+
+    /// All cells of the still life in a catalyst search.
+    pub(crate) fn still_cells(&self) -> impl Iterator<Item = CellRef<'a, R>> + '_ {
+        self.still.iter().map(LifeCell::borrow)
+    }
+
+    /// The vector in `cells` containing the cell of the given index,
+    /// and the position of the cell in it.
+    fn split_index(&self, index: usize) -> (usize, usize) {
+        if self.config.grow {
+            let len = self.line_len();
+            (index / len, index % len)
+        } else {
+            (0, index)
+        }
+    }
+
+    /// Finds a cell by its coordinates. Returns a `CellRef`.
+    pub(crate) fn find_cell(&self, coord: Coord) -> Option<CellRef<'a, R>> {
+        self.cell_index(coord).map(|index| {
+            let (i, j) = self.split_index(index);
+            self.cells[i][j].borrow()
+        })
+    }
+
+    /// Finds a cell by its coordinates. Returns a mutable pointer.
+    fn find_cell_mut(&mut self, coord: Coord) -> Option<*mut LifeCell<'a, R>> {
+        self.cell_index(coord).map(|index| {
+            let (i, j) = self.split_index(index);
+            &mut self.cells[i][j] as *mut _
+        })
+    }
+
     /// Sets the `state` of a cell, push it to the `set_stack`,
     /// and update the neighborhood descriptor of its neighbors.
     ///
     /// The original state of the cell must be unknown.
     ///
-    /// Return `false` if the number of living cells exceeds the `max_cell_count`
-    /// or the front becomes empty.
+    /// Return `false` if the number of living cells exceeds the `max_cell_count`,
+    /// the objective exceeds its bound, or the front becomes empty.
+    ///
+    /// Cells of the still life in a catalyst search are not counted.
     pub(crate) fn set_cell(&mut self, cell: CellRef<'a, R>, state: State, reason: Reason) -> bool {
+        let cause = if self.config.explain {
+            Some(Box::new(self.cause(cell, reason)))
+        } else {
+            None
+        };
         cell.state.set(Some(state));
         let mut result = true;
         cell.update_desc(Some(state), true);
-        if state == ALIVE {
+        if state == ALIVE && cell.coord.2 >= 0 && !self.is_open(cell) {
             self.cell_count[cell.coord.2 as usize] += 1;
             if let Some(max) = self.config.max_cell_count {
                 if *self.cell_count.iter().min().unwrap() > max {
@@ -412,13 +860,22 @@ impl<'a, R: Rule> World<'a, R> {
                 }
             }
         }
+        if cell.coord.2 >= 0 && self.config.objective != Objective::MinCellCount {
+            self.count_objective(cell, state, true);
+            if !self.within_bound() {
+                result = false;
+            }
+        }
+        if self.config.grow && state != cell.background && self.is_edge(cell) {
+            self.edge_count += 1;
+        }
         if cell.is_front && state == DEAD {
             self.front_cell_count -= 1;
             if self.config.non_empty_front && self.front_cell_count == 0 {
                 result = false;
             }
         }
-        self.set_stack.push(SetCell::new(cell, reason));
+        self.set_stack.push(SetCell::new(cell, reason, cause));
         result
     }
 
@@ -428,15 +885,531 @@ impl<'a, R: Rule> World<'a, R> {
         let old_state = cell.state.take();
         if old_state != None {
             cell.update_desc(old_state, false);
-            if old_state == Some(ALIVE) {
+            if old_state == Some(ALIVE) && cell.coord.2 >= 0 && !self.is_open(cell) {
                 self.cell_count[cell.coord.2 as usize] -= 1;
             }
+            if cell.coord.2 >= 0 && self.config.objective != Objective::MinCellCount {
+                self.count_objective(cell, old_state.unwrap(), false);
+            }
+            if self.config.grow && old_state != Some(cell.background) && self.is_edge(cell) {
+                self.edge_count -= 1;
+            }
             if cell.is_front && old_state == Some(DEAD) {
                 self.front_cell_count += 1;
             }
         }
     }
 
+    /// The coordinates of a position along and across the search order,
+    /// and the sizes of the world in these two directions.
+    fn along_across(&self, pos: (isize, isize)) -> (isize, isize, isize, isize) {
+        match self.config.auto_search_order() {
+            SearchOrder::RowFirst => (pos.1, pos.0, self.config.height, self.config.width),
+            SearchOrder::ColumnFirst => (pos.0, pos.1, self.config.width, self.config.height),
+        }
+    }
+
+    /// Whether a position is in the search range.
+    fn in_range(&self, pos: (isize, isize)) -> bool {
+        let (x, y) = pos;
+        0 <= x && x < self.config.width && 0 <= y && y < self.config.height
+    }
+
+    /// The position along the search order, counting from the row
+    /// (or column) just beyond the end of the search range.
+    ///
+    /// Returns `None` if the position is out of the search range
+    /// across the search order.
+    fn pos_from_end(&self, pos: (isize, isize)) -> Option<isize> {
+        let (along, across, size, cross_size) = self.along_across(pos);
+        if 0 <= across && across < cross_size {
+            Some(along - size)
+        } else {
+            None
+        }
+    }
+
+    /// Whether a position is beyond the end of a growing world.
+    fn beyond_end(&self, pos: (isize, isize)) -> bool {
+        self.config.grow && matches!(self.pos_from_end(pos), Some(n) if n >= 0)
+    }
+
+    /// Whether a cell is on the boundary just beyond the end of a growing world.
+    ///
+    /// Such cells are unknown rather than the background,
+    /// and are not counted in the `cell_count`, until the pattern is closed.
+    pub(crate) fn is_open(&self, cell: CellRef<'a, R>) -> bool {
+        let (x, y, t) = cell.coord;
+        self.config.grow && t >= 0 && self.pos_from_end((x, y)) == Some(0)
+    }
+
+    /// Whether a cell is not consistified in a growing world,
+    /// because the rule applied at it involves the cells after
+    /// the boundary just beyond the end.
+    ///
+    /// These cells are the background for now, but they become unknown
+    /// when the world grows, so nothing should be deduced from them.
+    /// Such a cell is on or after the boundary just beyond the end,
+    /// or in the last generation with its successor after that boundary.
+    /// It is only consistified after the world grows enough,
+    /// or when the pattern is closed.
+    ///
+    /// Only called in a growing world.
+    pub(crate) fn is_loose(&self, cell: CellRef<'a, R>) -> bool {
+        let (x, y, t) = cell.coord;
+        if t < 0 {
+            return false;
+        }
+        let (along, _, size, _) = self.along_across((x, y));
+        if along >= size {
+            true
+        } else if self.config.periodic && t == self.config.period - 1 {
+            let (succ_x, succ_y, _) = self.config.translate((x, y, t + 1));
+            matches!(self.pos_from_end((succ_x, succ_y)), Some(n) if n > 0)
+        } else {
+            false
+        }
+    }
+
+    /// Whether a cell is in the last row (or column) of a growing world,
+    /// or on the boundary just beyond it.
+    fn is_edge(&self, cell: CellRef<'a, R>) -> bool {
+        let (x, y, t) = cell.coord;
+        t >= 0 && matches!(self.pos_from_end((x, y)), Some(-1 | 0))
+    }
+
+    /// The size of the world along the search order,
+    /// i.e., the height (or the width).
+    fn size_along(&self) -> isize {
+        match self.config.auto_search_order() {
+            SearchOrder::RowFirst => self.config.height,
+            SearchOrder::ColumnFirst => self.config.width,
+        }
+    }
+
+    /// Sets the size of the world along the search order.
+    fn set_size_along(&mut self, size: isize) {
+        match self.config.auto_search_order() {
+            SearchOrder::RowFirst => self.config.height = size,
+            SearchOrder::ColumnFirst => self.config.width = size,
+        }
+    }
+
+    /// The translation along the search order after a period,
+    /// i.e., `dy` (or `dx`).
+    fn shift_along(&self) -> isize {
+        match self.config.auto_search_order() {
+            SearchOrder::RowFirst => self.config.dy,
+            SearchOrder::ColumnFirst => self.config.dx,
+        }
+    }
+
+    /// The cells in the line of `cells` at a position along the search order.
+    ///
+    /// Empty if there is no such line.
+    fn line(&self, along: isize) -> Vec<CellRef<'a, R>> {
+        if along < -1 {
+            return Vec::new();
+        }
+        let len = self.line_len();
+        let (i, j) = self.split_index((along + 1) as usize * len);
+        self.cells
+            .get(i)
+            .and_then(|cells| cells.get(j..j + len))
+            .map_or_else(Vec::new, |line| line.iter().map(LifeCell::borrow).collect())
+    }
+
+    /// Links the cells in a line to their neighbors, predecessors
+    /// and successors again, after a line is added or removed.
+    fn link_line(&mut self, along: isize) {
+        for cell in self.line(along) {
+            self.link(cell.coord);
+        }
+    }
+
+    /// Counts the `edge_count` again after the world grows or shrinks.
+    fn count_edge(&mut self) {
+        let size = self.size_along();
+        let edge = [self.line(size - 1), self.line(size)].concat();
+        self.edge_count = edge
+            .into_iter()
+            .filter(|&cell| {
+                self.is_edge(cell)
+                    && matches!(cell.state.get(), Some(state) if state != cell.background)
+            })
+            .count();
+    }
+
+    /// Changes the state of a cell, and updates the neighborhood descriptors,
+    /// without touching the `set_stack` or the counts.
+    fn replace_state(cell: CellRef<'a, R>, state: Option<State>) {
+        let old_state = cell.state.replace(state);
+        if old_state.is_some() {
+            cell.update_desc(old_state, false);
+        }
+        if state.is_some() {
+            cell.update_desc(state, true);
+        }
+    }
+
+    /// Whether the world should grow, i.e., the last row (or column)
+    /// or the boundary beyond it is nonempty.
+    pub(crate) fn should_grow(&self) -> bool {
+        self.config.grow && self.edge_count > 0
+    }
+
+    /// Adds a row (or a column) to the end of the world along the search order.
+    ///
+    /// The world grows in place: a new line of cells is added to the end,
+    /// and the cells are linked to it, so the cells which are already set
+    /// keep their states and their causes.
+    ///
+    /// The old boundary beyond the end becomes the last row, which is
+    /// consistified from now on, so there might be a conflict. The new
+    /// boundary beyond the end is unknown.
+    /// Returns `false` if there is a conflict,
+    /// `true` if the cells are consistent.
+    ///
+    /// The length of the `set_stack` is recorded in `grow_marks`, so that
+    /// the world shrinks when the search backtracks beyond this time.
+    pub(crate) fn grow(&mut self) -> bool {
+        let size = self.size_along() + 1;
+        if matches!(self.config.grow_limit, Some(limit) if size > limit) {
+            return false;
+        }
+        self.grow_marks.push(self.set_stack.len());
+        self.set_size_along(size);
+
+        // The new line is the background. So are its neighbors and successors
+        // for a moment, so that the neighborhood descriptors are consistent.
+        let shift = self.shift_along();
+        let nearby = [self.line(size - 1), self.line(size + shift)]
+            .concat()
+            .into_iter()
+            .filter(|cell| cell.state.get() != Some(cell.background))
+            .map(|cell| (cell, cell.state.get()))
+            .collect::<Vec<_>>();
+        for &(cell, _) in &nearby {
+            World::replace_state(cell, Some(cell.background));
+        }
+        let line = World::new_line(&self.config, &self.rule, size);
+        self.cells.push(line);
+        let len = self.cells.len() * self.line_len();
+        self.cell_conflicts.resize(len, 0);
+        for along in [size - 1, size, size - shift, size + shift] {
+            self.link_line(along);
+        }
+        for (cell, state) in nearby {
+            World::replace_state(cell, state);
+        }
+
+        // The new line is the open boundary beyond the end.
+        // The known cells there are the background, as checked by
+        // `init_known_cells`, but they are set again.
+        for cell in self.line(size) {
+            if self.is_open(cell) {
+                self.clear_cell(cell);
+            }
+        }
+        let mut result = true;
+        self.deducer = None;
+        for known in self.config.known_cells.clone() {
+            let (x, y, t) = self.config.translate(known.coord);
+            if self.pos_from_end((x, y)) == Some(0) {
+                let cell = self.find_cell((x, y, t)).unwrap();
+                if cell.state.get().is_none() {
+                    result &= self.set_cell(cell, known.state, Reason::Deduce);
+                }
+            }
+        }
+
+        // The old open boundary becomes the last row.
+        match self.config.auto_search_order() {
+            SearchOrder::RowFirst => self.row_counts.push(0),
+            SearchOrder::ColumnFirst => self.col_counts.push(0),
+        }
+        if !self.state_counts.is_empty() {
+            let (_, _, _, cross_size) = self.along_across((0, 0));
+            let len = self.state_counts.len() + cross_size as usize * self.rule.gen();
+            self.state_counts.resize(len, 0);
+        }
+        let last = self
+            .line(size - 1)
+            .into_iter()
+            .filter(|cell| self.in_range((cell.coord.0, cell.coord.1)))
+            .collect::<Vec<_>>();
+        let mut known = Vec::new();
+        for &cell in &last {
+            if let Some(state) = cell.state.get() {
+                if state == ALIVE {
+                    self.cell_count[cell.coord.2 as usize] += 1;
+                }
+                self.count_objective(cell, state, true);
+            }
+            if !self.link_sym(cell.coord) || self.lacks_pred(cell) {
+                known.push(cell);
+            }
+            self.search_list.push(cell);
+        }
+        if self.config.stator {
+            let rotor = self.rotor();
+            for &cell in last.iter().filter(|cell| cell.coord.2 == 0) {
+                self.link_stator((cell.coord.0, cell.coord.1), &rotor);
+            }
+        }
+        if matches!(self.config.max_cell_count, Some(max) if self.cell_count() > max)
+            || !self.within_bound()
+        {
+            result = false;
+        }
+        self.count_edge();
+
+        // The cells which are known as in `init_pred` and `init_sym`,
+        // and the cells equal to the known cells by the new links.
+        for cell in known {
+            match cell.state.get() {
+                Some(state) => result &= state == cell.background,
+                None => result &= self.set_cell(cell, cell.background, Reason::Deduce),
+            }
+        }
+        for &cell in &last {
+            if let Some(state) = cell.state.get() {
+                if self.config.explain {
+                    self.deducer = Some(Deducer::Symmetry(cell));
+                }
+                for &sym in cell.sym.iter() {
+                    match sym.state.get() {
+                        Some(old_state) => result &= old_state == state,
+                        None => result &= self.set_cell(sym, state, Reason::Deduce),
+                    }
+                }
+            }
+        }
+
+        // The cells which were not consistified: those in the last row,
+        // and those whose successors are on the new open boundary.
+        let mut cells = self.line(size - 1);
+        if shift > 1 {
+            cells.extend(self.line(size - shift));
+        }
+        for cell in cells {
+            if !result {
+                break;
+            }
+            result = self.consistify(cell);
+        }
+        self.deducer = None;
+        result && self.proceed()
+    }
+
+    /// Removes the row (or column) added by the last `grow`.
+    ///
+    /// All cells set after that time must be cleared,
+    /// so that the cells on the open boundary are all unknown.
+    fn shrink(&mut self) {
+        self.grow_marks.pop();
+        let size = self.size_along();
+
+        // The last row becomes the open boundary beyond the end.
+        let last = self
+            .line(size - 1)
+            .into_iter()
+            .filter(|cell| self.in_range((cell.coord.0, cell.coord.1)))
+            .collect::<Vec<_>>();
+        for &cell in &last {
+            if let Some(state) = cell.state.get() {
+                if state == ALIVE {
+                    self.cell_count[cell.coord.2 as usize] -= 1;
+                }
+                self.count_objective(cell, state, false);
+            }
+            let cell_ptr = self.find_cell_mut(cell.coord).unwrap();
+            unsafe {
+                cell_ptr.as_mut().unwrap().sym.clear();
+            }
+        }
+        self.search_list
+            .truncate(self.search_list.len() - last.len());
+        match self.config.auto_search_order() {
+            SearchOrder::RowFirst => self.row_counts.pop(),
+            SearchOrder::ColumnFirst => self.col_counts.pop(),
+        };
+        if !self.state_counts.is_empty() {
+            let len = self.state_counts.len()
+                - last.len() / self.config.period as usize * self.rule.gen();
+            self.state_counts.truncate(len);
+        }
+
+        // The open boundary is removed, after its cells become the background,
+        // which is what the cells linked to them assume without them.
+        for cell in self.line(size) {
+            if self.is_open(cell) {
+                World::replace_state(cell, Some(cell.background));
+                if cell.is_front && cell.background == DEAD {
+                    self.front_cell_count -= 1;
+                }
+            }
+        }
+        self.set_size_along(size - 1);
+        let line = self.cells.pop();
+        let shift = self.shift_along();
+        for along in [size - 1, size - shift, size + shift] {
+            self.link_line(along);
+        }
+        drop(line);
+        let len = self.cells.len() * self.line_len();
+        self.cell_conflicts.truncate(len);
+        self.count_edge();
+    }
+
+    /// Shrinks the world back to its size when the `set_stack`
+    /// had the given length.
+    ///
+    /// It is called in a growing world when the search backtracks,
+    /// before the cell at that position of the `set_stack` is cleared.
+    pub(crate) fn shrink_to(&mut self, len: usize) {
+        while matches!(self.grow_marks.last(), Some(&mark) if len < mark) {
+            self.shrink();
+        }
+    }
+
+    /// Whether the pattern in a growing world is closed,
+    /// i.e., it is still valid when all cells beyond the end
+    /// are the background.
+    ///
+    /// It is called when all cells in the search range are known.
+    /// Sets the unknown cells on the boundary beyond the end
+    /// to the background, and checks the cells which are not consistified
+    /// in a growing world. If the pattern is not closed, these cells are
+    /// cleared when the search backtracks.
+    pub(crate) fn close(&mut self) -> bool {
+        if !self.config.grow {
+            return true;
+        }
+        self.deducer = None;
+        for cell in self.line(self.size_along()) {
+            if self.is_open(cell) && cell.state.get().is_none() {
+                self.set_cell(cell, cell.background, Reason::Deduce);
+            }
+        }
+        if !self.proceed() {
+            return false;
+        }
+        // All cells are known now, so the rule only checks them.
+        let cells = self
+            .cells
+            .iter()
+            .flatten()
+            .map(LifeCell::borrow)
+            .collect::<Vec<_>>();
+        for cell in cells {
+            if self.is_loose(cell) && !Rule::consistify(self, cell) {
+                return false;
+            }
+            // The predecessor is after the boundary beyond the end.
+            let (x, y, t) = cell.coord;
+            if self.config.periodic
+                && t == 0
+                && cell.pred.is_none()
+                && self.in_range((x, y))
+                && cell.state.get() != Some(cell.background)
+            {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Updates the counts for the objective when a cell in the search range
+    /// is set to `state` (if `set` is `true`), or cleared from it.
+    fn count_objective(&mut self, cell: CellRef<'a, R>, state: State, set: bool) {
+        let (x, y, _) = cell.coord;
+        if x < 0 || x >= self.config.width || y < 0 || y >= self.config.height {
+            return;
+        }
+        match self.config.objective {
+            Objective::MinBoundingBox if state != cell.background => {
+                if set {
+                    self.col_counts[x as usize] += 1;
+                    self.row_counts[y as usize] += 1;
+                } else {
+                    self.col_counts[x as usize] -= 1;
+                    self.row_counts[y as usize] -= 1;
+                }
+            }
+            Objective::MinRotor | Objective::MaxRotor => {
+                let gen = self.rule.gen();
+                let period = self.config.period as usize;
+                let key = if state == cell.background {
+                    0
+                } else {
+                    state.0.max(1)
+                };
+                let (along, across, _, cross_size) = self.along_across((x, y));
+                let start = (along * cross_size + across) as usize * gen;
+                let counts = &mut self.state_counts[start..start + gen];
+                if set {
+                    counts[key] += 1;
+                    if counts[key] == 1 && counts.iter().filter(|&&n| n > 0).count() == 2 {
+                        self.rotor_count += 1;
+                    }
+                    if counts[key] == period {
+                        self.stable_count += 1;
+                    }
+                } else {
+                    if counts[key] == period {
+                        self.stable_count -= 1;
+                    }
+                    counts[key] -= 1;
+                    if counts[key] == 0 && counts.iter().filter(|&&n| n > 0).count() == 1 {
+                        self.rotor_count -= 1;
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+
+    /// The value of the objective, counting only the known cells.
+    ///
+    /// For an objective to minimize, it never exceeds its value in any result
+    /// that agrees with the known cells; for an objective to maximize,
+    /// it is never less than that. When all cells are known,
+    /// it is the value of the result.
+    pub(crate) fn objective_value(&self) -> usize {
+        match self.config.objective {
+            Objective::MinCellCount => self.cell_count(),
+            Objective::MinCellCountGen(t) => {
+                self.cell_count[t.rem_euclid(self.config.period) as usize]
+            }
+            Objective::MinBoundingBox => {
+                let span = |counts: &[usize]| match (
+                    counts.iter().position(|&n| n > 0),
+                    counts.iter().rposition(|&n| n > 0),
+                ) {
+                    (Some(first), Some(last)) => last - first + 1,
+                    _ => 0,
+                };
+                span(&self.col_counts) * span(&self.row_counts)
+            }
+            Objective::MinRotor => self.rotor_count,
+            Objective::MaxRotor => {
+                (self.config.width * self.config.height) as usize - self.stable_count
+            }
+        }
+    }
+
+    /// Whether the value of the objective can still meet its bound.
+    ///
+    /// The bound of `MinCellCount` is checked separately.
+    pub(crate) fn within_bound(&self) -> bool {
+        match (self.config.objective, self.config.bound) {
+            (Objective::MinCellCount, _) | (_, None) => true,
+            (objective, Some(bound)) if objective.is_max() => self.objective_value() >= bound,
+            (_, Some(bound)) => self.objective_value() <= bound,
+        }
+    }
+
     /// Gets a references to the first unknown cell since `index` in the `search_list`.
     pub(crate) fn get_unknown(&self, index: usize) -> Option<(usize, CellRef<'a, R>)> {
         self.search_list[index..]
@@ -453,15 +1426,19 @@ impl<'a, R: Rule> World<'a, R> {
 
     /// Tests whether the world is nonempty,
     /// and whether the minimal period of the pattern equals to the given period.
+    ///
+    /// The period is not tested if the configuration is not periodic.
     pub(crate) fn nontrivial(&self) -> bool {
         self.cell_count[0] > 0
-            && (1..self.config.period).all(|t| {
-                self.config.period % t != 0
-                    || self
-                        .cells
-                        .chunks(self.config.period as usize)
-                        .any(|c| c[0].state.get() != c[t as usize].state.get())
-            })
+            && (!self.config.periodic
+                || (1..self.config.period).all(|t| {
+                    self.config.period % t != 0
+                        || self
+                            .cells
+                            .iter()
+                            .flat_map(|line| line.chunks(self.config.period as usize))
+                            .any(|c| c[0].state.get() != c[t as usize].state.get())
+                }))
     }
 
     /// Gets the state of a cell. Returns `Err(())` if there is no such cell.
@@ -471,6 +1448,114 @@ impl<'a, R: Rule> World<'a, R> {
             .ok_or(Error::GetCellError(coord))
     }
 
+    /// The whole world in some generation as a `Pattern`.
+    pub fn pattern(&self, t: isize) -> Pattern {
+        let mut pattern = Pattern::new(self.config.width, self.config.height);
+        pattern.set_rule_string(Some(self.config.rule_string.clone()));
+        for y in 0..self.config.height {
+            for x in 0..self.config.width {
+                let cell = self.find_cell(self.config.translate((x, y, t))).unwrap();
+                pattern.set_cell_state(x, y, cell.state.get());
+            }
+        }
+        pattern
+    }
+
+    /// Records a conflict found at a cell.
+    ///
+    /// Cells of the still life in a catalyst search are not counted.
+    pub(crate) fn record_cell_conflict(&mut self, coord: Coord) {
+        if let Some(index) = self.cell_index(coord) {
+            self.cell_conflicts[index] += 1;
+        }
+    }
+
+    /// Number of conflicts found at each cell in some generation,
+    /// row by row.
+    pub fn conflict_map(&self, t: isize) -> Vec<Vec<u64>> {
+        (0..self.config.height)
+            .map(|y| {
+                (0..self.config.width)
+                    .map(|x| {
+                        let index = self.cell_index(self.config.translate((x, y, t))).unwrap();
+                        self.cell_conflicts[index]
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Records the current time if more cells are known than
+    /// at any earlier time since the last result.
+    ///
+    /// Only the length of the `set_stack` is recorded. The states
+    /// are copied by `save_deepest` before the search backtracks,
+    /// so that they are not copied at each step of a deepening search.
+    pub(crate) fn record_deepest(&mut self) {
+        let known = self.set_stack.len();
+        let depth = self
+            .deeper
+            .or_else(|| self.deepest.as_ref().map(|&(depth, _)| depth));
+        if !matches!(depth, Some(depth) if known <= depth) {
+            self.deeper = Some(known);
+        }
+    }
+
+    /// Copies the states at the time recorded by `record_deepest`
+    /// to `deepest`, if they are not copied yet.
+    ///
+    /// Must be called before any cell in the first part of the `set_stack`
+    /// is cleared.
+    pub(crate) fn save_deepest(&mut self) {
+        if let Some(known) = self.deeper.take() {
+            self.deepest = Some((known, self.states_at(known)));
+        }
+    }
+
+    /// The states of all cells when the `set_stack` had only
+    /// its first `known` cells.
+    fn states_at(&self, known: usize) -> Vec<Option<State>> {
+        let mut states = self
+            .cells
+            .iter()
+            .flatten()
+            .map(|cell| cell.state.get())
+            .collect::<Vec<_>>();
+        // Cells of the still life in a catalyst search are not in `cells`.
+        for set_cell in &self.set_stack[known..] {
+            if let Some(index) = self.cell_index(set_cell.cell.coord) {
+                states[index] = None;
+            }
+        }
+        states
+    }
+
+    /// The whole world in some generation as a `Pattern`,
+    /// at the time when the most cells were known since the last result.
+    ///
+    /// Returns `None` if no such time is recorded,
+    /// e.g., when the search has not started.
+    pub fn deepest_pattern(&self, t: isize) -> Option<Pattern> {
+        let current;
+        let states = match self.deeper {
+            Some(known) => {
+                current = self.states_at(known);
+                &current
+            }
+            None => &self.deepest.as_ref()?.1,
+        };
+        let mut pattern = Pattern::new(self.config.width, self.config.height);
+        pattern.set_rule_string(Some(self.config.rule_string.clone()));
+        for y in 0..self.config.height {
+            for x in 0..self.config.width {
+                // The world might have grown since then.
+                let index = self.cell_index(self.config.translate((x, y, t))).unwrap();
+                pattern.set_cell_state(x, y, states.get(index).copied().flatten());
+            }
+        }
+        Some(pattern)
+    }
+
     /// Minumum number of known living cells in all generation.
     ///
     /// For Generations rules, dying cells are not counted.
@@ -1,4 +1,9 @@
-use rlifesrc_lib::{Config, Error, Status, Symmetry, Transform};
+use rlifesrc_lib::{
+    Cause, Config, Engine, Error, KnownCell, NewState, Objective, Pattern, Proof, Restart,
+    RuleCase, SearchOrder, State, Status, Sweep, SweepStatus, Symmetry, Transform, Transition,
+    ALIVE, DEAD,
+};
+use std::collections::HashSet;
 
 #[test]
 fn default() -> Result<(), Error> {
@@ -55,6 +60,153 @@ fn p3_spaceship() -> Result<(), Error> {
     Ok(())
 }
 
+#[test]
+#[cfg(feature = "serialize")]
+fn ser_version() -> Result<(), Error> {
+    use rlifesrc_lib::{WorldSer, SAVE_VERSION};
+    use serde_json::json;
+
+    let config = Config::new(16, 5, 3).set_translate(0, 1);
+    let mut search = config.world()?;
+    search.search(Some(100));
+    let count = search.cell_count();
+    let save = search.ser();
+    assert_eq!(save.header().version, SAVE_VERSION);
+    assert_eq!(save.header().rule_string, "B3/S23");
+    let restored = save.world()?;
+    assert_eq!(restored.cell_count(), count);
+    for t in 0..3 {
+        assert_eq!(restored.pattern(t), search.pattern(t));
+    }
+    let value = serde_json::to_value(&save).unwrap();
+
+    // Proofs are not recorded by restored searches.
+    let mut proving = config.clone().set_proof(true).world()?;
+    proving.search(Some(100));
+    assert!(proving.proof().is_some());
+    let saved = serde_json::to_value(proving.ser()).unwrap();
+    assert!(saved["config"].get("proof").is_none());
+    let saved: WorldSer = serde_json::from_value(saved).unwrap();
+    assert!(saved.world()?.proof().is_none());
+
+    // A save written by rlifesrc 0.3, before the save format was versioned.
+    let old: WorldSer = serde_json::from_str(include_str!("saves/v0.json")).unwrap();
+    assert_eq!(old.header().version, 0);
+    let mut old = old.world()?;
+    assert_eq!(old.cell_count(), 4);
+    assert_eq!(serde_json::to_value(old.ser()).unwrap()["search_index"], 18);
+    assert_eq!(old.search(None), Status::Found);
+
+    // Symmetries and transformations use the notation of `FromStr`,
+    // and also accept the names of the variants used by older versions.
+    assert_eq!(serde_json::to_value(Symmetry::D2Col).unwrap(), json!("D2|"));
+    assert_eq!(
+        serde_json::to_value(Transform::FlipCol).unwrap(),
+        json!("F|")
+    );
+    for symmetry in [json!("D2|"), json!("D2Col")] {
+        assert_eq!(
+            serde_json::from_value::<Symmetry>(symmetry).unwrap(),
+            Symmetry::D2Col
+        );
+    }
+    for transform in [json!("F|"), json!("FlipCol")] {
+        assert_eq!(
+            serde_json::from_value::<Transform>(transform).unwrap(),
+            Transform::FlipCol
+        );
+    }
+    assert!(serde_json::from_value::<Symmetry>(json!("D3")).is_err());
+
+    let mut future = value.clone();
+    future["header"]["version"] = json!(SAVE_VERSION + 1);
+    let future: WorldSer = serde_json::from_value(future).unwrap();
+    assert_eq!(
+        future.world().err(),
+        Some(Error::SaveVersionError(SAVE_VERSION + 1))
+    );
+
+    let mut outside = value;
+    outside["decisions"][0]["coord"] = json!([16, 0, 0]);
+    let outside: WorldSer = serde_json::from_value(outside).unwrap();
+    assert_eq!(
+        outside.world().err(),
+        Some(Error::SaveCellError((16, 0, 0)))
+    );
+    Ok(())
+}
+
+#[test]
+fn rle_std() -> Result<(), Error> {
+    let config = Config::new(16, 5, 3).set_translate(0, 1);
+    let mut search = config.world()?;
+    assert_eq!(
+        search.rle_std_gen(0),
+        Err(Error::UnknownCellError((0, 0, 0)))
+    );
+    assert_eq!(search.search(None), Status::Found);
+    assert_eq!(
+        search.rle_std_gen(0)?,
+        String::from(
+            "#C Generated by rlifesrc.\n\
+             #C width = 16, height = 5, period = 3, dx = 0, dy = 1, \
+             transform = Id, symmetry = C1\n\
+             #C generation = 0\n\
+             x = 16, y = 5, rule = B3/S23\n\
+             8bo$b2ob3ob3o$b2o4bo2b2ob2o$o2bob2o3bo2b2o$12bo2bo!\n"
+        )
+    );
+    Ok(())
+}
+
+#[test]
+fn rle_std_gen() -> Result<(), Error> {
+    let config = Config::new(4, 4, 3).set_rule_string("23/3/3".to_owned());
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    assert!(search
+        .rle_std_gen(0)?
+        .ends_with("x = 4, y = 4, rule = 23/3/3\n2A$2A$2.2A$2.2A!\n"));
+    Ok(())
+}
+
+#[test]
+fn rle_std_phases() -> Result<(), Error> {
+    let config = Config::new(16, 5, 3).set_translate(0, 1);
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    let phases = Pattern::from_rle(&search.rle_std_phases()?)?;
+    assert_eq!((phases.width(), phases.height()), (50, 5));
+    for t in 0..3 {
+        assert_eq!(phases.crop(17 * t, 0, 16, 5), search.pattern(t));
+    }
+    Ok(())
+}
+
+#[test]
+fn rle_history() -> Result<(), Error> {
+    let config = Config::new(4, 4, 2);
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    assert!(search
+        .rle_history()?
+        .ends_with("x = 6, y = 6, rule = LifeHistory\n6F$F2C2.F$FCA2.F$F2.ACF$F2.2CF$6F!\n"));
+
+    let config = Config::new(4, 4, 2).set_rule_string("23/3".to_owned());
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    assert!(search.rle_history()?.contains("rule = LifeHistory\n"));
+
+    let config = Config::new(4, 4, 3).set_rule_string("23/3/3".to_owned());
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    assert_eq!(
+        search.rle_history(),
+        Err(Error::HistoryRuleError("23/3/3".to_owned()))
+    );
+    Ok(())
+}
+
 #[test]
 fn lwss() -> Result<(), Error> {
     let config = Config::new(6, 6, 4).set_translate(0, 2);
@@ -70,6 +222,9 @@ fn lwss_flip() -> Result<(), Error> {
         .set_transform(Transform::FlipCol);
     let mut search = config.world()?;
     assert_eq!(search.search(None), Status::Found);
+    assert!(search
+        .rle_std_gen(0)?
+        .contains("transform = F|, symmetry = C1\n"));
     Ok(())
 }
 
@@ -101,6 +256,491 @@ fn snowflakes() -> Result<(), Error> {
     Ok(())
 }
 
+#[test]
+fn verify() -> Result<(), Error> {
+    let config = Config::new(16, 5, 3).set_translate(0, 1);
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    assert_eq!(search.verify(), Ok(()));
+
+    let config = Config::new(5, 5, 2)
+        .set_translate(0, 1)
+        .set_transform(Transform::FlipCol);
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    assert_eq!(search.verify(), Ok(()));
+    Ok(())
+}
+
+#[test]
+fn verify_gen() -> Result<(), Error> {
+    let config = Config::new(4, 4, 3).set_rule_string("23/3/3".to_owned());
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    assert_eq!(search.verify(), Ok(()));
+    Ok(())
+}
+
+#[test]
+fn verify_b0() -> Result<(), Error> {
+    let config = Config::new(6, 6, 2).set_rule_string("B01245/S0127".to_owned());
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    assert_eq!(search.verify(), Ok(()));
+    Ok(())
+}
+
+#[test]
+fn cell_count_b0() -> Result<(), Error> {
+    let config = Config::new(6, 6, 2).set_rule_string("B01245/S0127".to_owned());
+    let mut search = config.world()?;
+    assert_eq!(search.cell_count(), 0);
+    assert_eq!(search.search(None), Status::Found);
+    let count = (0..2)
+        .map(|t| {
+            search
+                .pattern(t)
+                .known_cells()
+                .filter(|&(_, state)| state == ALIVE)
+                .count()
+        })
+        .min()
+        .unwrap();
+    assert_eq!(search.cell_count(), count);
+    Ok(())
+}
+
+#[test]
+fn verify_subperiod() -> Result<(), Error> {
+    let config = Config::new(5, 5, 8).set_translate(2, 2);
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    assert_eq!(search.verify(), Err(Error::SubperiodError(4)));
+    Ok(())
+}
+
+#[test]
+fn parse_rle() -> Result<(), Error> {
+    let pattern = Pattern::from_rle("#C A glider.\nx = 3, y = 3, rule = B3/S23\nbo$2bo$3o!\n")?;
+    assert_eq!((pattern.width(), pattern.height()), (3, 3));
+    assert_eq!(pattern.get_cell_state(1, 0), Some(ALIVE));
+    assert_eq!(pattern.get_cell_state(0, 1), Some(DEAD));
+    assert_eq!(
+        pattern.known_cells().filter(|&(_, s)| s == ALIVE).count(),
+        5
+    );
+
+    let pattern = Pattern::from_rle("x = 4, y = 2, rule = 23/3/3\n2A?pA$\n.B!")?;
+    assert_eq!((pattern.width(), pattern.height()), (4, 2));
+    assert_eq!(pattern.get_cell_state(1, 0), Some(State(1)));
+    assert_eq!(pattern.get_cell_state(2, 0), None);
+    assert_eq!(pattern.get_cell_state(3, 0), Some(State(25)));
+    assert_eq!(pattern.get_cell_state(1, 1), Some(State(2)));
+    assert_eq!(pattern.get_cell_state(3, 1), Some(DEAD));
+    assert!(Pattern::from_rle("x = 1, y = 1\n2z%!").is_err());
+    assert!(Pattern::from_rle("x = 3, y = 1\n4o!").is_err());
+    assert!(Pattern::from_rle("x = 3, y = 2\no3$o!").is_err());
+    assert!(Pattern::from_rle("99999999999999999999999o!").is_err());
+    assert!(Pattern::from_rle("x = -1, y = 1\n!").is_err());
+    assert!(Pattern::from_rle("x = 100000, y = 1\n!").is_err());
+    assert!(Pattern::from_rle("x = 65536, y = 65536\n!").is_err());
+    assert_eq!(Pattern::new(-1, 2).width(), 0);
+    Ok(())
+}
+
+#[test]
+fn parse_plaintext_life106() -> Result<(), Error> {
+    let glider = Pattern::from_rle("bo$2bo$3o!")?;
+    let plaintext: Pattern = "!Name: Glider\n.O.\n..O\nOOO\n".parse()?;
+    assert_eq!(plaintext, glider);
+    let life106: Pattern = "#Life 1.06\n0 -1\n1 0\n-1 1\n0 1\n1 1\n".parse()?;
+    assert_eq!(life106, glider);
+    let partial: Pattern = ".O?\n??O".parse()?;
+    assert_eq!(partial.known_cells().count(), 3);
+    Ok(())
+}
+
+#[test]
+fn known_cells() -> Result<(), Error> {
+    let pattern = Pattern::from_rle("8bo$b2ob3ob3o$b2o4bo2b2ob2o$o2bob2o3bo2b2o$12bo2bo!")?;
+    let config = Config::new(16, 5, 3)
+        .set_translate(0, 1)
+        .add_pattern(&pattern, 0, 0, 0);
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    for ((x, y), state) in pattern.known_cells() {
+        assert_eq!(search.get_cell_state((x, y, 0)), Ok(Some(state)));
+    }
+
+    let config = Config::new(5, 5, 1).add_pattern(&pattern, 0, 0, 0);
+    assert_eq!(config.world().err(), Some(Error::SetCellError((8, 0, 0))));
+
+    let config = Config::new(16, 5, 1)
+        .set_max_cell_count(Some(3))
+        .add_pattern(&pattern, 0, 0, 0);
+    assert_eq!(config.world().err(), Some(Error::KnownCellError((4, 1, 0))));
+
+    let config = Config::new(5, 5, 1).set_known_cells(vec![KnownCell {
+        coord: (0, 0, 0),
+        state: State(2),
+    }]);
+    assert_eq!(
+        config.world().err(),
+        Some(Error::KnownStateError((0, 0, 0)))
+    );
+    Ok(())
+}
+
+#[test]
+fn non_periodic() -> Result<(), Error> {
+    let glider = Pattern::from_rle("bo$2bo$3o!")?;
+    for engine in [Engine::Backtrack, Engine::Sat] {
+        let config = Config::new(6, 6, 3)
+            .set_periodic(false)
+            .set_engine(engine)
+            .set_target(&glider, 1, 1);
+        let mut search = config.world()?;
+        assert_eq!(search.search(None), Status::Found);
+        assert_eq!(search.cell_count_gen(2), 5);
+        for ((x, y), state) in glider.known_cells() {
+            assert_eq!(search.get_cell_state((x + 1, y + 1, 2)), Ok(Some(state)));
+        }
+        assert_eq!(search.verify(), Ok(()));
+        assert_eq!(
+            search.get_cell_state((0, 0, 3)),
+            Err(Error::GetCellError((0, 0, 3)))
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn stator() -> Result<(), Error> {
+    let rotor = ["O?\n?O".parse::<Pattern>()?, ".?\n?.".parse()?];
+    for engine in [Engine::Backtrack, Engine::Sat] {
+        let config = Config::new(6, 6, 1)
+            .set_engine(engine)
+            .set_rotor(&rotor, 2, 2);
+        assert_eq!(config.period, 2);
+        let mut search = config.world()?;
+        assert_eq!(search.search(None), Status::Found);
+        assert_eq!(search.verify(), Ok(()));
+        assert_eq!(search.cell_count_gen(1), 6);
+        assert_eq!(search.config().max_cell_count, Some(5));
+        assert_eq!(search.search(None), Status::None);
+    }
+    Ok(())
+}
+
+#[test]
+fn catalyst() -> Result<(), Error> {
+    let reaction = "...\nOOO\n...".parse::<Pattern>()?;
+    for engine in [Engine::Backtrack, Engine::Sat] {
+        let config = Config::new(7, 7, 3)
+            .set_engine(engine)
+            .set_reaction(&reaction, 2, 2);
+        assert!(config.catalyst && !config.periodic);
+        let mut search = config.world()?;
+        assert_eq!(search.search(None), Status::Found);
+        assert_eq!(search.verify(), Ok(()));
+        for x in 0..7 {
+            for y in 0..7 {
+                if !(2..5).contains(&x) || !(2..5).contains(&y) {
+                    assert_eq!(
+                        search.get_cell_state((x, y, 0)),
+                        search.get_cell_state((x, y, 2))
+                    );
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn pattern() -> Result<(), Error> {
+    let config = Config::new(16, 5, 3).set_translate(0, 1);
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    let pattern = search.pattern(0);
+    assert_eq!(pattern.rule_string(), Some("B3/S23"));
+    assert_eq!(pattern.population(), search.cell_count_gen(0));
+    let mut expected = Pattern::from_rle(&search.rle_std_gen(0)?)?;
+    assert_eq!(pattern, expected);
+    expected.set_rule_string(None);
+    assert_ne!(pattern, expected);
+    Ok(())
+}
+
+#[test]
+fn pattern_transform() -> Result<(), Error> {
+    let glider = Pattern::from_rle("x = 3, y = 2\nbo$2bo!")?;
+    let rotated = glider.transform(Transform::Rotate90);
+    assert_eq!((rotated.width(), rotated.height()), (2, 3));
+    assert_eq!(rotated, Pattern::from_rle("x = 2, y = 3\nbo$o!")?);
+    assert_eq!(
+        rotated.transform(Transform::Rotate270),
+        glider.transform(Transform::Id)
+    );
+    assert_eq!(
+        glider.transform(Transform::FlipDiag),
+        glider
+            .transform(Transform::Rotate90)
+            .transform(Transform::FlipRow)
+    );
+    assert_eq!(
+        glider.transform(Transform::FlipAntidiag),
+        glider
+            .transform(Transform::Rotate90)
+            .transform(Transform::FlipCol)
+    );
+    Ok(())
+}
+
+#[test]
+fn pattern_crop() -> Result<(), Error> {
+    let pattern = Pattern::from_rle("x = 5, y = 4\n$2bo$3b?!")?;
+    assert_eq!(pattern.population(), 1);
+    assert_eq!(pattern.bounding_box(), Some((2, 1, 2, 2)));
+    let trimmed = pattern.trim();
+    assert_eq!(trimmed, Pattern::from_rle("x = 2, y = 2\no$b?!")?);
+    assert_eq!(trimmed.crop(-1, 0, 2, 1), Pattern::from_rle("bo!")?);
+    assert_eq!(Pattern::new(3, 3).bounding_box(), None);
+    Ok(())
+}
+
+#[test]
+fn import_lls() -> Result<(), Error> {
+    let config = Config::from_lls(
+        "0 0 0 0 0\n\
+         0 * a * 0\n\
+         0, *, 1, *, 0\n\
+         0 * -b * 0\n\
+         0 0 0 0 0\n\
+         \n\
+         * * * * *\n\
+         * * * * *\n\
+         * 1 1 1 *\n\
+         * * * * *\n\
+         * * * * *\n",
+    )?;
+    assert_eq!((config.width, config.height, config.period), (5, 5, 2));
+    assert_eq!(config.known_cells.len(), 20);
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    let gen1 = search.pattern(1);
+    assert_eq!(gen1.population(), 3);
+    assert_eq!(gen1.bounding_box(), Some((1, 2, 3, 1)));
+
+    assert_eq!(
+        Config::from_lls("a 0\n0 a\n").err(),
+        Some(Error::ImportError(String::from(
+            "variable a appears at both (0, 0, 0) and (1, 1, 0), \
+             constraints between cells are not supported"
+        )))
+    );
+    assert!(Config::from_lls("0 0\n0\n").is_err());
+    assert!(Config::from_lls("0' 0\n0 0\n").is_err());
+    Ok(())
+}
+
+#[test]
+fn import_lss() -> Result<(), Error> {
+    let config = Config::from_lss(
+        "# A blinker\n\
+         V 1\n\
+         R B3/S23\n\
+         I 0 5 5 2 0 0 1 1 1 0 0 0 0\n\
+         S 3 3 0 1 0\n\
+         S 3 2 0 1 0\n\
+         S 1 1 0 0 1\n\
+         E\n",
+    )?;
+    assert_eq!((config.width, config.height, config.period), (5, 5, 2));
+    assert_eq!(config.symmetry, Symmetry::D4Ortho);
+    assert_eq!(config.known_cells.len(), 2);
+    assert_eq!(config.known_cells[1].coord, (1, 2, 0));
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+
+    assert!(Config::from_lss("I 0 5 5 2 0 0 1 0 0 1 0\n").is_err());
+    assert!(Config::from_lss("X 1\n").is_err());
+    assert!(Config::from_lss("V 1\n").is_err());
+    Ok(())
+}
+
+#[test]
+fn cnf() -> Result<(), Error> {
+    let config = Config::new(16, 5, 3).set_translate(0, 1);
+    let cnf = config.cnf()?;
+    assert!(cnf.var_count() > cnf.cell_vars().len());
+    let dimacs = cnf.dimacs();
+    assert!(dimacs.contains(&format!(
+        "p cnf {} {}\n",
+        cnf.var_count(),
+        cnf.clauses().len()
+    )));
+    assert!(dimacs.contains("c 1 0 0 0\n"));
+
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    let model = cnf
+        .cell_vars()
+        .iter()
+        .enumerate()
+        .map(|(i, &coord)| {
+            let var = i as i32 + 1;
+            if search.get_cell_state(coord)? == Some(ALIVE) {
+                Ok(format!("{} ", var))
+            } else {
+                Ok(format!("-{} ", var))
+            }
+        })
+        .collect::<Result<String, Error>>()?;
+    let result = cnf.read_model(&format!("s SATISFIABLE\nv {}0\n", model))?;
+    assert_eq!(result.rle_gen(0), search.rle_gen(0));
+
+    let dead = (1..=cnf.cell_vars().len())
+        .map(|var| format!("-{} ", var))
+        .collect::<String>();
+    assert_eq!(
+        cnf.read_model(&dead).err(),
+        Some(Error::CnfError(String::from("the model is not a solution")))
+    );
+    assert!(cnf.read_model("s UNSATISFIABLE\n").is_err());
+    assert!(Config::new(4, 4, 3)
+        .set_rule_string("23/3/3".to_owned())
+        .cnf()
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn sat_engine() -> Result<(), Error> {
+    let config = Config::new(4, 4, 4)
+        .set_translate(1, 1)
+        .set_engine(Engine::Sat);
+    let mut search = config.world()?;
+    assert_eq!(search.get_cell_state((0, 0, 0))?, None);
+    assert_eq!(search.search(None), Status::Found);
+    assert!(search.rle_std_gen(0).is_ok());
+    search.verify()?;
+
+    let mut search = Config::new(4, 4, 3).set_engine(Engine::Sat).world()?;
+    assert_eq!(search.search(None), Status::None);
+
+    let config = Config::new(5, 5, 1).set_max_cell_count(Some(5));
+    let count = |config: Config| -> Result<usize, Error> {
+        let mut search = config.world()?;
+        let mut count = 0;
+        while search.search(None) == Status::Found {
+            assert!(search.cell_count() <= 5);
+            count += 1;
+        }
+        Ok(count)
+    };
+    assert_eq!(
+        count(config.clone().set_engine(Engine::Sat))?,
+        count(config)?
+    );
+
+    assert!(Config::new(4, 4, 3)
+        .set_rule_string("23/3/3".to_owned())
+        .set_engine(Engine::Sat)
+        .world()
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn depth() -> Result<(), Error> {
+    for engine in [Engine::Backtrack, Engine::Sat] {
+        let config = Config::new(16, 5, 3).set_translate(0, 1).set_engine(engine);
+        let mut search = config.world()?;
+        assert_eq!(search.depth(), 0);
+        assert_eq!(search.search(Some(20)), Status::Searching);
+        assert!(search.depth() > 0);
+        assert!(search.depth() <= 16 * 5 * 3);
+    }
+    Ok(())
+}
+
+#[test]
+fn deepest() -> Result<(), Error> {
+    let mut search = Config::new(5, 5, 3).world()?;
+    assert_eq!(search.deepest_pattern(0), None);
+    assert_eq!(search.search(None), Status::None);
+    let deepest = search.deepest_pattern(0).unwrap();
+    assert!(deepest.known_cells().count() > 0);
+    assert!(search
+        .rle_deepest_gen(0)
+        .unwrap()
+        .starts_with("x = 5, y = 5"));
+
+    // Before backtracking, the deepest time is just before the last decision.
+    let mut search = Config::new(16, 5, 3).set_translate(0, 1).world()?;
+    assert_eq!(search.search(Some(0)), Status::Searching);
+    let deepest = search.deepest_pattern(0).unwrap();
+    let current = search.pattern(0);
+    assert_eq!(
+        deepest.known_cells().count() + 1,
+        current.known_cells().count()
+    );
+    assert!(deepest
+        .known_cells()
+        .all(|((x, y), state)| current.get_cell_state(x, y) == Some(state)));
+
+    let mut search = Config::new(5, 5, 3).set_engine(Engine::Sat).world()?;
+    assert_eq!(search.search(None), Status::None);
+    assert_eq!(search.rle_deepest_gen(0), None);
+    Ok(())
+}
+
+#[test]
+fn sweep() -> Result<(), Error> {
+    let sweep = Sweep::new(Config::new(1, 4, 1)).set_widths(1..=3);
+    let mut results = Vec::new();
+    let points = sweep.clone().set_stop_at_first(true).run(|point, search| {
+        if point.status == SweepStatus::Found {
+            results.push(search.rle_gen(0));
+        }
+    });
+    let statuses = points
+        .iter()
+        .map(|point| point.status.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(statuses, vec![SweepStatus::None, SweepStatus::Found]);
+    assert_eq!((points[1].width, points[1].height), (2, 4));
+    assert_eq!(results.len(), 1);
+    assert_eq!(sweep.run(|_, _| ()).len(), 3);
+
+    let square = Sweep::new(Config::new(1, 1, 1).set_symmetry(Symmetry::D8))
+        .set_widths(1..=3)
+        .set_heights(1..=3);
+    assert_eq!(square.configs().len(), 3);
+
+    let points = Sweep::new(Config::new(16, 5, 3).set_translate(0, 1))
+        .set_periods(2..=3)
+        .set_max_step(Some(10))
+        .run(|_, _| ());
+    assert_eq!(points.len(), 2);
+    assert_eq!(points[1].status, SweepStatus::TimedOut);
+
+    // A point where the known cells do not fit does not end the sweep.
+    let glider = Pattern::from_rle("bo$2bo$3o!")?;
+    let config = Config::new(1, 6, 4)
+        .set_translate(1, 1)
+        .add_pattern(&glider, 0, 0, 0);
+    let points = Sweep::new(config).set_widths(2..=3).run(|_, _| ());
+    assert_eq!(points.len(), 2);
+    assert_eq!(
+        points[0].status,
+        SweepStatus::Invalid(Error::SetCellError((2, 1, 0)).to_string())
+    );
+    assert_eq!(points[1].status, SweepStatus::None);
+    Ok(())
+}
+
 #[test]
 #[cfg(feature = "serialize")]
 fn ser() -> Result<(), Error> {
@@ -123,5 +763,322 @@ fn ser() -> Result<(), Error> {
              ............o..o!\n"
         )
     );
+
+    let config = Config::new(5, 5, 1)
+        .set_max_cell_count(Some(5))
+        .set_engine(Engine::Sat);
+    let mut search = config.world()?;
+    let mut results = Vec::new();
+    for _ in 0..3 {
+        assert_eq!(search.search(None), Status::Found);
+        results.push(search.rle_gen(0));
+    }
+    let mut new_search = search.ser().world()?;
+    assert_eq!(new_search.conflicts(), search.conflicts());
+    while new_search.search(None) == Status::Found {
+        assert!(!results.contains(&new_search.rle_gen(0)));
+    }
+
+    let config = Config::new(5, 5, 2)
+        .set_engine(Engine::Sat)
+        .set_reduce_max(true);
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    let best = search.optimum();
+    let new_search = search.ser().world()?;
+    assert_eq!(new_search.optimum(), best);
+    assert_eq!(new_search.config(), search.config());
+    Ok(())
+}
+
+#[test]
+fn objective() -> Result<(), Error> {
+    for (objective, optimum) in [
+        (Objective::MinBoundingBox, 9),
+        (Objective::MinRotor, 2),
+        (Objective::MaxRotor, 8),
+    ] {
+        for engine in [Engine::Backtrack, Engine::Sat] {
+            let config = Config::new(5, 5, 2)
+                .set_engine(engine)
+                .set_reduce_max(true)
+                .set_objective(objective);
+            let mut search = config.world()?;
+            assert_eq!(search.optimum(), None);
+            while search.search(None) == Status::Found {
+                assert_eq!(search.verify(), Ok(()));
+            }
+            assert_eq!(search.optimum(), Some(optimum));
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn grow() -> Result<(), Error> {
+    let config = Config::new(5, 1, 4)
+        .set_translate(0, 2)
+        .set_search_order(Some(SearchOrder::RowFirst))
+        .set_grow(true, None);
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    assert_eq!(search.verify(), Ok(()));
+    assert_eq!(search.config().height, 7);
+    assert_eq!(search.cell_count(), 9);
+
+    let config = Config::new(1, 5, 4)
+        .set_translate(2, 0)
+        .set_search_order(Some(SearchOrder::ColumnFirst))
+        .set_grow(true, None);
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    assert_eq!(search.verify(), Ok(()));
+    assert_eq!(search.config().width, 7);
+
+    let config = Config::new(4, 1, 4)
+        .set_translate(0, 2)
+        .set_search_order(Some(SearchOrder::RowFirst))
+        .set_grow(true, Some(8));
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::None);
+
+    let config = Config::new(5, 1, 4)
+        .set_symmetry(Symmetry::D2Row)
+        .set_search_order(Some(SearchOrder::RowFirst))
+        .set_grow(true, None);
+    assert!(matches!(config.world(), Err(Error::GrowError)));
+    Ok(())
+}
+
+#[test]
+fn grow_and_shrink() -> Result<(), Error> {
+    // The world grows and shrinks back while backtracking.
+    let config = Config::new(5, 1, 4)
+        .set_translate(0, 2)
+        .set_search_order(Some(SearchOrder::RowFirst))
+        .set_grow(true, Some(7))
+        .set_explain(true);
+    let mut search = config.world()?;
+    let mut heights = vec![1];
+    let mut results = 0;
+    loop {
+        match search.search(Some(1)) {
+            Status::Found => {
+                assert_eq!(search.verify(), Ok(()));
+                let height = search.config().height;
+                let pattern = search.pattern(0);
+                assert_eq!(pattern.height(), height);
+                assert!(search.explain((2, 0, 0)).is_ok());
+                assert!(search.explain((2, height - 1, 0)).is_ok());
+                results += 1;
+            }
+            Status::Searching => (),
+            _ => break,
+        }
+        let height = search.config().height;
+        if heights.last() != Some(&height) {
+            heights.push(height);
+        }
+    }
+    assert_eq!(results, 2);
+    assert!(heights.windows(2).any(|w| w[0] < w[1]));
+    assert!(heights.windows(2).any(|w| w[0] > w[1]));
+    assert_eq!(search.config().height, 1);
+
+    // Restarts undo the growth after the first decision.
+    let config = config.set_restart(Restart::Luby(3));
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    assert_eq!(search.verify(), Ok(()));
+    assert_eq!(search.config().height, 7);
+    assert_eq!(search.cell_count(), 9);
+    Ok(())
+}
+
+#[test]
+fn random() -> Result<(), Error> {
+    let config = Config::new(16, 5, 3)
+        .set_translate(0, 1)
+        .set_new_state(NewState::Random)
+        .set_seed(Some(42));
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    assert_eq!(search.verify(), Ok(()));
+    let mut new_search = config.world()?;
+    assert_eq!(new_search.search(None), Status::Found);
+    assert_eq!(new_search.rle_gen(0), search.rle_gen(0));
+    assert_eq!(new_search.conflicts(), search.conflicts());
+
+    let mut dead_search = config.clone().set_new_state(NewState::ChooseDead).world()?;
+    let mut sparse_search = config.clone().set_density(Some(0)).world()?;
+    assert_eq!(dead_search.search(None), Status::Found);
+    assert_eq!(sparse_search.search(None), Status::Found);
+    assert_eq!(sparse_search.rle_gen(0), dead_search.rle_gen(0));
+
+    for restart in [Restart::Fixed(1000), Restart::Luby(100)] {
+        let mut search = config.clone().set_restart(restart).world()?;
+        assert_eq!(search.search(None), Status::Found);
+        assert_eq!(search.verify(), Ok(()));
+    }
+    let config = Config::new(4, 4, 3)
+        .set_new_state(NewState::Random)
+        .set_seed(Some(42))
+        .set_restart(Restart::Luby(100));
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::None);
+
+    // No restarts after the first result, so no result is found twice.
+    let config = Config::new(5, 5, 2)
+        .set_new_state(NewState::Random)
+        .set_seed(Some(1));
+    let mut results = Vec::new();
+    for restart in [Restart::Never, Restart::Luby(1)] {
+        let mut search = config.clone().set_restart(restart).world()?;
+        let mut found = HashSet::new();
+        while search.search(None) == Status::Found {
+            assert!(found.insert(search.rle_gen(0)));
+        }
+        results.push(found);
+    }
+    assert_eq!(results[0], results[1]);
+
+    assert_eq!("luby:100".parse(), Ok(Restart::Luby(100)));
+    assert!("fixed:0".parse::<Restart>().is_err());
+    Ok(())
+}
+
+#[test]
+fn proof() -> Result<(), Error> {
+    let config = Config::new(5, 5, 3).set_proof(true);
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::None);
+    let proof = search.proof().unwrap().clone();
+    let summary = config.check_proof(&proof)?;
+    assert_eq!(summary.results, 0);
+    assert_eq!(summary.leaves, proof.leaves.len());
+    assert_eq!(proof.to_string().parse::<Proof>()?, proof);
+
+    let mut incomplete = proof.clone();
+    incomplete.leaves.pop();
+    assert!(config.check_proof(&incomplete).is_err());
+    let mut wrong = proof.clone();
+    let leaf = wrong
+        .leaves
+        .iter_mut()
+        .find(|leaf| leaf.cells.len() > 1)
+        .unwrap();
+    leaf.cells.pop();
+    assert!(config.check_proof(&wrong).is_err());
+
+    let config = Config::new(5, 5, 4).set_translate(1, 1).set_proof(true);
+    let mut search = config.world()?;
+    let mut found = 0;
+    while search.search(None) == Status::Found {
+        found += 1;
+    }
+    assert!(found > 0);
+    let summary = config.check_proof(search.proof().unwrap())?;
+    assert_eq!(summary.results, found);
+
+    // The proof can be taken while searching.
+    let config = Config::new(6, 6, 2).set_translate(0, 1).set_proof(true);
+    let mut search = config.world()?;
+    let mut proof = Proof::default();
+    while search.search(Some(10)) != Status::None {
+        proof
+            .leaves
+            .append(&mut search.take_proof().unwrap().leaves);
+    }
+    proof
+        .leaves
+        .append(&mut search.take_proof().unwrap().leaves);
+    assert!(search.proof().unwrap().leaves.is_empty());
+    assert!(proof.leaves.len() > 1);
+    config.check_proof(&proof)?;
+
+    let config = Config::new(3, 3, 2)
+        .set_rule_string("3457/357/5".to_owned())
+        .set_proof(true);
+    assert_eq!(config.world().err(), Some(Error::ProofConfigError));
+    let config = Config::new(5, 5, 3)
+        .set_proof(true)
+        .set_restart(Restart::Fixed(100));
+    assert_eq!(config.world().err(), Some(Error::ProofConfigError));
+    Ok(())
+}
+
+#[test]
+fn explain() -> Result<(), Error> {
+    let config = Config::new(16, 5, 3).set_translate(0, 1).set_explain(true);
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    for x in 0..16 {
+        let chain = search.explain((x, 2, 1))?;
+        assert_eq!(chain.last().unwrap().coord, (x, 2, 1));
+        assert!(matches!(
+            chain[0].cause,
+            Cause::Known | Cause::Decision | Cause::Backtrack
+        ));
+        // The determined cell is unknown in the transition that forces it.
+        for deduction in chain {
+            if let Cause::Rule(_, case) = deduction.cause {
+                let transition = case.transition();
+                match case {
+                    RuleCase::Successor(_) => assert_eq!(transition.succ, None),
+                    RuleCase::Cell(_) => assert_eq!(transition.state, None),
+                    RuleCase::Neighbor(_) => assert!(transition.nbhd.contains(&None)),
+                }
+            }
+        }
+    }
+    assert_eq!(search.explain((-1, 0, 0))?[0].cause, Cause::Known);
+    assert_eq!(
+        search.explain((17, 0, 0)),
+        Err(Error::GetCellError((17, 0, 0)))
+    );
+    let search = config.set_explain(false).world()?;
+    assert_eq!(search.explain((0, 0, 0)), Err(Error::ExplainError));
+
+    // A blinker is not a still life.
+    let pattern = Pattern::from_rle("3o!")?;
+    let config = Config::new(3, 2, 1)
+        .add_pattern(&pattern, 0, 0, 0)
+        .set_explain(true);
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::None);
+    let conflict = search.explain_root_conflict().unwrap();
+    assert!(conflict
+        .iter()
+        .any(|deduction| deduction.coord == (1, 0, 0) && deduction.cause == Cause::Known));
+    assert!(conflict
+        .iter()
+        .all(|deduction| deduction.cause != Cause::Decision));
+
+    let mut nbhd = [Some(DEAD); 8];
+    nbhd[1] = Some(ALIVE);
+    nbhd[6] = None;
+    let transition = Transition {
+        state: Some(ALIVE),
+        nbhd,
+        succ: None,
+    };
+    assert_eq!(transition.to_string(), ".../oo?/... -> ?");
+    Ok(())
+}
+
+#[test]
+fn conflict_map() -> Result<(), Error> {
+    let config = Config::new(16, 5, 3).set_translate(0, 1);
+    let mut search = config.world()?;
+    assert_eq!(search.search(None), Status::Found);
+    let mut total = 0;
+    for t in 0..3 {
+        let map = search.conflict_map(t).unwrap();
+        assert_eq!(map.len(), 5);
+        assert!(map.iter().all(|row| row.len() == 16));
+        total += map.iter().flatten().sum::<u64>();
+    }
+    assert!(total > 0);
+    assert!(total <= search.conflicts());
     Ok(())
 }
@@ -1,16 +1,168 @@
 //! Parsing command-line arguments.
 
+use crate::checkpoint::{parse_duration, Checkpoint, Checkpointer};
 use clap::{App, AppSettings, Arg, Error, ErrorKind, Result as ClapResult};
-use rlifesrc_lib::{rules::NtLifeGen, Config, NewState, Search, SearchOrder, Symmetry, Transform};
+use rlifesrc_lib::{
+    rules::NtLifeGen, Config, Engine, NewState, Objective, Pattern, Restart, Search, SearchOrder,
+    Sweep,
+};
+use std::{
+    env,
+    ffi::{OsStr, OsString},
+    fs, mem,
+    ops::RangeInclusive,
+    path::Path,
+    time::Duration,
+};
 
 fn is_positive(s: &str) -> bool {
     s.chars().all(|c| c.is_ascii_digit()) && s != "0" && !s.starts_with('-')
 }
 
+/// Parses a position of the form `X,Y`.
+fn parse_position(s: &str) -> Option<(isize, isize)> {
+    let mut coords = s.split(',').map(|c| c.trim().parse::<isize>());
+    match (coords.next(), coords.next(), coords.next()) {
+        (Some(Ok(x)), Some(Ok(y)), None) => Some((x, y)),
+        _ => None,
+    }
+}
+
+/// Parses a range of positive integers of the form `A..B`,
+/// where both ends are included, or a single positive integer.
+fn parse_range(s: &str) -> Option<RangeInclusive<isize>> {
+    let (start, end) = match s.find("..") {
+        Some(i) => (&s[..i], s[i + 2..].trim_start_matches('=')),
+        None => (s, s),
+    };
+    let (start, end) = (start.trim(), end.trim());
+    if !is_positive(start) || !is_positive(end) {
+        return None;
+    }
+    let range = start.parse().ok()?..=end.parse().ok()?;
+    if range.is_empty() {
+        None
+    } else {
+        Some(range)
+    }
+}
+
+/// Default time between two progress reports of `--progress`.
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Output formats of the results.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Format {
+    /// A mix of Plaintext and RLE format, with `?` for unknown cells.
+    Mixed,
+    /// The standard RLE format.
+    ///
+    /// Falls back to `Mixed` for partial results.
+    Rle,
+    /// All generations side by side in the standard RLE format.
+    ///
+    /// Falls back to `Mixed` for partial results.
+    Phases,
+    /// Generation 0 in LifeHistory format, marking the stator and the rotor.
+    ///
+    /// Falls back to `Mixed` for partial results.
+    History,
+    /// A JSON object for each result, containing the configuration,
+    /// all generations, the cell counts, the number of conflicts and the time.
+    ///
+    /// The TUI displays the results in the `Mixed` format,
+    /// and prints the JSON object after quitting.
+    Json,
+}
+
+/// Reads the search configuration from a TOML or JSON file,
+/// or imports it from a WinLifeSearch or Logic Life Search file.
+///
+/// Files with the extension `.json` are read as JSON, `.lss` as
+/// WinLifeSearch save files, `.lls` as Logic Life Search input,
+/// and other files as TOML.
+fn read_config(path: &str) -> ClapResult<Config> {
+    let input = fs::read_to_string(path).map_err(|e| {
+        Error::with_description(&format!("Unable to read {}: {}", path, e), ErrorKind::Io)
+    })?;
+    let extension = Path::new(path).extension().and_then(OsStr::to_str);
+    let result = match extension {
+        Some("json") => serde_json::from_str(&input).map_err(|e| e.to_string()),
+        Some("lss") => Config::from_lss(&input).map_err(|e| e.to_string()),
+        Some("lls") => Config::from_lls(&input).map_err(|e| e.to_string()),
+        _ => toml::from_str(&input).map_err(|e| e.to_string()),
+    };
+    result.map_err(|e| {
+        Error::with_description(
+            &format!("Invalid configuration file {}: {}", path, e),
+            ErrorKind::InvalidValue,
+        )
+    })
+}
+
+/// Reads a pattern from a file.
+fn read_pattern(path: &str) -> ClapResult<Pattern> {
+    let input = fs::read_to_string(path).map_err(|e| {
+        Error::with_description(&format!("Unable to read {}: {}", path, e), ErrorKind::Io)
+    })?;
+    input.parse().map_err(|e: rlifesrc_lib::Error| {
+        Error::with_description(&e.to_string(), ErrorKind::InvalidValue)
+    })
+}
+
+/// Reads a pattern for each generation from a file.
+///
+/// The patterns are separated by blank lines.
+fn read_patterns(path: &str) -> ClapResult<Vec<Pattern>> {
+    let input = fs::read_to_string(path).map_err(|e| {
+        Error::with_description(&format!("Unable to read {}: {}", path, e), ErrorKind::Io)
+    })?;
+    let mut chunks = Vec::new();
+    let mut chunk = String::new();
+    for line in input.lines() {
+        if !line.trim().is_empty() {
+            chunk.push_str(line);
+            chunk.push('\n');
+        } else if !chunk.is_empty() {
+            chunks.push(mem::take(&mut chunk));
+        }
+    }
+    if !chunk.is_empty() {
+        chunks.push(chunk);
+    }
+    if chunks.is_empty() {
+        return Err(Error::with_description(
+            &format!("No pattern in {}", path),
+            ErrorKind::InvalidValue,
+        ));
+    }
+    chunks
+        .iter()
+        .map(|chunk| {
+            chunk.parse().map_err(|e: rlifesrc_lib::Error| {
+                Error::with_description(&e.to_string(), ErrorKind::InvalidValue)
+            })
+        })
+        .collect()
+}
+
 /// A struct to store the parse results.
 pub(crate) struct Args {
     pub(crate) search: Box<dyn Search>,
     pub(crate) all: bool,
+    pub(crate) verify: bool,
+    pub(crate) print_config: bool,
+    pub(crate) dimacs: Option<String>,
+    pub(crate) model: bool,
+    pub(crate) proof: Option<String>,
+    pub(crate) check_proof: Option<String>,
+    pub(crate) sweep: Option<Sweep>,
+    pub(crate) format: Format,
+    pub(crate) checkpoint: Option<Checkpointer>,
+    pub(crate) progress: Option<Duration>,
+    pub(crate) progress_pattern: bool,
+    pub(crate) found: u64,
+    pub(crate) time: Duration,
     #[cfg(feature = "tui")]
     pub(crate) reset: bool,
     #[cfg(feature = "tui")]
@@ -20,6 +172,15 @@ pub(crate) struct Args {
 impl Args {
     /// Parses the command-line arguments.
     pub(crate) fn parse() -> ClapResult<Self> {
+        Args::parse_from(env::args_os())
+    }
+
+    /// Parses the given arguments, the first of which is the program name.
+    fn parse_from<I, T>(args: I) -> ClapResult<Self>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString> + Clone,
+    {
         let mut app = App::new("rlifesrc")
             .about("Searching for patterns in Conway's Game of Life")
             .long_about(
@@ -28,7 +189,7 @@ impl Args {
                  The program is based on David Bell's lifesrc and Jason Summers's \n\
                  WinLifeSearch, using an algorithm invented by Dean Hickerson.\n\
                  \n\
-                 The search result is displayed in a mix of Plaintext and \n\
+                 By default, the search result is displayed in a mix of Plaintext and \n\
                  RLE format.\n\
                  * Dead cells are represented by `.`;\n\
                  * Living cells are represented by `o` for rules with 2 states, \
@@ -36,14 +197,18 @@ impl Args {
                  * Dying cells are represented by uppercase letters starting from `B`;\n\
                  * Unknown cells are represented by `?`;\n\
                  * Each line is ended with `$`;\n\
-                 * The whole pattern is ended with `!`",
+                 * The whole pattern is ended with `!`\n\
+                 \n\
+                 With `--format rle`, results are displayed in the standard RLE format \n\
+                 instead, which can be read by Golly and LifeViewer.",
             )
+            .after_help("Run `rlifesrc batch --help` for running a batch of searches from a file.")
             .version("0.3.0")
             .settings(&[AppSettings::AllowNegativeNumbers, AppSettings::ColoredHelp])
             .arg(
                 Arg::with_name("X")
                     .help("Width of the pattern")
-                    .required(true)
+                    .required_unless_one(&["CONFIG", "RESUME", "WIDTH", "HEIGHT", "PERIOD"])
                     .index(1)
                     .validator(|x| {
                         if is_positive(&x) {
@@ -56,7 +221,7 @@ impl Args {
             .arg(
                 Arg::with_name("Y")
                     .help("Height of the pattern")
-                    .required(true)
+                    .required_unless_one(&["CONFIG", "RESUME", "WIDTH", "HEIGHT", "PERIOD"])
                     .index(2)
                     .validator(|y| {
                         if is_positive(&y) {
@@ -170,6 +335,72 @@ impl Args {
                     .possible_values(&["dead", "alive", "random", "d", "a", "r"])
                     .default_value("alive"),
             )
+            .arg(
+                Arg::with_name("DENSITY")
+                    .help("Probability in percent of choosing alive cells with --choose random")
+                    .long_help(
+                        "Probability in percent of choosing alive cells with --choose random\n\
+                         Other states are chosen with equal probabilities. \
+                         If it is not set, all states are chosen with equal probabilities.\n",
+                    )
+                    .long("density")
+                    .takes_value(true)
+                    .value_name("PERCENT")
+                    .validator(|d| match d.parse::<u32>() {
+                        Ok(d) if d <= 100 => Ok(()),
+                        Ok(_) => Err(String::from("density must be at most 100")),
+                        Err(e) => Err(e.to_string()),
+                    }),
+            )
+            .arg(
+                Arg::with_name("SEED")
+                    .help("Seed of the random number generator")
+                    .long_help(
+                        "Seed of the random number generator\n\
+                         Searches with the same options and the same seed make the same \
+                         random choices, so that a lucky run can be reproduced. \
+                         If it is not set, the seed is chosen randomly.\n",
+                    )
+                    .long("seed")
+                    .takes_value(true)
+                    .value_name("N")
+                    .validator(|n| n.parse::<u64>().map(|_| ()).map_err(|e| e.to_string())),
+            )
+            .arg(
+                Arg::with_name("RESTART")
+                    .help("When to restart the search")
+                    .long_help(
+                        "When to restart the search\n\
+                         A restart discards all decisions and starts the search again. \
+                         \"fixed:N\" restarts after every N conflicts, and the search might \
+                         never end if there is no result. \"luby:N\" restarts after N times \
+                         the terms of the Luby sequence 1, 1, 2, 1, 1, 2, 4, ... conflicts. \
+                         Useful with --choose random. The search no longer restarts after \
+                         the first result, and it cannot be used with --all or --rotor. \
+                         Ignored by the SAT engine.\n",
+                    )
+                    .long("restart")
+                    .takes_value(true)
+                    .value_name("POLICY")
+                    .default_value("never")
+                    .validator(|s| s.parse::<Restart>().map(|_| ())),
+            )
+            .arg(
+                Arg::with_name("ENGINE")
+                    .help("The engine that solves the search problem")
+                    .long_help(
+                        "The engine that solves the search problem\n\
+                         \"backtrack\" is the algorithm of lifesrc and WinLifeSearch.\n\
+                         \"sat\" encodes the problem as in --dimacs, and solves it with \
+                         an embedded SAT solver. It is often faster for large symmetric \
+                         worlds. Generations rules are not supported, and checkpoints \
+                         only save the configuration.\n",
+                    )
+                    .long("engine")
+                    .takes_value(true)
+                    .possible_values(&["backtrack", "sat"])
+                    .default_value("backtrack"),
+            )
             .arg(
                 Arg::with_name("MAX")
                     .help("Upper bound of numbers of minimum living cells in all generations")
@@ -199,9 +430,468 @@ impl Args {
                     .long_help(
                         "Reduce the max cell count when a result is found\n\
                          The new max cell count will be set to the cell count of \
-                         the current result minus one.",
+                         the current result minus one. With --objective, the bound of \
+                         the objective is tightened instead, so that each result is \
+                         better than the last one. When the search ends, the value of \
+                         the last result is printed as the proven optimum.",
                     )
                     .long("reduce"),
+            )
+            .arg(
+                Arg::with_name("OBJECTIVE")
+                    .help("The objective to optimize with --reduce")
+                    .long_help(
+                        "The objective to optimize with --reduce\n\
+                         \"cells\" is the minimum number of living cells in all generations, \
+                         bounded by --max. \"cells:T\" is the number of living cells in \
+                         generation T. \"box\" is the area of the bounding box of all \
+                         generations. \"rotor\" is the number of cells that change their \
+                         states, and \"max-rotor\" maximizes it. Other than \"cells\", \
+                         the objectives are bounded by --bound.\n",
+                    )
+                    .long("objective")
+                    .takes_value(true)
+                    .value_name("OBJECTIVE")
+                    .default_value("cells")
+                    .validator(|s| s.parse::<Objective>().map(|_| ())),
+            )
+            .arg(
+                Arg::with_name("BOUND")
+                    .help("Bound of the objective other than \"cells\"")
+                    .long_help(
+                        "Bound of the objective other than \"cells\"\n\
+                         An upper bound, or a lower bound for \"max-rotor\". \
+                         If this value is set to 0, it means there is no limitation.\n",
+                    )
+                    .long("bound")
+                    .takes_value(true)
+                    .value_name("N")
+                    .default_value("0")
+                    .validator(|d| d.parse::<u32>().map(|_| ()).map_err(|e| e.to_string())),
+            )
+            .arg(
+                Arg::with_name("VERIFY")
+                    .help("Verifies the result on the unbounded plane")
+                    .long_help(
+                        "Verifies the result on the unbounded plane\n\
+                         Checks that the cells outside the search range stay in the \
+                         background state, and that the result has the given period, \
+                         translation and transformation. The verification result \
+                         is printed to stderr.",
+                    )
+                    .long("verify"),
+            )
+            .arg(
+                Arg::with_name("FORMAT")
+                    .help("Output format of the results")
+                    .long_help(
+                        "Output format of the results\n\
+                         \"mixed\" is a mix of Plaintext and RLE format, \
+                         where unknown cells are represented by `?`.\n\
+                         \"rle\" is the standard RLE format.\n\
+                         \"phases\" shows all generations side by side in the standard \
+                         RLE format.\n\
+                         \"history\" shows generation 0 in LifeHistory format, where \
+                         state 1 is the rotor cells that are alive in generation 0, \
+                         state 2 is the other rotor cells, state 3 is the stator, \
+                         and state 6 is the boundary. Only for Conway's Game of Life.\n\
+                         Partial results with unknown cells are still displayed \
+                         in the mixed format.\n\
+                         \"json\" prints each result as a JSON object in a single line, \
+                         containing the configuration, all generations, the cell counts \
+                         of all generations, the number of conflicts and the time in seconds. \
+                         The TUI still displays the mixed format.\n",
+                    )
+                    .long("format")
+                    .takes_value(true)
+                    .possible_values(&["mixed", "rle", "phases", "history", "json"])
+                    .default_value("mixed"),
+            )
+            .arg(
+                Arg::with_name("CONFIG")
+                    .help("Reads the search configuration from a TOML or JSON file")
+                    .long_help(
+                        "Reads the search configuration from a TOML or JSON file\n\
+                         Files with the extension `.json` are read as JSON, \
+                         other files as TOML. Missing fields take their default values. \
+                         Arguments given in the command line override the file. \
+                         The file format is the same as the output of --print-config, \
+                         and can also be imported and exported by the web app.\n\
+                         Files with the extension `.lss` are imported as WinLifeSearch \
+                         save files, and `.lls` as Logic Life Search input patterns.\n",
+                    )
+                    .long("config")
+                    .takes_value(true)
+                    .value_name("FILE"),
+            )
+            .arg(
+                Arg::with_name("PRINTCONFIG")
+                    .help("Prints the search configuration and exits")
+                    .long_help(
+                        "Prints the search configuration and exits\n\
+                         The configuration is printed in TOML format, \
+                         or in JSON format if --format json is set.\n",
+                    )
+                    .long("print-config"),
+            )
+            .arg(
+                Arg::with_name("INPUT")
+                    .help("Reads a pattern from a file as known cells")
+                    .long_help(
+                        "Reads a pattern from a file as known cells\n\
+                         Supports RLE (including multistate RLE), Plaintext and \
+                         Life 1.06 formats. Cells represented by `?` are unknown.\n\
+                         The upper left corner of the pattern is placed at the position \
+                         given by --at, in the generation given by --gen.\n",
+                    )
+                    .short("i")
+                    .long("input")
+                    .takes_value(true)
+                    .value_name("FILE"),
+            )
+            .arg(
+                Arg::with_name("AT")
+                    .help("Position of the upper left corner of the input, target, rotor or reaction pattern")
+                    .long("at")
+                    .takes_value(true)
+                    .value_name("X,Y")
+                    .default_value("0,0")
+                    .validator(|s| {
+                        parse_position(&s)
+                            .map(|_| ())
+                            .ok_or_else(|| String::from("position must be of the form X,Y"))
+                    }),
+            )
+            .arg(
+                Arg::with_name("GEN")
+                    .help("Generation of the input pattern")
+                    .long("gen")
+                    .takes_value(true)
+                    .value_name("T")
+                    .default_value("0")
+                    .validator(|t| t.parse::<isize>().map(|_| ()).map_err(|e| e.to_string())),
+            )
+            .arg(
+                Arg::with_name("NONPERIODIC")
+                    .help("Searches for evolutions that do not return to the first generation")
+                    .long_help(
+                        "Searches for evolutions that do not return to the first generation\n\
+                         The first generation has no predecessor, and the last generation \
+                         has no successor. DX, DY, --transform and --front are ignored.\n",
+                    )
+                    .long("non-periodic"),
+            )
+            .arg(
+                Arg::with_name("GROW")
+                    .help("Grows the world along the search order when needed")
+                    .long_help(
+                        "Grows the world along the search order when needed\n\
+                         With --order row, a row is added to the bottom of the world \
+                         whenever the last row becomes nonempty, so HEIGHT is only the \
+                         initial height. With --order column, a column is added to the \
+                         right instead. A result is found when its last row is empty. \
+                         The optional value is the maximal height (or width), \
+                         e.g. --grow=32. The symmetry and the transformation must not \
+                         involve the height (or the width), and the engine must be \
+                         backtrack.\n",
+                    )
+                    .long("grow")
+                    .takes_value(true)
+                    .value_name("LIMIT")
+                    .min_values(0)
+                    .require_equals(true)
+                    .validator(|l| l.parse::<u32>().map(|_| ()).map_err(|e| e.to_string())),
+            )
+            .arg(
+                Arg::with_name("TARGET")
+                    .help("Searches for predecessors of a pattern in a file")
+                    .long_help(
+                        "Searches for predecessors of a pattern in a file\n\
+                         The pattern is read in the same way as --input, and placed at \
+                         the position given by --at in the last generation. Other cells \
+                         in the last generation are dead. Implies \
+                         --non-periodic, so the first generation of a result is a \
+                         predecessor P-1 generations before the pattern.\n",
+                    )
+                    .long("target")
+                    .takes_value(true)
+                    .value_name("FILE")
+                    .conflicts_with_all(&["INPUT", "PERIOD"]),
+            )
+            .arg(
+                Arg::with_name("ROTOR")
+                    .help("Searches for the smallest stator supporting a rotor in a file")
+                    .long_help(
+                        "Searches for the smallest stator supporting a rotor in a file\n\
+                         The file contains a pattern for each generation, separated by \
+                         blank lines, in the same formats as --input. Their upper left \
+                         corners are placed at the position given by --at. The period is \
+                         the number of patterns, so P is ignored. Known cells in the \
+                         patterns are the rotor, and all other cells must be stable. \
+                         Implies --reduce and --all, so each result has a smaller stator \
+                         than the last one.\n",
+                    )
+                    .long("rotor")
+                    .takes_value(true)
+                    .value_name("FILE")
+                    .conflicts_with_all(&["TARGET", "NONPERIODIC", "PERIOD"]),
+            )
+            .arg(
+                Arg::with_name("REACTION")
+                    .help("Searches for catalysts of a reaction in a file")
+                    .long_help(
+                        "Searches for catalysts of a reaction in a file\n\
+                         The pattern is read in the same way as --input, and placed at \
+                         the position given by --at in generation 0. Its known cells are \
+                         the active region, which evolves freely. All other cells must \
+                         be a still life in generation 0, and return to it in the last \
+                         generation. Implies --non-periodic, so P is the number of \
+                         generations in which the catalyst must recover.\n",
+                    )
+                    .long("reaction")
+                    .takes_value(true)
+                    .value_name("FILE")
+                    .conflicts_with_all(&["TARGET", "ROTOR"]),
+            )
+            .arg(
+                Arg::with_name("DIMACS")
+                    .help("Writes the search problem to a file in DIMACS CNF format and exits")
+                    .long_help(
+                        "Writes the search problem to a file in DIMACS CNF format and exits\n\
+                         The file can be solved by any SAT solver. Each unknown cell \
+                         is a variable, which is true when the cell is alive. \
+                         The variable map is written as comment lines `c VAR X Y T`. \
+                         Generations rules are not supported.\n",
+                    )
+                    .long("dimacs")
+                    .takes_value(true)
+                    .value_name("FILE")
+                    .conflicts_with("RESUME"),
+            )
+            .arg(
+                Arg::with_name("MODEL")
+                    .help("Reads a result from the output of a SAT solver")
+                    .long_help(
+                        "Reads a result from the output of a SAT solver\n\
+                         The solver must be run on the output of --dimacs \
+                         with the same search options. \
+                         The pattern in the model is checked and printed \
+                         in the given --format, without searching or entering the TUI.\n",
+                    )
+                    .long("model")
+                    .takes_value(true)
+                    .value_name("FILE")
+                    .conflicts_with_all(&["RESUME", "DIMACS"]),
+            )
+            .arg(
+                Arg::with_name("PROOF")
+                    .help("Writes a proof to a file as the search goes on")
+                    .long_help(
+                        "Writes a proof to a file as the search goes on\n\
+                         The proof is complete when the search ends without more results. \
+                         It lists the leaves of the search tree, each in a line: \
+                         the number of cells kept from the previous branch, the other \
+                         cells chosen or switched by backtracking as `X,Y,T=STATE`, \
+                         and `!X,Y,T` for the cell where the conflict is found, \
+                         `!trivial` for a rejected pattern, or `!found` for a result. \
+                         It can be checked by --check-proof with the same search options. \
+                         Only supported for rules with two states, by the backtracking \
+                         engine, without --grow, --restart or --reduce. Implies --no-tui.\n",
+                    )
+                    .long("proof")
+                    .takes_value(true)
+                    .value_name("FILE")
+                    .conflicts_with_all(&["RESUME", "DIMACS", "MODEL"]),
+            )
+            .arg(
+                Arg::with_name("CHECKPROOF")
+                    .help("Checks a proof written by --proof and exits")
+                    .long_help(
+                        "Checks a proof written by --proof and exits\n\
+                         The search options must be the same as those of the search \
+                         that wrote the proof. Each branch is checked by unit propagation \
+                         over the encoding written by --dimacs, independently of the search. \
+                         If the proof passes the check, \
+                         the numbers of leaves and results are printed, and there are \
+                         no results other than those in the proof.\n",
+                    )
+                    .long("check-proof")
+                    .takes_value(true)
+                    .value_name("FILE")
+                    .conflicts_with_all(&["RESUME", "DIMACS", "MODEL", "PROOF"]),
+            )
+            .arg(
+                Arg::with_name("WIDTH")
+                    .help("Sweeps over a range of widths")
+                    .long_help(
+                        "Sweeps over a range of widths\n\
+                         A range `A..B` contains both ends. \
+                         If any of --width, --height and --period is set, \
+                         the search runs at each point of the ranges in turn, \
+                         without entering the TUI, until a result is found, \
+                         there are no results, or the --budget runs out. \
+                         Points are searched in the order of periods, then widths, \
+                         then heights. The results are printed when they are found, \
+                         and a summary table is printed to stderr at the end.\n\
+                         Sizes that are not square are skipped if the transformation \
+                         or the symmetry is only valid for square worlds.\n",
+                    )
+                    .long("width")
+                    .takes_value(true)
+                    .value_name("RANGE")
+                    .conflicts_with_all(&["ALL", "RESUME", "DIMACS", "MODEL", "CHECKPOINT", "PROGRESS"])
+                    .validator(|r| {
+                        parse_range(&r)
+                            .map(|_| ())
+                            .ok_or_else(|| String::from("range must be of the form A..B"))
+                    }),
+            )
+            .arg(
+                Arg::with_name("HEIGHT")
+                    .help("Sweeps over a range of heights")
+                    .long_help(
+                        "Sweeps over a range of heights\n\
+                         See --width for details.\n",
+                    )
+                    .long("height")
+                    .takes_value(true)
+                    .value_name("RANGE")
+                    .conflicts_with_all(&["ALL", "RESUME", "DIMACS", "MODEL", "CHECKPOINT", "PROGRESS"])
+                    .validator(|r| {
+                        parse_range(&r)
+                            .map(|_| ())
+                            .ok_or_else(|| String::from("range must be of the form A..B"))
+                    }),
+            )
+            .arg(
+                Arg::with_name("PERIOD")
+                    .help("Sweeps over a range of periods")
+                    .long_help(
+                        "Sweeps over a range of periods\n\
+                         See --width for details.\n",
+                    )
+                    .long("period")
+                    .takes_value(true)
+                    .value_name("RANGE")
+                    .conflicts_with_all(&["ALL", "RESUME", "DIMACS", "MODEL", "CHECKPOINT", "PROGRESS"])
+                    .validator(|r| {
+                        parse_range(&r)
+                            .map(|_| ())
+                            .ok_or_else(|| String::from("range must be of the form A..B"))
+                    }),
+            )
+            .arg(
+                Arg::with_name("BUDGET")
+                    .help("Time limit at each point of a sweep")
+                    .long_help(
+                        "Time limit at each point of a sweep\n\
+                         A number followed by `s`, `m`, `h` or `d`. \
+                         A number without a unit is in seconds. \
+                         Without this option, each point is searched to the end.\n",
+                    )
+                    .long("budget")
+                    .takes_value(true)
+                    .value_name("TIME")
+                    .validator(|d| {
+                        parse_duration(&d)
+                            .map(|_| ())
+                            .ok_or_else(|| String::from("invalid time, e.g. 30s, 10m or 2h"))
+                    }),
+            )
+            .arg(
+                Arg::with_name("FIRST")
+                    .help("Stops a sweep at the first point where a result is found")
+                    .long("first"),
+            )
+            .arg(
+                Arg::with_name("PROGRESS")
+                    .help("Prints the search progress to stderr periodically")
+                    .long_help(
+                        "Prints the search progress to stderr periodically\n\
+                         The progress contains the time, the number of known living cells, \
+                         the number of conflicts and conflicts per second since the last \
+                         report, the number of decisions on the current branch of the search, \
+                         and the number of results found so far. \
+                         The optional value is the time between two reports, \
+                         e.g. --progress=30s. The TUI shows the progress by itself, \
+                         so this option only works with --no-tui.\n",
+                    )
+                    .long("progress")
+                    .takes_value(true)
+                    .value_name("TIME")
+                    .min_values(0)
+                    .require_equals(true)
+                    .validator(|d| {
+                        parse_duration(&d)
+                            .map(|_| ())
+                            .ok_or_else(|| String::from("invalid time, e.g. 30s, 10m or 2h"))
+                    }),
+            )
+            .arg(
+                Arg::with_name("EXPLAIN")
+                    .help("Explains why the search fails before any decision")
+                    .long_help(
+                        "Explains why the search fails before any decision\n\
+                         Records why each cell has its state. When no result is found, \
+                         the cells involved in the last conflict found before any decision \
+                         are printed to stderr, each with the cell where the rule or \
+                         the symmetry determines it, back to the known cells. \
+                         This shows which known cells cannot coexist. \
+                         In the TUI, press [e] to explain why a cell has its state. \
+                         It makes the search slower.\n",
+                    )
+                    .long("explain"),
+            )
+            .arg(
+                Arg::with_name("PROGRESSPATTERN")
+                    .help("Prints the current partial pattern with the progress")
+                    .long("progress-pattern")
+                    .requires("PROGRESS"),
+            )
+            .arg(
+                Arg::with_name("CHECKPOINT")
+                    .help("Writes the search status to a file periodically")
+                    .long_help(
+                        "Writes the search status to a file periodically\n\
+                         The file is written every --checkpoint-interval, when the search \
+                         ends, and when the program is interrupted by Ctrl-C or SIGTERM. \
+                         It can be read by --resume to continue the search.\n",
+                    )
+                    .long("checkpoint")
+                    .takes_value(true)
+                    .value_name("FILE"),
+            )
+            .arg(
+                Arg::with_name("INTERVAL")
+                    .help("Time between two checkpoints")
+                    .long_help(
+                        "Time between two checkpoints\n\
+                         A number followed by `s`, `m`, `h` or `d`. \
+                         A number without a unit is in seconds.\n",
+                    )
+                    .long("checkpoint-interval")
+                    .takes_value(true)
+                    .value_name("TIME")
+                    .default_value("10m")
+                    .validator(|d| {
+                        parse_duration(&d)
+                            .map(|_| ())
+                            .ok_or_else(|| String::from("invalid time, e.g. 30s, 10m or 2h"))
+                    }),
+            )
+            .arg(
+                Arg::with_name("RESUME")
+                    .help("Continues the search from a checkpoint file")
+                    .long_help(
+                        "Continues the search from a checkpoint file\n\
+                         The search configuration, the number of conflicts, \
+                         the number of found results and the time are all restored \
+                         from the file. Other search options are ignored.\n",
+                    )
+                    .long("resume")
+                    .takes_value(true)
+                    .value_name("FILE")
+                    .conflicts_with_all(&["CONFIG", "INPUT"]),
             );
 
         #[cfg(feature = "tui")]
@@ -243,85 +933,344 @@ impl Args {
             );
         }
 
-        let matches = app.get_matches_safe()?;
+        let matches = app.get_matches_from_safe(args)?;
 
-        let width = matches.value_of("X").unwrap().parse().unwrap();
-        let height = matches.value_of("Y").unwrap().parse().unwrap();
-        let period = matches.value_of("P").unwrap().parse().unwrap();
+        let mut config = match matches.value_of("CONFIG") {
+            Some(path) => read_config(path)?,
+            // Without a configuration file, `--front` is off unless it is set.
+            None => Config::default().set_non_empty_front(false),
+        };
 
-        let dx = matches.value_of("DX").unwrap().parse().unwrap();
-        let dy = matches.value_of("DY").unwrap().parse().unwrap();
+        // Only the arguments that are explicitly given override the configuration file.
+        let explicit = |name| {
+            if matches.occurrences_of(name) > 0 {
+                matches.value_of(name)
+            } else {
+                None
+            }
+        };
+        if let Some(width) = explicit("X") {
+            config.width = width.parse().unwrap();
+        }
+        if let Some(height) = explicit("Y") {
+            config.height = height.parse().unwrap();
+        }
+        if let Some(period) = explicit("P") {
+            config.period = period.parse().unwrap();
+        }
+        if let Some(dx) = explicit("DX") {
+            config.dx = dx.parse().unwrap();
+        }
+        if let Some(dy) = explicit("DY") {
+            config.dy = dy.parse().unwrap();
+        }
+        if let Some(transform) = explicit("TRANSFORM") {
+            config.transform = transform.parse().unwrap();
+        }
+        if let Some(symmetry) = explicit("SYMMETRY") {
+            config.symmetry = symmetry.parse().unwrap();
+        }
+        if let Some(rule_string) = explicit("RULE") {
+            config.rule_string = rule_string.to_string();
+        }
+        if let Some(order) = explicit("ORDER") {
+            config.search_order = match order {
+                "row" | "r" => Some(SearchOrder::RowFirst),
+                "column" | "c" => Some(SearchOrder::ColumnFirst),
+                _ => None,
+            };
+        }
+        if let Some(choose) = explicit("CHOOSE") {
+            config.new_state = match choose {
+                "dead" | "d" => NewState::ChooseDead,
+                "alive" | "a" => NewState::ChooseAlive,
+                "random" | "r" => NewState::Random,
+                _ => NewState::ChooseAlive,
+            };
+        }
+        if let Some(density) = matches.value_of("DENSITY") {
+            config.density = Some(density.parse().unwrap());
+        }
+        if let Some(seed) = matches.value_of("SEED") {
+            config.seed = Some(seed.parse().unwrap());
+        }
+        if let Some(restart) = explicit("RESTART") {
+            config.restart = restart.parse().unwrap();
+        }
+        if let Some(engine) = explicit("ENGINE") {
+            config.engine = match engine {
+                "sat" => Engine::Sat,
+                _ => Engine::Backtrack,
+            };
+        }
+        if let Some(max) = explicit("MAX") {
+            config.max_cell_count = match max.parse().unwrap() {
+                0 => None,
+                i => Some(i),
+            };
+        }
+        if matches.is_present("FRONT") {
+            config.non_empty_front = true;
+        }
+        if matches.is_present("REDUCE") {
+            config.reduce_max = true;
+        }
+        if let Some(objective) = explicit("OBJECTIVE") {
+            config.objective = objective.parse().unwrap();
+        }
+        if let Some(bound) = explicit("BOUND") {
+            config.bound = match bound.parse().unwrap() {
+                0 => None,
+                i => Some(i),
+            };
+        }
+        if matches.is_present("NONPERIODIC") {
+            config.periodic = false;
+        }
+        if matches.is_present("GROW") {
+            let limit = matches.value_of("GROW").map(|l| l.parse().unwrap());
+            config = config.set_grow(true, limit);
+        }
+        if matches.is_present("EXPLAIN") {
+            config = config.set_explain(true);
+        }
+        let proof = matches.value_of("PROOF").map(String::from);
+        if proof.is_some() {
+            config = config.set_proof(true);
+        }
+        let check_proof = matches.value_of("CHECKPROOF").map(String::from);
 
-        let transform: Transform = matches.value_of("TRANSFORM").unwrap().parse().unwrap();
-        let symmetry: Symmetry = matches.value_of("SYMMETRY").unwrap().parse().unwrap();
+        let all = matches.is_present("ALL") || matches.is_present("ROTOR");
+        let verify = matches.is_present("VERIFY");
+        let print_config = matches.is_present("PRINTCONFIG");
+        let format = match matches.value_of("FORMAT").unwrap() {
+            "rle" => Format::Rle,
+            "phases" => Format::Phases,
+            "history" => Format::History,
+            "json" => Format::Json,
+            _ => Format::Mixed,
+        };
+        #[cfg(feature = "tui")]
+        let reset = matches.is_present("RESET");
+        #[cfg(feature = "tui")]
+        let no_tui = matches.is_present("NOTUI");
 
-        if width != height {
-            if transform.square_world() {
-                return Err(Error::with_description(
-                    &format!(
-                        "The transformation '{:?}' is only valid for square worlds",
-                        transform
-                    ),
+        if let Some(path) = matches.value_of("INPUT") {
+            let pattern = read_pattern(path)?;
+            let (x, y) = parse_position(matches.value_of("AT").unwrap()).unwrap();
+            let t = matches.value_of("GEN").unwrap().parse().unwrap();
+            config = config.add_pattern(&pattern, x, y, t);
+        }
+        if let Some(path) = matches.value_of("TARGET") {
+            let pattern = read_pattern(path)?;
+            let (x, y) = parse_position(matches.value_of("AT").unwrap()).unwrap();
+            config = config.set_target(&pattern, x, y);
+        }
+        if let Some(path) = matches.value_of("ROTOR") {
+            let rotor = read_patterns(path)?;
+            let (x, y) = parse_position(matches.value_of("AT").unwrap()).unwrap();
+            config = config.set_rotor(&rotor, x, y);
+        }
+        if let Some(path) = matches.value_of("REACTION") {
+            let pattern = read_pattern(path)?;
+            let (x, y) = parse_position(matches.value_of("AT").unwrap()).unwrap();
+            config = config.set_reaction(&pattern, x, y);
+        }
+
+        let sweep = if ["WIDTH", "HEIGHT", "PERIOD"]
+            .iter()
+            .any(|&name| matches.is_present(name))
+        {
+            let range = |name| matches.value_of(name).and_then(parse_range);
+            let mut sweep = Sweep::new(config.clone())
+                .set_time_limit(matches.value_of("BUDGET").and_then(parse_duration))
+                .set_stop_at_first(matches.is_present("FIRST"));
+            if let Some(widths) = range("WIDTH") {
+                sweep = sweep.set_widths(widths);
+            }
+            if let Some(heights) = range("HEIGHT") {
+                sweep = sweep.set_heights(heights);
+            }
+            if let Some(periods) = range("PERIOD") {
+                sweep = sweep.set_periods(periods);
+            }
+            // The search and the printed configuration are those of the first point
+            // whose world can be created, or of the first point if there is none,
+            // so that its error is reported.
+            let configs = sweep.configs();
+            let first = configs.first().ok_or_else(|| {
+                Error::with_description(
+                    "The sweep contains no valid sizes for the transformation and the symmetry",
                     ErrorKind::InvalidValue,
-                ));
+                )
+            })?;
+            config = configs
+                .iter()
+                .find(|config| config.world().is_ok())
+                .unwrap_or(first)
+                .clone();
+            Some(sweep)
+        } else {
+            if config.width != config.height {
+                if config.transform.square_world() {
+                    return Err(Error::with_description(
+                        &format!(
+                            "The transformation '{}' is only valid for square worlds",
+                            config.transform
+                        ),
+                        ErrorKind::InvalidValue,
+                    ));
+                }
+                if config.symmetry.square_world() {
+                    return Err(Error::with_description(
+                        &format!(
+                            "The symmetry '{}' is only valid for square worlds",
+                            config.symmetry
+                        ),
+                        ErrorKind::InvalidValue,
+                    ));
+                }
             }
-            if symmetry.square_world() {
+            None
+        };
+
+        let dimacs = matches.value_of("DIMACS").map(String::from);
+
+        let model = matches.is_present("MODEL");
+        let (search, found, time) = if let Some(path) = matches.value_of("RESUME") {
+            let checkpoint =
+                Checkpoint::read(path).map_err(|e| Error::with_description(&e, ErrorKind::Io))?;
+            if checkpoint.finished {
                 return Err(Error::with_description(
                     &format!(
-                        "The symmetry '{:?}' is only valid for square worlds",
-                        symmetry
+                        "The search in {} has already finished with {} results",
+                        path, checkpoint.found
                     ),
                     ErrorKind::InvalidValue,
                 ));
             }
-        }
-
-        let all = matches.is_present("ALL");
-        #[cfg(feature = "tui")]
-        let reset = matches.is_present("RESET");
-        #[cfg(feature = "tui")]
-        let no_tui = matches.is_present("NOTUI");
-        let search_order = match matches.value_of("ORDER").unwrap() {
-            "row" | "r" => Some(SearchOrder::RowFirst),
-            "column" | "c" => Some(SearchOrder::ColumnFirst),
-            _ => None,
-        };
-        let new_state = match matches.value_of("CHOOSE").unwrap() {
-            "dead" | "d" => NewState::ChooseDead,
-            "alive" | "a" => NewState::ChooseAlive,
-            "random" | "r" => NewState::Random,
-            _ => NewState::ChooseAlive,
-        };
-        let max_cell_count = matches.value_of("MAX").unwrap().parse().unwrap();
-        let max_cell_count = match max_cell_count {
-            0 => None,
-            i => Some(i),
+            let search = checkpoint.world.world().map_err(|e| {
+                Error::with_description(
+                    &format!("Unable to restore {}: {}", path, e),
+                    ErrorKind::InvalidValue,
+                )
+            })?;
+            let time = Duration::from_secs_f64(checkpoint.time);
+            (search, checkpoint.found, time)
+        } else if let Some(path) = matches.value_of("MODEL") {
+            let model = fs::read_to_string(path).map_err(|e| {
+                Error::with_description(&format!("Unable to read {}: {}", path, e), ErrorKind::Io)
+            })?;
+            let search = config
+                .cnf()
+                .and_then(|cnf| cnf.read_model(&model))
+                .map_err(|e| {
+                    Error::with_description(
+                        &format!("Unable to read the model in {}: {}", path, e),
+                        ErrorKind::InvalidValue,
+                    )
+                })?;
+            (search, 1, Duration::default())
+        } else {
+            let search = config
+                .world()
+                .map_err(|e| Error::with_description(&e.to_string(), ErrorKind::InvalidValue))?;
+            (search, 0, Duration::default())
         };
-        let non_empty_front = matches.is_present("FRONT");
-        let reduce_max = matches.is_present("REDUCE");
 
-        let rule_string = matches.value_of("RULE").unwrap().to_string();
+        if format == Format::History && !search.config().is_life() {
+            return Err(Error::with_description(
+                "The LifeHistory format is only available for Conway's Game of Life",
+                ErrorKind::InvalidValue,
+            ));
+        }
+        if all && search.config().restart != Restart::Never {
+            return Err(Error::with_description(
+                "--restart cannot be used with --all or --rotor",
+                ErrorKind::ArgumentConflict,
+            ));
+        }
 
-        let config = Config::new(width, height, period)
-            .set_translate(dx, dy)
-            .set_transform(transform)
-            .set_symmetry(symmetry)
-            .set_search_order(search_order)
-            .set_new_state(new_state)
-            .set_max_cell_count(max_cell_count)
-            .set_non_empty_front(non_empty_front)
-            .set_reduce_max(reduce_max)
-            .set_rule_string(rule_string);
+        let checkpoint = match matches.value_of("CHECKPOINT") {
+            Some(path) => {
+                let interval = parse_duration(matches.value_of("INTERVAL").unwrap()).unwrap();
+                let checkpointer = Checkpointer::new(path, interval)
+                    .map_err(|e| Error::with_description(&e, ErrorKind::Io))?;
+                Some(checkpointer)
+            }
+            None => None,
+        };
 
-        let search = config.world().unwrap();
+        let progress = if matches.is_present("PROGRESS") {
+            let interval = matches.value_of("PROGRESS").and_then(parse_duration);
+            Some(interval.unwrap_or(PROGRESS_INTERVAL))
+        } else {
+            None
+        };
+        let progress_pattern = matches.is_present("PROGRESSPATTERN");
 
         Ok(Args {
             search,
             all,
+            verify,
+            print_config,
+            dimacs,
+            model,
+            proof,
+            check_proof,
+            sweep,
+            format,
+            checkpoint,
+            progress,
+            progress_pattern,
+            found,
+            time,
             #[cfg(feature = "tui")]
             reset,
             #[cfg(feature = "tui")]
             no_tui,
         })
     }
+
+    /// Parses the search options of a batch job, given in a single line.
+    ///
+    /// The line is split at whitespace, without any quoting.
+    /// A line with only a file name is read as `--config FILE`.
+    pub(crate) fn parse_job(line: &str) -> ClapResult<Self> {
+        let mut args = vec!["rlifesrc"];
+        // Batch jobs never enter the TUI.
+        #[cfg(feature = "tui")]
+        args.push("--no-tui");
+        let words = line.split_whitespace().collect::<Vec<_>>();
+        if let [path] = words[..] {
+            if !is_positive(path) {
+                args.push("--config");
+            }
+        }
+        for option in &[
+            "--print-config",
+            "--dimacs",
+            "--model",
+            "--check-proof",
+            "--checkpoint",
+            "--progress",
+            "--resume",
+            "--width",
+            "--height",
+            "--period",
+        ] {
+            let prefix = format!("{}=", option);
+            if words
+                .iter()
+                .any(|word| word == option || word.starts_with(&prefix))
+            {
+                return Err(Error::with_description(
+                    &format!("The option '{}' is not supported in batch jobs", option),
+                    ErrorKind::ArgumentConflict,
+                ));
+            }
+        }
+        args.extend(words);
+        Args::parse_from(args)
+    }
 }
@@ -0,0 +1,383 @@
+//! Running a batch of searches from a file.
+
+use crate::{
+    args::Args,
+    checkpoint::{self, parse_duration, Checkpoint, Checkpointer},
+    search_loop,
+};
+use clap::{App, AppSettings, Arg, Error, ErrorKind, Result as ClapResult};
+use serde::{Deserialize, Serialize};
+use std::{
+    ffi::OsString,
+    fs::{self, OpenOptions},
+    path::{Path, PathBuf},
+    process::exit,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Creates the app that parses the options of `rlifesrc batch`.
+fn app() -> App<'static, 'static> {
+    App::new("rlifesrc batch")
+        .about("Runs a batch of searches from a file")
+        .long_about(
+            "Runs a batch of searches from a file\n\
+             \n\
+             Each line of the file is a search, written in the same way as the \
+             command-line arguments, e.g., `16 5 3 0 1 --all`. A line with only \
+             a file name is read as `--config FILE`. Empty lines and lines \
+             starting with `#` are ignored. The searches never enter the TUI.\n\
+             \n\
+             The results of the N-th search are written to `job-N.txt` in the \
+             output directory, its checkpoint to `job-N.checkpoint`, and its \
+             status to `job-N.status` when it ends. When the batch is run again \
+             with the same output directory, searches that have ended are skipped, \
+             interrupted searches continue from their checkpoints, and searches \
+             that ended with errors are run again.",
+        )
+        .arg(
+            Arg::with_name("JOBS")
+                .help("The file of searches, one in each line")
+                .required(true)
+                .index(1),
+        )
+        .arg(
+            Arg::with_name("OUTPUT")
+                .help("The output directory")
+                .long_help(
+                    "The output directory\n\
+                     Defaults to the file of searches with the extension `.out`.\n",
+                )
+                .short("o")
+                .long("output")
+                .takes_value(true)
+                .value_name("DIR"),
+        )
+        .arg(
+            Arg::with_name("THREADS")
+                .help("Number of searches running at the same time")
+                .short("j")
+                .long("jobs")
+                .takes_value(true)
+                .value_name("N")
+                .default_value("1")
+                .validator(|n| match n.parse::<usize>() {
+                    Ok(n) if n > 0 => Ok(()),
+                    _ => Err(String::from("number of jobs must be a positive integer")),
+                }),
+        )
+        .arg(
+            Arg::with_name("INTERVAL")
+                .help("Time between two checkpoints of each search")
+                .long("checkpoint-interval")
+                .takes_value(true)
+                .value_name("TIME")
+                .default_value("10m")
+                .validator(|d| {
+                    parse_duration(&d)
+                        .map(|_| ())
+                        .ok_or_else(|| String::from("invalid time, e.g. 30s, 10m or 2h"))
+                }),
+        )
+}
+
+/// How a search in a batch ends.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum JobResult {
+    /// Some results are found.
+    Found,
+    /// No results are found.
+    None,
+    /// The search cannot be started or written.
+    Error,
+}
+
+/// The content of a status file.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct JobStatus {
+    /// The line of the search in the file of searches.
+    line: String,
+    /// How the search ends.
+    result: JobResult,
+    /// Number of results found.
+    found: u64,
+    /// Time spent on the search, in seconds.
+    time: f64,
+    /// The error message, if there is an error.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl JobStatus {
+    /// The status of a search that ends with an error.
+    fn error(line: &str, error: String) -> Self {
+        JobStatus {
+            line: line.to_string(),
+            result: JobResult::Error,
+            found: 0,
+            time: 0.0,
+            error: Some(error),
+        }
+    }
+}
+
+/// A batch of searches.
+pub(crate) struct Batch {
+    /// The lines of the searches.
+    jobs: Vec<String>,
+    /// The output directory.
+    output: PathBuf,
+    /// Number of searches running at the same time.
+    threads: usize,
+    /// Time between two checkpoints of each search.
+    interval: Duration,
+}
+
+impl Batch {
+    /// Parses the arguments after `rlifesrc`, the first of which is `batch`.
+    pub(crate) fn parse<I, T>(args: I) -> ClapResult<Self>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString> + Clone,
+    {
+        let matches = app()
+            .setting(AppSettings::ColoredHelp)
+            .get_matches_from_safe(args)?;
+        let path = matches.value_of("JOBS").unwrap();
+        let input = fs::read_to_string(path).map_err(|e| {
+            Error::with_description(&format!("Unable to read {}: {}", path, e), ErrorKind::Io)
+        })?;
+        let jobs = input
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(String::from)
+            .collect();
+        let output = match matches.value_of("OUTPUT") {
+            Some(dir) => PathBuf::from(dir),
+            None => Path::new(path).with_extension("out"),
+        };
+        let threads = matches.value_of("THREADS").unwrap().parse().unwrap();
+        let interval = parse_duration(matches.value_of("INTERVAL").unwrap()).unwrap();
+        Ok(Batch {
+            jobs,
+            output,
+            threads,
+            interval,
+        })
+    }
+
+    /// Path of a file of the `index`-th search, counting from 0.
+    fn path(&self, index: usize, extension: &str) -> PathBuf {
+        let width = self.jobs.len().to_string().len();
+        self.output.join(format!(
+            "job-{:0width$}.{}",
+            index + 1,
+            extension,
+            width = width
+        ))
+    }
+
+    /// Reads the status of the `index`-th search, if it has ended.
+    fn read_status(&self, index: usize) -> Result<Option<JobStatus>, String> {
+        let path = self.path(index, "status");
+        if !path.exists() {
+            return Ok(None);
+        }
+        let input = fs::read_to_string(&path)
+            .map_err(|e| format!("Unable to read {}: {}", path.display(), e))?;
+        let status: JobStatus = serde_json::from_str(&input)
+            .map_err(|e| format!("Invalid status file {}: {}", path.display(), e))?;
+        if status.line != self.jobs[index] {
+            return Err(format!(
+                "{} is for the search `{}`, not `{}`",
+                path.display(),
+                status.line,
+                self.jobs[index]
+            ));
+        }
+        Ok(Some(status))
+    }
+
+    /// Runs the `index`-th search, and writes its status file.
+    ///
+    /// Returns `None` if the program is interrupted.
+    fn run_job(&self, index: usize) -> Option<JobStatus> {
+        let line = &self.jobs[index];
+        let status = match self.search(index) {
+            Ok(Some((found, time))) => {
+                let result = match found {
+                    0 => JobResult::None,
+                    _ => JobResult::Found,
+                };
+                JobStatus {
+                    line: line.clone(),
+                    result,
+                    found,
+                    time: time.as_secs_f64(),
+                    error: None,
+                }
+            }
+            Ok(None) => return None,
+            Err(e) => JobStatus::error(line, e),
+        };
+        let path = self.path(index, "status");
+        if let Err(e) = fs::write(&path, serde_json::to_string(&status).unwrap()) {
+            eprintln!("Unable to write {}: {}", path.display(), e);
+        }
+        Some(status)
+    }
+
+    /// Runs the `index`-th search, continuing from its checkpoint if there is one.
+    ///
+    /// Returns the number of results and the time,
+    /// or `None` if the program is interrupted.
+    fn search(&self, index: usize) -> Result<Option<(u64, Duration)>, String> {
+        let mut args = Args::parse_job(&self.jobs[index]).map_err(|e| {
+            e.message
+                .trim_start_matches("error: ")
+                .lines()
+                .next()
+                .unwrap_or_default()
+                .to_string()
+        })?;
+        let checkpoint_path = self.path(index, "checkpoint");
+        let checkpoint_path = checkpoint_path.to_str().unwrap();
+        if Path::new(checkpoint_path).exists() {
+            let checkpoint = Checkpoint::read(checkpoint_path)?;
+            let time = Duration::from_secs_f64(checkpoint.time);
+            if checkpoint.finished {
+                return Ok(Some((checkpoint.found, time)));
+            }
+            args.search = checkpoint
+                .world
+                .world()
+                .map_err(|e| format!("Unable to restore {}: {}", checkpoint_path, e))?;
+            args.found = checkpoint.found;
+            args.time = time;
+        }
+        args.checkpoint = Some(Checkpointer::new(checkpoint_path, self.interval)?);
+        let time = args.time;
+        let start_time = Instant::now();
+        let path = self.path(index, "txt");
+        let mut output = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(|e| format!("Unable to write {}: {}", path.display(), e))?;
+        let mut log = output
+            .try_clone()
+            .map_err(|e| format!("Unable to write {}: {}", path.display(), e))?;
+        let result = search_loop(args, &mut output, &mut log)
+            .map_err(|e| format!("Unable to write {}: {}", path.display(), e))?;
+        Ok(result.map(|(_, found)| (found, time + start_time.elapsed())))
+    }
+
+    /// Runs the batch.
+    ///
+    /// Prints the progress and a summary table to stderr.
+    pub(crate) fn run(self) {
+        if let Err(e) = fs::create_dir_all(&self.output) {
+            eprintln!("Unable to create {}: {}", self.output.display(), e);
+            exit(1);
+        }
+        let mut statuses = Vec::with_capacity(self.jobs.len());
+        for index in 0..self.jobs.len() {
+            match self.read_status(index) {
+                Ok(status) => statuses.push(status),
+                Err(e) => {
+                    eprintln!("{}", e);
+                    exit(1);
+                }
+            }
+        }
+        if let Err(e) = checkpoint::handle_interrupts() {
+            eprintln!("{}", e);
+            exit(1);
+        }
+
+        let pending = (0..self.jobs.len())
+            .filter(|&index| match &statuses[index] {
+                Some(status) => status.result == JobResult::Error,
+                None => true,
+            })
+            .collect::<Vec<_>>();
+        let next = AtomicUsize::new(0);
+        let statuses = Mutex::new(statuses);
+        thread::scope(|scope| {
+            for _ in 0..self.threads.min(pending.len()) {
+                scope.spawn(|| {
+                    while !checkpoint::interrupted() {
+                        let index = match pending.get(next.fetch_add(1, Ordering::SeqCst)) {
+                            Some(&index) => index,
+                            None => break,
+                        };
+                        let name = format!("Job {}", index + 1);
+                        eprintln!("{}: started `{}`", name, self.jobs[index]);
+                        let status = self.run_job(index);
+                        match &status {
+                            Some(status) => match status.result {
+                                JobResult::Found => {
+                                    eprintln!("{}: found {} result(s)", name, status.found)
+                                }
+                                JobResult::None => eprintln!("{}: not found", name),
+                                JobResult::Error => eprintln!(
+                                    "{}: {}",
+                                    name,
+                                    status.error.as_deref().unwrap_or_default()
+                                ),
+                            },
+                            None => eprintln!("{}: interrupted", name),
+                        }
+                        statuses.lock().unwrap()[index] = status;
+                    }
+                });
+            }
+        });
+
+        let statuses = statuses.into_inner().unwrap();
+        eprint!("{}", self.display_statuses(&statuses));
+        if statuses.iter().any(Option::is_none) {
+            eprintln!("Interrupted.");
+            exit(130);
+        }
+    }
+
+    /// Displays the statuses of all searches as a table.
+    ///
+    /// Searches that have not ended are shown as `pending`.
+    fn display_statuses(&self, statuses: &[Option<JobStatus>]) -> String {
+        let mut table = format!(
+            "{:>5}  {:<7} {:>8} {:>10}  {}\n",
+            "job", "result", "found", "time", "search"
+        );
+        for (index, (line, status)) in self.jobs.iter().zip(statuses).enumerate() {
+            let (result, found, time) = match status {
+                Some(status) => {
+                    let result = match status.result {
+                        JobResult::Found => "found",
+                        JobResult::None => "none",
+                        JobResult::Error => "error",
+                    };
+                    let time = format!("{:.2}s", status.time);
+                    (result, status.found.to_string(), time)
+                }
+                None => ("pending", String::new(), String::new()),
+            };
+            table += &format!(
+                "{:>5}  {:<7} {:>8} {:>10}  {}\n",
+                index + 1,
+                result,
+                found,
+                time,
+                line
+            );
+        }
+        table
+    }
+}
@@ -0,0 +1,151 @@
+//! Saving and restoring the search status.
+
+use rlifesrc_lib::{Search, WorldSer};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    io::Result as IoResult,
+    path::PathBuf,
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, Instant},
+};
+
+/// Parses a duration like `90`, `30s`, `10m`, `2h` or `1d`.
+///
+/// A number without a unit is in seconds.
+pub(crate) fn parse_duration(s: &str) -> Option<Duration> {
+    let s = s.trim();
+    let (number, unit) = match s.find(|c: char| !c.is_ascii_digit()) {
+        Some(i) => s.split_at(i),
+        None => (s, "s"),
+    };
+    let secs = number.parse::<u64>().ok()?;
+    let secs = match unit {
+        "s" => secs,
+        "m" => secs.checked_mul(60)?,
+        "h" => secs.checked_mul(60 * 60)?,
+        "d" => secs.checked_mul(24 * 60 * 60)?,
+        _ => return None,
+    };
+    Some(Duration::from_secs(secs))
+}
+
+/// The content of a checkpoint file.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct Checkpoint {
+    /// The saved world.
+    pub(crate) world: WorldSer,
+    /// Number of results found so far.
+    pub(crate) found: u64,
+    /// Time spent on the search so far, in seconds.
+    pub(crate) time: f64,
+    /// Whether there are no more results.
+    ///
+    /// A finished world looks the same as a new one,
+    /// so it must be marked explicitly.
+    #[serde(default)]
+    pub(crate) finished: bool,
+}
+
+impl Checkpoint {
+    /// Reads a checkpoint file.
+    pub(crate) fn read(path: &str) -> Result<Self, String> {
+        let input =
+            fs::read_to_string(path).map_err(|e| format!("Unable to read {}: {}", path, e))?;
+        serde_json::from_str(&input).map_err(|e| format!("Invalid checkpoint {}: {}", path, e))
+    }
+}
+
+/// Whether the program is interrupted by Ctrl-C or SIGTERM.
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+/// Whether the handler for Ctrl-C and SIGTERM is installed.
+static HANDLED: AtomicBool = AtomicBool::new(false);
+
+/// Installs the handler for Ctrl-C and SIGTERM, if it is not installed yet.
+///
+/// After the handler is installed, Ctrl-C and SIGTERM no longer terminate
+/// the program. It is up to the search loop to check `interrupted`.
+pub(crate) fn handle_interrupts() -> Result<(), String> {
+    if !HANDLED.swap(true, Ordering::SeqCst) {
+        ctrlc::set_handler(|| INTERRUPTED.store(true, Ordering::SeqCst))
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Whether the program is interrupted by Ctrl-C or SIGTERM.
+pub(crate) fn interrupted() -> bool {
+    INTERRUPTED.load(Ordering::SeqCst)
+}
+
+/// Writes checkpoints to a file periodically,
+/// and when the program is interrupted by Ctrl-C or SIGTERM.
+pub(crate) struct Checkpointer {
+    /// Path of the checkpoint file.
+    path: PathBuf,
+    /// Time between two checkpoints.
+    interval: Duration,
+    /// Time of the last checkpoint.
+    last: Instant,
+}
+
+impl Checkpointer {
+    /// Creates a new `Checkpointer`, and installs the handler for Ctrl-C and SIGTERM.
+    pub(crate) fn new(path: &str, interval: Duration) -> Result<Self, String> {
+        handle_interrupts()?;
+        Ok(Checkpointer {
+            path: PathBuf::from(path),
+            interval,
+            last: Instant::now(),
+        })
+    }
+
+    /// Whether the program is interrupted by Ctrl-C or SIGTERM.
+    pub(crate) fn interrupted(&self) -> bool {
+        interrupted()
+    }
+
+    /// Whether it is time to write a new checkpoint.
+    pub(crate) fn due(&self) -> bool {
+        self.last.elapsed() >= self.interval
+    }
+
+    /// Writes a checkpoint.
+    ///
+    /// The checkpoint is first written to a temporary file, and then renamed,
+    /// so that the old checkpoint is kept if the program dies while writing.
+    pub(crate) fn save(
+        &mut self,
+        search: &dyn Search,
+        found: u64,
+        time: Duration,
+        finished: bool,
+    ) -> IoResult<()> {
+        let checkpoint = Checkpoint {
+            world: search.ser(),
+            found,
+            time: time.as_secs_f64(),
+            finished,
+        };
+        let mut tmp_path = self.path.clone().into_os_string();
+        tmp_path.push(".tmp");
+        fs::write(&tmp_path, serde_json::to_string(&checkpoint)?)?;
+        fs::rename(&tmp_path, &self.path)?;
+        self.last = Instant::now();
+        Ok(())
+    }
+
+    /// Writes a checkpoint, and prints a warning to stderr if it fails.
+    pub(crate) fn save_or_warn(
+        &mut self,
+        search: &dyn Search,
+        found: u64,
+        time: Duration,
+        finished: bool,
+    ) {
+        if let Err(e) = self.save(search, found, time, finished) {
+            eprintln!("Unable to write checkpoint {}: {}", self.path.display(), e);
+        }
+    }
+}
@@ -1,54 +1,432 @@
 mod args;
+mod batch;
+mod checkpoint;
 
 #[cfg(feature = "tui")]
 mod tui;
 
-use args::Args;
-use rlifesrc_lib::{Search, Status};
-use std::process::exit;
+use args::{Args, Format};
+use batch::Batch;
+use rlifesrc_lib::{Config, Pattern, Search, Status, SweepPoint, SweepStatus};
+use serde::Serialize;
+use std::{
+    env,
+    fs::{self, File},
+    io::{stderr, stdout, BufWriter, Error as IoError, Result as IoResult, Write},
+    process::exit,
+    time::{Duration, Instant},
+};
 
-/// Runs the search without TUI.
+/// A search result in JSON format.
+#[derive(Serialize)]
+struct JsonResult<'a> {
+    /// World configuration.
+    config: &'a Config,
+    /// All generations of the result.
+    generations: Vec<Pattern>,
+    /// Number of living cells in each generation.
+    cell_counts: Vec<usize>,
+    /// Number of conflicts during the search.
+    conflicts: u64,
+    /// Time spent on the search, in seconds.
+    time: f64,
+}
+
+/// Displays a result as a JSON object in a single line.
+pub(crate) fn display_json(search: &dyn Search, time: Duration) -> String {
+    let period = search.config().period;
+    let result = JsonResult {
+        config: search.config(),
+        generations: (0..period).map(|t| search.pattern(t)).collect(),
+        cell_counts: (0..period).map(|t| search.cell_count_gen(t)).collect(),
+        conflicts: search.conflicts(),
+        time: time.as_secs_f64(),
+    };
+    serde_json::to_string(&result).unwrap()
+}
+
+/// Displays the search configuration, in JSON format if `format` is `Json`,
+/// otherwise in TOML format.
+fn display_config(config: &Config, format: Format) -> String {
+    match format {
+        Format::Json => serde_json::to_string_pretty(config).unwrap() + "\n",
+        _ => toml::to_string(config).unwrap(),
+    }
+}
+
+/// Writes the search problem in DIMACS CNF format to a file.
+fn write_dimacs(config: &Config, path: &str) {
+    let result = config
+        .cnf()
+        .map_err(|e| e.to_string())
+        .and_then(|cnf| fs::write(path, cnf.dimacs()).map_err(|e| e.to_string()));
+    if let Err(e) = result {
+        eprintln!("Unable to write {}: {}", path, e);
+        exit(1);
+    }
+}
+
+/// Checks a proof in a file, and prints the result.
+///
+/// Exits with an error if the proof does not pass the check.
+fn check_proof(config: &Config, path: &str) {
+    let result = fs::read_to_string(path)
+        .map_err(|e| e.to_string())
+        .and_then(|proof| {
+            proof
+                .parse()
+                .and_then(|proof| config.check_proof(&proof))
+                .map_err(|e| e.to_string())
+        });
+    match result {
+        Ok(summary) => {
+            println!(
+                "Proof checked: {} leaves, {} results, depth {}.",
+                summary.leaves, summary.results, summary.depth
+            );
+        }
+        Err(e) => {
+            eprintln!("Proof check failed for {}: {}", path, e);
+            exit(1);
+        }
+    }
+}
+
+/// Displays the whole world in some generation in the given format.
+///
+/// The JSON format contains all generations, so it is only used for
+/// the final results. Here the mixed format is used instead.
+pub(crate) fn display_gen(search: &dyn Search, t: isize, format: Format) -> String {
+    match format {
+        Format::Mixed | Format::Json => search.rle_gen(t),
+        Format::Rle => search.rle_std_gen(t).unwrap_or_else(|_| search.rle_gen(t)),
+        Format::Phases => search
+            .rle_std_phases()
+            .unwrap_or_else(|_| search.rle_gen(t)),
+        Format::History => search.rle_history().unwrap_or_else(|_| search.rle_gen(t)),
+    }
+}
+
+/// Verifies the result on the unbounded plane,
+/// and displays the verification result.
+fn verification(search: &dyn Search) -> String {
+    match search.verify() {
+        Ok(()) => String::from("Verified."),
+        Err(e) => format!("Verification failed: {}", e),
+    }
+}
+
+/// Verifies the result on the unbounded plane,
+/// and prints the verification result to stderr.
+pub(crate) fn print_verification(search: &dyn Search) {
+    eprintln!("{}", verification(search));
+}
+
+/// Prints the pattern read from a SAT solver's model.
+fn print_model(args: Args) {
+    let search = args.search.as_ref();
+    match args.format {
+        Format::Json => println!("{}", display_json(search, args.time)),
+        format => println!("{}", display_gen(search, 0, format)),
+    }
+    if args.verify {
+        print_verification(search);
+    }
+}
+
+/// Displays the outcomes of a sweep as a table.
+fn display_sweep(points: &[SweepPoint]) -> String {
+    let mut table = format!(
+        "{:>6} {:>6} {:>6}  {:<9} {:>12} {:>10}\n",
+        "width", "height", "period", "result", "conflicts", "time"
+    );
+    for point in points {
+        let status = match point.status {
+            SweepStatus::Found => "found",
+            SweepStatus::None => "none",
+            SweepStatus::TimedOut => "timed out",
+            SweepStatus::Invalid(_) => "invalid",
+        };
+        table += &format!(
+            "{:>6} {:>6} {:>6}  {:<9} {:>12} {:>9.2}s",
+            point.width,
+            point.height,
+            point.period,
+            status,
+            point.conflicts,
+            point.time.as_secs_f64()
+        );
+        if let SweepStatus::Invalid(e) = &point.status {
+            table += &format!("  {}", e);
+        }
+        table.push('\n');
+    }
+    table
+}
+
+/// Runs a sweep over the sizes and the periods.
+///
+/// Each result is printed when it is found, and the table of the
+/// outcomes at all points is printed to stderr at the end.
+fn run_sweep(args: Args) {
+    let Args {
+        sweep,
+        verify,
+        format,
+        ..
+    } = args;
+    let points = sweep.unwrap().run(|point, search| {
+        if point.status == SweepStatus::Found {
+            match format {
+                Format::Json => println!("{}", display_json(search, point.time)),
+                _ => println!("{}", display_gen(search, 0, format)),
+            }
+            if verify {
+                print_verification(search);
+            }
+        }
+    });
+    eprint!("{}", display_sweep(&points));
+    if points
+        .iter()
+        .all(|point| point.status != SweepStatus::Found)
+    {
+        eprintln!("Not found.");
+        exit(1);
+    }
+}
+
+/// Displays the search progress in a single line,
+/// followed by the current partial pattern if `pattern` is true.
 ///
-/// If `all` is true, it will print all possible results
+/// `rate` is the number of conflicts per second since the last report.
+fn display_progress(
+    search: &dyn Search,
+    time: Duration,
+    rate: f64,
+    found: u64,
+    pattern: bool,
+) -> String {
+    let mut progress = format!(
+        "Time: {:.2?}  Cells: {}  Confl: {} ({:.0}/s)  Depth: {}  Found: {}\n",
+        time,
+        search.cell_count(),
+        search.conflicts(),
+        rate,
+        search.depth(),
+        found
+    );
+    if pattern {
+        progress += &search.rle_gen(0);
+    }
+    progress
+}
+
+/// Number of steps between two checks of the checkpoint in `search_loop`.
+const CHECK_FREQ: u64 = 100000;
+
+/// Writes the leaves of the proof recorded since the last call to a file.
+fn write_proof(search: &mut dyn Search, file: &mut dyn Write) -> IoResult<()> {
+    if let Some(proof) = search.take_proof() {
+        write!(file, "{}", proof)?;
+    }
+    Ok(())
+}
+
+/// Number of steps between two checks in `search_loop` when the progress
+/// is printed, so that a slow search does not delay the reports.
+const PROGRESS_CHECK_FREQ: u64 = 1000;
+
+/// Runs the search without TUI, writing the results to `output`,
+/// and the verification results to `log`.
+///
+/// If `args.all` is true, it will write all possible results
 /// instead of only the first one.
-fn run_search(mut search: Box<dyn Search>, all: bool) {
-    if all {
-        let mut found = false;
-        loop {
-            match search.search(None) {
-                Status::Found => {
-                    found = true;
-                    println!("{}", search.rle_gen(0))
+///
+/// If `args.verify` is true, each result will be verified on the unbounded plane.
+///
+/// In the JSON format, each result is written as a JSON object in a single line.
+///
+/// If `args.checkpoint` is set, the search status is saved periodically,
+/// when the search ends, and when the program is interrupted.
+///
+/// If `args.progress` is set, the search progress is printed to stderr periodically.
+///
+/// If `args.proof` is set, the leaves of the proof are written to that file
+/// as the search goes on. The proof is complete when the search ends
+/// with `Status::None`.
+///
+/// If no results are found, `Not found.` and the deepest partial result
+/// are written to `log`, followed by the explanation of the conflict before
+/// any decision if `explain` is set. If the search ends after some results
+/// are found with `reduce_max`, the proven optimum is written to `log`.
+///
+/// Returns the final status and the number of results found so far,
+/// or `None` if the program is interrupted.
+pub(crate) fn search_loop(
+    args: Args,
+    output: &mut dyn Write,
+    log: &mut dyn Write,
+) -> IoResult<Option<(Status, u64)>> {
+    let Args {
+        mut search,
+        all,
+        verify,
+        format,
+        mut checkpoint,
+        progress,
+        progress_pattern,
+        proof,
+        mut found,
+        time,
+        ..
+    } = args;
+    let mut proof = match proof {
+        Some(path) => Some(BufWriter::new(File::create(&path).map_err(|e| {
+            IoError::new(e.kind(), format!("Unable to write {}: {}", path, e))
+        })?)),
+        None => None,
+    };
+    let start_time = Instant::now();
+    let mut last_report = (Instant::now(), search.conflicts());
+    let elapsed = || time + start_time.elapsed();
+    let display = |search: &dyn Search| match format {
+        Format::Json => display_json(search, elapsed()),
+        _ => display_gen(search, 0, format),
+    };
+    let check_freq = if progress.is_some() {
+        PROGRESS_CHECK_FREQ
+    } else {
+        CHECK_FREQ
+    };
+    let status = loop {
+        match search.search(Some(check_freq)) {
+            Status::Found => {
+                found += 1;
+                writeln!(output, "{}", display(search.as_ref()))?;
+                if verify {
+                    writeln!(log, "{}", verification(search.as_ref()))?;
+                }
+                if !all {
+                    break Status::Found;
                 }
-                Status::None => break,
-                _ => (),
+            }
+            Status::None => break Status::None,
+            _ => (),
+        }
+        if let Some(proof) = proof.as_mut() {
+            write_proof(search.as_mut(), proof)?;
+        }
+        if let Some(interval) = progress {
+            let (last_time, last_conflicts) = last_report;
+            if last_time.elapsed() >= interval {
+                let conflicts = search.conflicts();
+                let rate = (conflicts - last_conflicts) as f64 / last_time.elapsed().as_secs_f64();
+                eprint!(
+                    "{}",
+                    display_progress(search.as_ref(), elapsed(), rate, found, progress_pattern)
+                );
+                last_report = (Instant::now(), conflicts);
             }
         }
-        if !found {
-            eprintln!("Not found.");
+        if let Some(checkpoint) = checkpoint.as_mut() {
+            if checkpoint.interrupted() {
+                checkpoint.save_or_warn(search.as_ref(), found, elapsed(), false);
+                return Ok(None);
+            } else if checkpoint.due() {
+                checkpoint.save_or_warn(search.as_ref(), found, elapsed(), false);
+            }
+        }
+    };
+    if let Some(proof) = proof.as_mut() {
+        write_proof(search.as_mut(), proof)?;
+        proof.flush()?;
+    }
+    if let Some(checkpoint) = checkpoint.as_mut() {
+        let finished = status == Status::None;
+        checkpoint.save_or_warn(search.as_ref(), found, elapsed(), finished);
+    }
+    if status == Status::None && (!all || found == 0) {
+        writeln!(log, "Not found.")?;
+        if let Some(deepest) = search.rle_deepest_gen(0) {
+            writeln!(log, "The deepest partial result:\n{}", deepest)?;
+        }
+        if let Some(conflict) = search.explain_root_conflict() {
+            writeln!(log, "The conflict before any decision involves:")?;
+            for deduction in conflict {
+                writeln!(log, "{}", deduction)?;
+            }
+        }
+    }
+    if status == Status::None {
+        if let Some(optimum) = search.optimum() {
+            writeln!(log, "The optimum is {}.", optimum)?;
+        }
+    }
+    Ok(Some((status, found)))
+}
+
+/// Runs the search without TUI, printing the results to stdout.
+///
+/// See `search_loop` for details.
+fn run_search(args: Args) {
+    let all = args.all;
+    match search_loop(args, &mut stdout(), &mut stderr()) {
+        Ok(Some((Status::None, found))) if !all || found == 0 => exit(1),
+        Ok(Some(_)) => (),
+        Ok(None) => {
+            eprintln!("Interrupted.");
+            exit(130);
+        }
+        Err(e) => {
+            eprintln!("{}", e);
             exit(1);
         }
-    } else if let Status::Found = search.search(None) {
-        println!("{}", search.rle_gen(0));
-    } else {
-        eprintln!("Not found.");
-        exit(1);
     }
 }
 
 #[cfg(feature = "tui")]
 fn main() {
+    if matches!(env::args_os().nth(1), Some(arg) if arg == "batch") {
+        let batch = Batch::parse(env::args_os().skip(1)).unwrap_or_else(|e| e.exit());
+        return batch.run();
+    }
     let args = Args::parse().unwrap_or_else(|e| e.exit());
-    let search = args.search;
-    if args.no_tui {
-        run_search(search, args.all);
+    if args.print_config {
+        print!("{}", display_config(args.search.config(), args.format));
+    } else if let Some(path) = &args.dimacs {
+        write_dimacs(args.search.config(), path);
+    } else if args.model {
+        print_model(args);
+    } else if let Some(path) = &args.check_proof {
+        check_proof(args.search.config(), path);
+    } else if args.sweep.is_some() {
+        run_sweep(args);
+    } else if args.no_tui || args.proof.is_some() {
+        run_search(args);
     } else {
-        tui::tui(search, args.reset).unwrap();
+        tui::tui(args).unwrap();
     }
 }
 
 #[cfg(not(feature = "tui"))]
 fn main() {
+    if matches!(env::args_os().nth(1), Some(arg) if arg == "batch") {
+        let batch = Batch::parse(env::args_os().skip(1)).unwrap_or_else(|e| e.exit());
+        return batch.run();
+    }
     let args = Args::parse().unwrap_or_else(|e| e.exit());
-    run_search(args.search, args.all);
+    if args.print_config {
+        print!("{}", display_config(args.search.config(), args.format));
+    } else if let Some(path) = &args.dimacs {
+        write_dimacs(args.search.config(), path);
+    } else if args.model {
+        print_model(args);
+    } else if let Some(path) = &args.check_proof {
+        check_proof(args.search.config(), path);
+    } else if args.sweep.is_some() {
+        run_sweep(args);
+    } else {
+        run_search(args);
+    }
 }
@@ -1,5 +1,10 @@
 #![allow(clippy::borrowed_box)]
 
+use crate::{
+    args::{Args, Format},
+    checkpoint::Checkpointer,
+    display_gen, display_json, print_verification,
+};
 use async_std::task;
 use crossterm::{
     cursor::{Hide, MoveTo, MoveToNextLine, Show},
@@ -20,6 +25,9 @@ const VIEW_FREQ: u64 = 5000;
 #[cfg(not(debug_assertions))]
 const VIEW_FREQ: u64 = 100000;
 
+/// Time between two checks of interruption when the search is paused.
+const INTERRUPT_CHECK: Duration = Duration::from_millis(200);
+
 /// A macro to generate constant key events.
 macro_rules! const_key {
     ($($name:ident => $key:expr),* $(,)?) => {
@@ -40,26 +48,34 @@ struct App<'a, W: Write> {
     start_time: Option<Instant>,
     timing: Duration,
     reset: bool,
+    format: Format,
+    heatmap: bool,
+    /// The position of the cell being explained, if any.
+    cursor: Option<(isize, isize)>,
+    found: u64,
+    checkpoint: Option<Checkpointer>,
     output: &'a mut W,
     term_size: (u16, u16),
-    world_size: (isize, isize),
 }
 
 impl<'a, W: Write> App<'a, W> {
-    fn new(search: Box<dyn Search>, reset: bool, output: &'a mut W) -> Self {
-        let period = search.config().period;
-        let world_size = (search.config().width, search.config().height);
+    fn new(args: Args, output: &'a mut W) -> Self {
+        let period = args.search.config().period;
         App {
             gen: 0,
             period,
-            search,
+            search: args.search,
             status: Status::Paused,
             start_time: None,
-            timing: Duration::default(),
-            reset,
+            timing: args.time,
+            reset: args.reset,
+            format: args.format,
+            heatmap: false,
+            cursor: None,
+            found: args.found,
+            checkpoint: args.checkpoint,
             output,
             term_size: (80, 24),
-            world_size,
         }
     }
 
@@ -68,8 +84,6 @@ impl<'a, W: Write> App<'a, W> {
         self.output.execute(EnterAlternateScreen)?.execute(Hide)?;
         terminal::enable_raw_mode()?;
         self.term_size = terminal::size()?;
-        self.world_size.0 = self.world_size.0.min(self.term_size.0 as isize - 1);
-        self.world_size.1 = self.world_size.1.min(self.term_size.1 as isize - 3);
         self.update()
     }
 
@@ -92,10 +106,11 @@ impl<'a, W: Write> App<'a, W> {
             .queue(Print(format!(
                 "{:1$}",
                 format!(
-                    "Gen: {}  Cells: {}  Confl: {}{}",
+                    "Gen: {}  Cells: {}  Confl: {}  Found: {}{}",
                     self.gen,
                     self.search.cell_count_gen(self.gen),
                     self.search.conflicts(),
+                    self.found,
                     if self.status == Status::Searching {
                         String::new()
                     } else {
@@ -107,44 +122,152 @@ impl<'a, W: Write> App<'a, W> {
         Ok(())
     }
 
+    /// Whether the search has failed, and the deepest partial result
+    /// is shown instead of the world.
+    fn show_deepest(&self) -> bool {
+        self.status == Status::None && self.search.deepest_pattern(self.gen).is_some()
+    }
+
+    /// Displays the current generation in the current format,
+    /// or the deepest partial result in the mixed format if `show_deepest`.
+    fn display(&self) -> String {
+        if self.show_deepest() {
+            self.search.rle_deepest_gen(self.gen).unwrap()
+        } else {
+            display_gen(self.search.as_ref(), self.gen, self.format)
+        }
+    }
+
     /// Updates the main part of the screen.
     /// Prints the pattern in a mix of
     /// [Plaintext](https://conwaylife.com/wiki/Plaintext) and
     /// [RLE](https://conwaylife.com/wiki/Rle) format.
+    ///
+    /// If the format is `Rle` and all cells in the generation are known,
+    /// prints the pattern in the standard RLE format instead.
+    ///
+    /// After the search fails, prints the deepest partial result.
+    ///
+    /// If `heatmap` is set, prints the heatmap of conflicts instead.
+    ///
+    /// If `cursor` is set, prints the explanation of the cell instead.
     fn update_main(&mut self) -> CrosstermResult<()> {
-        self.output
-            .queue(MoveTo(0, 1))?
-            .queue(ResetColor)?
-            .queue(Print(format!(
-                "x = {}, y = {}, rule = {}",
-                self.search.config().width,
-                self.search.config().height,
-                self.search.config().rule_string
-            )))?
-            .queue(MoveToNextLine(1))?;
-        for y in 0..self.world_size.1 {
-            let mut line = String::new();
-            for x in 0..self.world_size.0 {
-                let state = self.search.get_cell_state((x, y, self.gen)).unwrap();
-                match state {
-                    Some(DEAD) => line.push('.'),
-                    Some(ALIVE) => {
-                        if self.search.is_gen_rule() {
-                            line.push('A')
-                        } else {
-                            line.push('o')
-                        }
-                    }
-                    Some(State(i)) => line.push((b'A' + i as u8 - 1) as char),
-                    _ => line.push('?'),
+        if let Some(cursor) = self.cursor {
+            return self.update_explain(cursor);
+        }
+        if self.heatmap {
+            if let Some(map) = self.search.conflict_map(self.gen) {
+                return self.update_heatmap(map);
+            }
+        }
+        let text = self.display();
+        let height = self.term_size.1 as usize - 2;
+        let width = self.term_size.0 as usize;
+        self.output.queue(MoveTo(0, 1))?.queue(ResetColor)?;
+        for line in text.lines().take(height) {
+            let line = line.get(..width).unwrap_or(line);
+            self.output
+                .queue(Print(format!("{:1$}", line, width)))?
+                .queue(MoveToNextLine(1))?;
+        }
+        Ok(())
+    }
+
+    /// Updates the main part of the screen with the heatmap of conflicts.
+    ///
+    /// Prints the current generation in the mixed format,
+    /// with the background of each cell colored by the number of
+    /// conflicts found at it, relative to the most of all cells.
+    fn update_heatmap(&mut self, map: Vec<Vec<u64>>) -> CrosstermResult<()> {
+        const COLORS: [Color; 4] = [Color::Blue, Color::Green, Color::Yellow, Color::Red];
+
+        let text = self.search.rle_gen(self.gen);
+        let height = self.term_size.1 as usize - 2;
+        let width = self.term_size.0 as usize;
+        let max = map.iter().flatten().copied().max().unwrap_or(0);
+        self.output.queue(MoveTo(0, 1))?;
+        for (y, line) in text.lines().take(height).enumerate() {
+            let line = line.get(..width).unwrap_or(line);
+            for (x, c) in line.chars().enumerate() {
+                // The first line is the header.
+                let count = y
+                    .checked_sub(1)
+                    .and_then(|y| map.get(y))
+                    .and_then(|row| row.get(x))
+                    .copied()
+                    .unwrap_or(0);
+                if count == 0 {
+                    self.output.queue(ResetColor)?;
+                } else {
+                    let level = ((count - 1) * COLORS.len() as u64 / max) as usize;
+                    self.output
+                        .queue(SetBackgroundColor(COLORS[level]))?
+                        .queue(SetForegroundColor(Color::Black))?;
+                }
+                self.output.queue(Print(c))?;
+            }
+            self.output
+                .queue(ResetColor)?
+                .queue(Print(format!("{:1$}", "", width - line.len())))?
+                .queue(MoveToNextLine(1))?;
+        }
+        Ok(())
+    }
+
+    /// Updates the main part of the screen with the explanation of a cell.
+    ///
+    /// Prints the current generation in the mixed format, with the cell
+    /// highlighted, followed by the chain of deductions that leads to
+    /// its state. If the chain does not fit on the screen,
+    /// only its end is printed.
+    fn update_explain(&mut self, (cx, cy): (isize, isize)) -> CrosstermResult<()> {
+        let height = self.term_size.1 as usize - 2;
+        let width = self.term_size.0 as usize;
+        let mut lines = self
+            .search
+            .rle_gen(self.gen)
+            .lines()
+            .map(String::from)
+            .collect::<Vec<_>>();
+        lines.push(String::new());
+        match self.search.explain((cx, cy, self.gen)) {
+            Ok(chain) => {
+                let state = match chain.last().unwrap().state {
+                    DEAD => String::from("dead"),
+                    ALIVE => String::from("alive"),
+                    State(i) => format!("in state {}", i),
                 };
+                lines.push(format!("Why is the cell at ({}, {}) {}?", cx, cy, state));
+                let room = height.saturating_sub(lines.len()).max(2);
+                if chain.len() > room {
+                    let skip = chain.len() - room + 1;
+                    lines.push(format!("({} earlier deductions are not shown.)", skip));
+                    lines.extend(chain[skip..].iter().map(ToString::to_string));
+                } else {
+                    lines.extend(chain.iter().map(ToString::to_string));
+                }
+            }
+            Err(e) => lines.push(e.to_string()),
+        }
+        self.output.queue(MoveTo(0, 1))?;
+        for y in 0..height {
+            let line = lines.get(y).map_or("", String::as_str);
+            let line = line.get(..width).unwrap_or(line);
+            for (x, c) in line.chars().enumerate() {
+                // The first line is the header.
+                if y as isize == cy + 1 && x as isize == cx {
+                    self.output
+                        .queue(SetBackgroundColor(Color::White))?
+                        .queue(SetForegroundColor(Color::Black))?;
+                } else {
+                    self.output.queue(ResetColor)?;
+                }
+                self.output.queue(Print(c))?;
             }
-            if y == self.search.config().height - 1 {
-                line.push('!')
-            } else {
-                line.push('$')
-            };
-            self.output.queue(Print(line))?.queue(MoveToNextLine(1))?;
+            self.output
+                .queue(ResetColor)?
+                .queue(Print(format!("{:1$}", "", width - line.chars().count())))?
+                .queue(MoveToNextLine(1))?;
         }
         Ok(())
     }
@@ -154,24 +277,36 @@ impl<'a, W: Write> App<'a, W> {
         const INITIAL: &str = "Press [space] to start.";
         const FOUND: &str = "Found a result. Press [q] to quit or [space] to search for the next.";
         const NONE: &str = "No more result. Press [q] to quit.";
+        const DEEPEST: &str =
+            "No more result. Showing the deepest partial result. Press [q] to quit.";
         const SEARCHING: &str = "Searching... Press [space] to pause.";
         const PAUSED: &str = "Paused. Press [space] to resume.";
+        const EXPLAIN: &str = "Move the cursor with the arrow keys. Press [e] to leave.";
 
+        let show_deepest = self.show_deepest();
+        let footer = match self.status {
+            Status::Initial => INITIAL,
+            Status::Found => FOUND,
+            Status::None if show_deepest => DEEPEST,
+            Status::None => NONE,
+            Status::Searching => SEARCHING,
+            Status::Paused => PAUSED,
+        };
+        let footer = match self.search.optimum() {
+            Some(optimum) if self.status == Status::None => {
+                footer.replacen(". ", &format!(". The optimum is {}. ", optimum), 1)
+            }
+            _ => footer.to_string(),
+        };
+        let footer = match self.cursor {
+            Some(_) => String::from(EXPLAIN),
+            None => footer,
+        };
         self.output
             .queue(MoveTo(0, self.term_size.1 - 1))?
             .queue(SetBackgroundColor(Color::White))?
             .queue(SetForegroundColor(Color::Black))?
-            .queue(Print(format!(
-                "{:1$}",
-                match self.status {
-                    Status::Initial => INITIAL,
-                    Status::Found => FOUND,
-                    Status::None => NONE,
-                    Status::Searching => SEARCHING,
-                    Status::Paused => PAUSED,
-                },
-                self.term_size.0 as usize
-            )))?;
+            .queue(Print(format!("{:1$}", footer, self.term_size.0 as usize)))?;
         Ok(())
     }
 
@@ -185,11 +320,29 @@ impl<'a, W: Write> App<'a, W> {
     }
 
     /// Pauses.
+    ///
+    /// Also writes a checkpoint, so that nothing is lost if the program
+    /// is killed while paused.
     fn pause(&mut self) {
         self.status = Status::Paused;
         if let Some(instant) = self.start_time.take() {
             self.timing += instant.elapsed();
         }
+        self.save_checkpoint();
+    }
+
+    /// Writes a checkpoint if `--checkpoint` is set.
+    fn save_checkpoint(&mut self) {
+        let timing = self.timing + self.start_time.map_or(Duration::default(), |t| t.elapsed());
+        let finished = self.status == Status::None;
+        if let Some(checkpoint) = self.checkpoint.as_mut() {
+            checkpoint.save_or_warn(self.search.as_ref(), self.found, timing, finished);
+        }
+    }
+
+    /// Whether the program is interrupted by SIGTERM or SIGINT.
+    fn interrupted(&self) -> bool {
+        matches!(&self.checkpoint, Some(checkpoint) if checkpoint.interrupted())
     }
 
     /// Starts or resumes.
@@ -201,12 +354,20 @@ impl<'a, W: Write> App<'a, W> {
     /// Searches for one step.
     async fn step(&mut self) {
         match self.search.search(Some(VIEW_FREQ)) {
-            Status::Searching => (),
+            Status::Searching => {
+                if matches!(&self.checkpoint, Some(c) if c.due()) {
+                    self.save_checkpoint();
+                }
+            }
             s => {
+                if s == Status::Found {
+                    self.found += 1;
+                }
                 self.status = s;
                 if let Some(instant) = self.start_time.take() {
                     self.timing += instant.elapsed();
                 }
+                self.save_checkpoint();
                 if self.reset {
                     self.start_time = None;
                     self.timing = Duration::default();
@@ -252,9 +413,29 @@ impl<'a, W: Write> App<'a, W> {
             KEY_PAGEDOWN => KeyCode::PageDown,
             KEY_SPACE => KeyCode::Char(' '),
             KEY_ENTER => KeyCode::Enter,
+            KEY_F => KeyCode::Char('f'),
+            KEY_H => KeyCode::Char('h'),
+            KEY_E => KeyCode::Char('e'),
+            KEY_UP => KeyCode::Up,
+            KEY_DOWN => KeyCode::Down,
+            KEY_LEFT => KeyCode::Left,
+            KEY_RIGHT => KeyCode::Right,
         };
 
         match event {
+            Some(Event::Key(KeyEvent {
+                code: KeyCode::Char('c'),
+                modifiers: KeyModifiers::CONTROL,
+            })) => {
+                // Raw mode turns Ctrl-C into a key event instead of SIGINT.
+                if is_searching {
+                    self.pause();
+                } else {
+                    self.save_checkpoint();
+                }
+                self.update()?;
+                return Ok(true);
+            }
             Some(KEY_Q) | Some(KEY_ESC) => {
                 if is_searching {
                     self.pause();
@@ -282,14 +463,66 @@ impl<'a, W: Write> App<'a, W> {
                 if is_searching {
                     self.pause();
                 } else {
+                    if self.cursor.take().is_some() {
+                        self.output
+                            .queue(ResetColor)?
+                            .queue(Clear(ClearType::All))?;
+                    }
                     self.start();
                 }
                 self.update()?;
             }
+            Some(KEY_E) => {
+                if is_searching {
+                    self.pause();
+                }
+                self.cursor = match self.cursor {
+                    Some(_) => None,
+                    None => Some((0, 0)),
+                };
+                self.output
+                    .queue(ResetColor)?
+                    .queue(Clear(ClearType::All))?;
+                self.update()?;
+            }
+            Some(KEY_UP) | Some(KEY_DOWN) | Some(KEY_LEFT) | Some(KEY_RIGHT)
+                if self.cursor.is_some() =>
+            {
+                let (x, y) = self.cursor.unwrap();
+                let (dx, dy) = match event {
+                    Some(KEY_UP) => (0, -1),
+                    Some(KEY_DOWN) => (0, 1),
+                    Some(KEY_LEFT) => (-1, 0),
+                    _ => (1, 0),
+                };
+                let config = self.search.config();
+                self.cursor = Some((
+                    (x + dx).clamp(0, config.width - 1),
+                    (y + dy).clamp(0, config.height - 1),
+                ));
+                self.update()?;
+            }
+            Some(KEY_F) => {
+                self.format = match self.format {
+                    Format::Mixed | Format::Json => Format::Rle,
+                    Format::Rle => Format::Phases,
+                    Format::Phases if self.search.config().is_life() => Format::History,
+                    Format::Phases | Format::History => Format::Mixed,
+                };
+                self.output
+                    .queue(ResetColor)?
+                    .queue(Clear(ClearType::All))?;
+                self.update()?;
+            }
+            Some(KEY_H) => {
+                self.heatmap = !self.heatmap;
+                self.output
+                    .queue(ResetColor)?
+                    .queue(Clear(ClearType::All))?;
+                self.update()?;
+            }
             Some(Event::Resize(width, height)) => {
                 self.term_size = (width, height);
-                self.world_size.0 = self.world_size.0.min(self.term_size.0 as isize - 1);
-                self.world_size.1 = self.world_size.1.min(self.term_size.1 as isize - 3);
                 self.output
                     .queue(ResetColor)?
                     .queue(Clear(ClearType::All))?;
@@ -311,6 +544,14 @@ impl<'a, W: Write> App<'a, W> {
     /// The main loop.
     async fn main_loop(&mut self, reader: &mut EventStream) -> CrosstermResult<()> {
         loop {
+            if self.interrupted() {
+                if let Status::Searching = self.status {
+                    self.pause();
+                } else {
+                    self.save_checkpoint();
+                }
+                break;
+            }
             if let Status::Searching = self.status {
                 select! {
                     event = reader.try_next().fuse() => {
@@ -322,6 +563,16 @@ impl<'a, W: Write> App<'a, W> {
                         self.update()?;
                     },
                 };
+            } else if self.checkpoint.is_some() {
+                // Wakes up regularly to check whether the program is interrupted.
+                select! {
+                    event = reader.try_next().fuse() => {
+                        if self.handle(event?, reader, false).await? {
+                            break;
+                        }
+                    },
+                    _ = task::sleep(INTERRUPT_CHECK).fuse() => (),
+                };
             } else if self.handle(reader.try_next().await?, reader, false).await? {
                 break;
             }
@@ -332,14 +583,39 @@ impl<'a, W: Write> App<'a, W> {
 
 /// Runs the search with a TUI.
 ///
-/// If `reset` is true, the time will be reset when starting a new search.
-pub(crate) fn tui(search: Box<dyn Search>, reset: bool) -> CrosstermResult<()> {
+/// If `args.reset` is true, the time will be reset when starting a new search.
+///
+/// If `args.verify` is true and a result is found, the displayed result
+/// will be verified on the unbounded plane after quitting.
+///
+/// The `args.format` is the initial output format. It can be switched
+/// by pressing [f]. In the JSON format, the result is displayed in
+/// the mixed format, and printed as JSON after quitting.
+///
+/// Pressing [e] shows a cursor, and explains why the cell under it
+/// has its state, if `explain` is set in the configuration.
+///
+/// After the search fails, the deepest partial result is displayed
+/// and printed instead.
+///
+/// If `args.checkpoint` is set, the search status is saved periodically,
+/// when the search is paused or ends, and when quitting.
+pub(crate) fn tui(args: Args) -> CrosstermResult<()> {
     let mut stdout = stdout();
     let mut reader = EventStream::new();
-    let mut app = App::new(search, reset, &mut stdout);
+    let json = args.format == Format::Json;
+    let verify = args.verify;
+    let mut app = App::new(args, &mut stdout);
     app.init()?;
     task::block_on(app.main_loop(&mut reader))?;
     app.quit()?;
-    println!("{}", app.search.rle_gen(app.gen));
+    if json {
+        println!("{}", display_json(app.search.as_ref(), app.timing));
+    } else {
+        println!("{}", app.display());
+    }
+    if verify && app.status == Status::Found {
+        print_verification(app.search.as_ref());
+    }
     Ok(())
 }
@@ -0,0 +1,109 @@
+use serde_json::Value;
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::{Command, Output},
+};
+
+/// Creates an empty directory for a test.
+fn test_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("rlifesrc-test-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn run_batch(jobs: &Path, output: &Path) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_rlifesrc"))
+        .arg("batch")
+        .arg(jobs)
+        .arg("--output")
+        .arg(output)
+        .output()
+        .unwrap()
+}
+
+fn read_status(output: &Path, index: usize) -> Value {
+    let path = output.join(format!("job-{}.status", index));
+    serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+}
+
+#[test]
+fn batch() {
+    let dir = test_dir("batch");
+    let jobs = dir.join("jobs.txt");
+    let output = dir.join("out");
+    fs::write(
+        &jobs,
+        "# Comments and empty lines are skipped.\n\
+         \n\
+         4 4 3\n\
+         2 2 1 --max abc\n\
+         3 3 1 --resume save.json\n\
+         \x20 3 3 1 \n\
+         missing.toml\n\
+         3 3 1 -r B36/S23 --format history\n",
+    )
+    .unwrap();
+
+    let result = run_batch(&jobs, &output);
+    assert!(result.status.success());
+
+    let status = read_status(&output, 1);
+    assert_eq!(status["line"], "4 4 3");
+    assert_eq!(status["result"], "none");
+    assert_eq!(status["found"], 0);
+
+    // A failing job does not stop the others.
+    let status = read_status(&output, 2);
+    assert_eq!(status["result"], "error");
+    assert!(status["error"].as_str().unwrap().contains("--max"));
+    let status = read_status(&output, 3);
+    assert_eq!(status["result"], "error");
+    assert!(status["error"]
+        .as_str()
+        .unwrap()
+        .contains("'--resume' is not supported in batch jobs"));
+
+    let status = read_status(&output, 4);
+    assert_eq!(status["line"], "3 3 1");
+    assert_eq!(status["result"], "found");
+    assert_eq!(status["found"], 1);
+    let results = fs::read_to_string(output.join("job-4.txt")).unwrap();
+    assert!(results.starts_with("x = 3, y = 3"));
+    assert!(output.join("job-4.checkpoint").exists());
+
+    // A line with only a file name is a configuration file.
+    let status = read_status(&output, 5);
+    assert_eq!(status["result"], "error");
+    assert!(status["error"]
+        .as_str()
+        .unwrap()
+        .starts_with("Unable to read missing.toml"));
+    let status = read_status(&output, 6);
+    assert_eq!(status["result"], "error");
+    assert!(status["error"]
+        .as_str()
+        .unwrap()
+        .contains("only available for Conway's Game of Life"));
+
+    // Running again skips the ended jobs, and runs the failed ones again.
+    let result = run_batch(&jobs, &output);
+    assert!(result.status.success());
+    let log = String::from_utf8(result.stderr).unwrap();
+    assert!(!log.contains("Job 1: started"));
+    assert!(log.contains("Job 2: started"));
+    assert!(log.contains("Job 3: started"));
+    assert!(!log.contains("Job 4: started"));
+    assert_eq!(
+        fs::read_to_string(output.join("job-4.txt")).unwrap(),
+        results
+    );
+
+    // The status files must belong to the same jobs.
+    fs::write(&jobs, "4 4 4\n").unwrap();
+    let result = run_batch(&jobs, &output);
+    assert!(!result.status.success());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
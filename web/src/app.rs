@@ -1,7 +1,7 @@
 use crate::{
     help::Help,
     settings::Settings,
-    worker::{Request, Response, Worker},
+    worker::{Format, Request, Response, Worker},
     world::World,
 };
 use rlifesrc_lib::{Config, Status};
@@ -11,6 +11,7 @@ use yew::{
     events::MouseWheelEvent,
     format::Json,
     html,
+    html::ChangeData,
     services::{storage::Area, DialogService, IntervalService, StorageService, Task},
     Bridge, Bridged, Component, ComponentLink, Html, ShouldRender,
 };
@@ -41,7 +42,17 @@ pub struct App {
     gen: isize,
     cells: usize,
     world: String,
+    partial: bool,
+    format: Format,
     period: isize,
+    /// Whether to show the heatmap of conflicts.
+    heatmap: bool,
+    /// Number of conflicts found at each cell, from the worker.
+    conflicts: Option<Vec<Vec<u64>>>,
+    /// The clicked cell, whose state is explained.
+    selected: Option<(isize, isize)>,
+    /// The explanation of the clicked cell, from the worker.
+    explanation: Option<Result<Vec<String>, String>>,
     worker: Box<dyn Bridge<Worker>>,
     storage: StorageService,
     interval: IntervalService,
@@ -52,6 +63,9 @@ pub enum Msg {
     Tick,
     IncGen,
     DecGen,
+    SetFormat(Format),
+    SetHeatmap,
+    Explain((isize, isize)),
     Start,
     Pause,
     Reset,
@@ -74,6 +88,29 @@ impl App {
     fn stop_job(&mut self) {
         self.job.take();
     }
+
+    /// Asks the worker to explain the clicked cell in the displayed generation.
+    fn explain(&mut self) {
+        self.explanation = None;
+        if let Some((x, y)) = self.selected {
+            self.worker.send(Request::Explain((x, y, self.gen)));
+        }
+    }
+
+    /// Switches to the mixed format if the LifeHistory format is chosen
+    /// but the rule is not Conway's Game of Life.
+    fn check_format(&mut self) {
+        if self.format == Format::History && !self.config.is_life() {
+            self.format = Format::Mixed;
+            self.worker.send(Request::SetFormat(self.format));
+        }
+    }
+
+    /// Forgets the clicked cell, whose explanation is no longer valid.
+    fn deselect(&mut self) {
+        self.selected = None;
+        self.explanation = None;
+    }
 }
 
 impl Component for App {
@@ -97,7 +134,13 @@ impl Component for App {
             gen: 0,
             cells: 0,
             world,
+            partial: false,
+            format: Format::Mixed,
             period,
+            heatmap: false,
+            conflicts: None,
+            selected: None,
+            explanation: None,
             worker,
             storage,
             interval,
@@ -117,6 +160,7 @@ impl Component for App {
                 }
                 self.gen += 1;
                 self.worker.send(Request::DisplayGen(self.gen));
+                self.explain();
             }
             Msg::DecGen => {
                 if self.gen <= 0 {
@@ -124,18 +168,34 @@ impl Component for App {
                 }
                 self.gen -= 1;
                 self.worker.send(Request::DisplayGen(self.gen));
+                self.explain();
+            }
+            Msg::SetFormat(format) => {
+                self.format = format;
+                self.worker.send(Request::SetFormat(format));
+                self.worker.send(Request::DisplayGen(self.gen));
+            }
+            Msg::SetHeatmap => {
+                self.heatmap ^= true;
+                self.conflicts = None;
+                self.worker.send(Request::SetHeatmap(self.heatmap));
+                self.worker.send(Request::DisplayGen(self.gen));
+            }
+            Msg::Explain(coord) => {
+                self.selected = Some(coord);
+                self.explain();
             }
             Msg::Start => {
+                self.deselect();
                 self.worker.send(Request::Start);
-                return false;
             }
             Msg::Pause => {
                 self.worker.send(Request::Pause);
                 return false;
             }
             Msg::Reset => {
+                self.deselect();
                 self.worker.send(Request::SetWorld(self.config.clone()));
-                return false;
             }
             Msg::Store => {
                 self.worker.send(Request::Store);
@@ -143,23 +203,27 @@ impl Component for App {
             }
             Msg::Restore => {
                 if let Json(Ok(world_ser)) = self.storage.restore(KEY) {
+                    self.deselect();
                     self.worker.send(Request::Restore(world_ser));
                 }
-                return false;
             }
             Msg::Apply(config) => {
                 self.config = config;
                 self.gen = 0;
                 self.period = self.config.period;
+                self.deselect();
+                self.check_format();
                 self.worker.send(Request::SetWorld(self.config.clone()));
             }
             Msg::DataReceived(response) => match response {
-                Response::UpdateWorld((world, cells)) => {
+                Response::UpdateWorld((world, cells, partial)) => {
                     self.world = world;
                     self.cells = cells;
+                    self.partial = partial;
                 }
                 Response::UpdateConfig(config) => {
                     self.config = config;
+                    self.check_format();
                 }
                 Response::UpdateStatus(status) => {
                     let old_status = self.status;
@@ -181,6 +245,13 @@ impl Component for App {
                     self.storage.store(KEY, Json(&world_ser));
                     return false;
                 }
+                Response::UpdateHeatmap(conflicts) => {
+                    self.conflicts = conflicts;
+                    return false;
+                }
+                Response::Explain(explanation) => {
+                    self.explanation = Some(explanation);
+                }
             },
             Msg::None => return false,
         }
@@ -270,8 +341,13 @@ impl App {
                             </ul>
                             <div class="mui-tabs__pane mui--is-active" id="pane-world">
                                 { self.data() }
-                                <World world= &self.world />
+                                <World world= &self.world
+                                    clickable=self.clickable()
+                                    selected=self.selected
+                                    heatmap=self.conflicts.clone()
+                                    onselect=self.link.callback(Msg::Explain) />
                                 { self.buttons() }
+                                { self.explanation() }
                             </div>
                             <div class="mui-tabs__pane" id="pane-settings">
                                 <Settings config=&self.config
@@ -323,11 +399,20 @@ impl App {
                     { ": " }
                     { self.cells }
                 </li>
+                <li>
+                    { self.set_format() }
+                </li>
+                <li>
+                    { self.set_heatmap() }
+                </li>
                 <li>
                     {
                         match self.status {
                             Status::Initial => "",
                             Status::Found => "Found a result.",
+                            Status::None if self.partial => {
+                                "No more result. Showing the deepest partial result."
+                            }
                             Status::None => "No more result.",
                             Status::Searching => "Searching...",
                             Status::Paused => "Paused.",
@@ -338,6 +423,107 @@ impl App {
         }
     }
 
+    /// Whether the cells of the displayed world can be clicked to explain them.
+    ///
+    /// This needs `explain` in the configuration, and the current world
+    /// displayed in the mixed format.
+    fn clickable(&self) -> bool {
+        self.config.explain
+            && (self.format == Format::Mixed || self.conflicts.is_some())
+            && !self.partial
+            && self.status != Status::Searching
+    }
+
+    fn explanation(&self) -> Html {
+        let (x, y) = match self.selected {
+            Some(coord) if self.clickable() => coord,
+            _ => return html! {},
+        };
+        let title = format!(
+            "Why does the cell at ({}, {}) in generation {} have its state?",
+            x, y, self.gen
+        );
+        html! {
+            <div id="explanation">
+                <div class="mui--text-subhead">
+                    { title }
+                </div>
+                {
+                    match &self.explanation {
+                        None => html! {},
+                        Some(Ok(chain)) => html! {
+                            <pre>
+                                { for chain.iter().map(|deduction| html! { <>{ deduction }{ "\n" }</> }) }
+                            </pre>
+                        },
+                        Some(Err(e)) => html! {
+                            <div class="mui--text-body1">{ e }</div>
+                        },
+                    }
+                }
+            </div>
+        }
+    }
+
+    fn set_heatmap(&self) -> Html {
+        html! {
+            <label>
+                <input id="set_heatmap"
+                    type="checkbox"
+                    checked=self.heatmap
+                    onclick=self.link.callback(|_| Msg::SetHeatmap)/>
+                { " " }
+                <abbr title="Color each cell by how many conflicts the search has found at it, \
+                    from blue (few) to red (many). The world is displayed in the mixed format. \
+                    Not available for the SAT solver.">
+                    { "Heatmap" }
+                </abbr>
+            </label>
+        }
+    }
+
+    fn set_format(&self) -> Html {
+        let onchange = self.link.callback(|e: ChangeData| {
+            if let ChangeData::Select(s) = e {
+                match s.raw_value().as_ref() {
+                    "Mixed" => Msg::SetFormat(Format::Mixed),
+                    "RLE" => Msg::SetFormat(Format::Rle),
+                    "Phases" => Msg::SetFormat(Format::Phases),
+                    "History" => Msg::SetFormat(Format::History),
+                    _ => Msg::None,
+                }
+            } else {
+                Msg::None
+            }
+        });
+        html! {
+            <>
+                <abbr title="Output format. Partial results with unknown cells \
+                    are always displayed in the mixed format. \
+                    LifeHistory is only available for Conway's Game of Life.">
+                    { "Format" }
+                </abbr>
+                { ": " }
+                <select id="set_format" onchange=onchange>
+                    <option value="Mixed" selected=self.format == Format::Mixed>
+                        { "Mixed" }
+                    </option>
+                    <option value="RLE" selected=self.format == Format::Rle>
+                        { "RLE" }
+                    </option>
+                    <option value="Phases" selected=self.format == Format::Phases>
+                        { "All phases" }
+                    </option>
+                    <option value="History"
+                        selected=self.format == Format::History
+                        disabled=!self.config.is_life()>
+                        { "LifeHistory" }
+                    </option>
+                </select>
+            </>
+        }
+    }
+
     fn buttons(&self) -> Html {
         html! {
             <div class="buttons">
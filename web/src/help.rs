@@ -45,13 +45,49 @@ and [RLE](https://conwaylife.com/wiki/Rle) format. Specifically:
 
 Currently it cannot properly display Generations rules with more than 25 states.
 
+You can also choose the `RLE` format next to `Cell count`, which displays the result \
+in the standard [RLE](https://conwaylife.com/wiki/Rle) format, so that it can be read by \
+Golly and LifeViewer. Repeated symbols are run-length encoded, lines are at most \
+70 characters long, and the search configuration is written in `#C` comment lines. \
+For Generations rules, dead cells are represented by `.`, and dying cells by `B` to `X`, \
+then `pA` to `pX`, `qA` to `qX`, etc. Partial results with unknown cells are still \
+displayed in the mixed format.
+
+The `All phases` format displays all generations side by side in a single RLE. \
+The `LifeHistory` format displays generation 0 in \
+[LifeHistory](https://conwaylife.com/wiki/LifeHistory) format: state 1 (`A`) marks \
+the rotor cells that are alive in generation 0, state 2 (`B`) the other rotor cells, \
+state 3 (`C`) the stator, and state 6 (`F`) the boundary of the search range. \
+It is only available for Conway's Game of Life. \
+These two formats ignore the displayed generation.
+
 You can click the `+`/`-` sign next to `Generation` to increase/decrease the displayed generation.
 
+Check `Heatmap` to color each cell by how many conflicts the search has found at it, \
+from blue (few) to red (many). The heatmap shows where the search keeps failing, \
+and helps to choose the size, the search order and the known cells. \
+The world is then displayed in the mixed format. \
+It is not available for the SAT solver.
+
+If `Record why each cell has its state` is checked in the settings, you can click a cell \
+of the world in the mixed format, when the search is not running, to see why it has its state: \
+the chain of deductions from the known cells and the decisions of the search to that cell. \
+Each line is a cell, its state, and the decision, the symmetry or the rule that determines it. \
+For a rule, the states of the cell where the rule is applied, its neighbors and its successor \
+at that time are shown row by row, e.g. `.o./.o?/... -> ?`, where `?` marks the unknown cells, \
+including the one that the rule determines.
+
 `Cells` means the number of known living cells in the current generation. \
 For Generations rules, dying cells are not counted.
 
 ## Settings
 
+Click `Import` to import the settings from a TOML or JSON file, and `Export` to export \
+them as a TOML file. The file format is the same as the `--config` option of \
+the command-line tool, so configurations can be moved between the two. Fields that are \
+missing in an imported file take their default values. After importing, click \
+`Apply settings` to apply them.
+
 ### Rule
 
 Rule of the cellular automaton.
@@ -144,6 +180,23 @@ Here \"front\" means the first row or column to be searched, according to the se
 Reduce the `Max cell count` when a result is found.
 
 The new `Max cell count` will be set to the cell count of the current result minus one.
+
+### Non-periodic
+
+Do not return to the first generation after the last generation.
+
+The first generation has no predecessor, and the last generation has no successor, \
+so the search finds evolutions of `Period` generations. \
+The translation, the transformation and the front are ignored.
+
+With known cells in the last generation, e.g., imported from a configuration file \
+printed by `--target FILE --print-config` in the command-line tool, \
+the first generation of a result is a predecessor of them.
+
+### Record why each cell has its state
+
+Records why each cell has its state, so that clicking a cell of the world \
+explains it. It makes the search slower.
 ";
 
 lazy_static! {
@@ -1,7 +1,10 @@
 use rlifesrc_lib::{rules::NtLifeGen, Config, NewState, SearchOrder, Symmetry, Transform};
 use stdweb::js;
 use yew::{
-    html, html::ChangeData, Callback, Component, ComponentLink, Html, Properties, ShouldRender,
+    html,
+    html::ChangeData,
+    services::reader::{File, FileData, ReaderService, ReaderTask},
+    Callback, Component, ComponentLink, Html, Properties, ShouldRender,
 };
 
 pub struct Settings {
@@ -9,6 +12,8 @@ pub struct Settings {
     callback: Callback<Config>,
     config: Config,
     rule_is_valid: bool,
+    reader: ReaderService,
+    task: Option<ReaderTask>,
 }
 
 #[derive(Clone, Properties)]
@@ -32,9 +37,26 @@ pub enum Msg {
     SetMax(Option<usize>),
     SetFront,
     SetReduce,
+    SetPeriodic,
+    SetExplain,
+    Import(File),
+    Loaded(FileData),
+    Export,
     None,
 }
 
+/// Parses a configuration file in the same format as the command-line tool.
+///
+/// Files with the extension `.json` are read as JSON, other files as TOML.
+fn parse_config(file: &FileData) -> Result<Config, String> {
+    let input = String::from_utf8_lossy(&file.content);
+    if file.name.ends_with(".json") {
+        serde_json::from_str(&input).map_err(|e| e.to_string())
+    } else {
+        toml::from_str(&input).map_err(|e| e.to_string())
+    }
+}
+
 impl Component for Settings {
     type Message = Msg;
     type Properties = Props;
@@ -46,6 +68,8 @@ impl Component for Settings {
             callback: props.callback,
             config: props.config,
             rule_is_valid,
+            reader: ReaderService::new(),
+            task: None,
         }
     }
 
@@ -97,6 +121,45 @@ impl Component for Settings {
             Msg::SetReduce => {
                 self.config.reduce_max ^= true;
             }
+            Msg::SetPeriodic => {
+                self.config.periodic ^= true;
+            }
+            Msg::SetExplain => {
+                self.config.explain ^= true;
+            }
+            Msg::Import(file) => {
+                let callback = self.link.callback(Msg::Loaded);
+                self.task = self.reader.read_file(file, callback).ok();
+                return false;
+            }
+            Msg::Loaded(file) => {
+                self.task = None;
+                match parse_config(&file) {
+                    Ok(config) => {
+                        self.rule_is_valid = config.rule_string.parse::<NtLifeGen>().is_ok();
+                        self.config = config;
+                    }
+                    Err(e) => {
+                        let message = format!("Invalid configuration file {}: {}", file.name, e);
+                        js! { @(no_return)
+                            alert(@{message});
+                        };
+                        return false;
+                    }
+                }
+            }
+            Msg::Export => {
+                let content = toml::to_string(&self.config).unwrap();
+                js! { @(no_return)
+                    const blob = new Blob([@{content}], { type: "application/toml" });
+                    const link = document.createElement("a");
+                    link.href = URL.createObjectURL(blob);
+                    link.download = "rlifesrc.toml";
+                    link.click();
+                    URL.revokeObjectURL(link.href);
+                };
+                return false;
+            }
             Msg::Apply => {
                 self.callback.emit(self.config.clone());
                 return false;
@@ -144,10 +207,50 @@ impl Settings {
                         </abbr>
                     </span>
                 </button>
+                { self.import_export_buttons() }
             </div>
         }
     }
 
+    fn import_export_buttons(&self) -> Html {
+        let onchange = self.link.callback(|e: ChangeData| {
+            if let ChangeData::Files(files) = e {
+                files.iter().next().map_or(Msg::None, Msg::Import)
+            } else {
+                Msg::None
+            }
+        });
+        html! {
+            <>
+                <label class="mui-btn mui-btn--raised" for="import_config">
+                    <i class="fas fa-file-upload"></i>
+                    <span class="mui--hidden-xs">
+                        <abbr title="Import the settings from a TOML or JSON file, \
+                            in the same format as the --config option of the command-line tool. \
+                            Missing fields take their default values.">
+                            { "Import" }
+                        </abbr>
+                    </span>
+                </label>
+                <input id="import_config"
+                    type="file"
+                    accept=".toml,.json"
+                    style="display: none"
+                    onchange=onchange/>
+                <button class="mui-btn mui-btn--raised"
+                    onclick=self.link.callback(|_| Msg::Export)>
+                    <i class="fas fa-file-download"></i>
+                    <span class="mui--hidden-xs">
+                        <abbr title="Export the settings as a TOML file, \
+                            which can be read by the --config option of the command-line tool.">
+                            { "Export" }
+                        </abbr>
+                    </span>
+                </button>
+            </>
+        }
+    }
+
     fn settings(&self) -> Html {
         html! {
             <div id="settings">
@@ -164,6 +267,8 @@ impl Settings {
                 { self.set_choose() }
                 { self.set_front() }
                 { self.set_reduce() }
+                { self.set_periodic() }
+                { self.set_explain() }
             </div>
         }
     }
@@ -388,6 +493,41 @@ impl Settings {
         }
     }
 
+    fn set_periodic(&self) -> Html {
+        html! {
+            <div class="mui-checkbox">
+                <label>
+                    <input id="set_periodic"
+                        type="checkbox"
+                        checked=!self.config.periodic
+                        onclick=self.link.callback(|_| Msg::SetPeriodic)/>
+                    <abbr title="The first generation has no predecessor, \
+                        and the last generation has no successor.\n\
+                        The translation, the transformation and the front are ignored.">
+                        { "Do not return to the first generation" }
+                    </abbr>
+                </label>
+            </div>
+        }
+    }
+
+    fn set_explain(&self) -> Html {
+        html! {
+            <div class="mui-checkbox">
+                <label>
+                    <input id="set_explain"
+                        type="checkbox"
+                        checked=self.config.explain
+                        onclick=self.link.callback(|_| Msg::SetExplain)/>
+                    <abbr title="Click a cell in the mixed format to see why it has its state.\n\
+                        It makes the search slower.">
+                        { "Record why each cell has its state" }
+                    </abbr>
+                </label>
+            </div>
+        }
+    }
+
     fn set_trans(&self) -> Html {
         let onchange = self.link.callback(|e: ChangeData| {
             if let ChangeData::Select(s) = e {
@@ -1,4 +1,4 @@
-use rlifesrc_lib::{Config, Search, Status, WorldSer};
+use rlifesrc_lib::{Config, Search, Status, WorldSer, ALIVE};
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 use yew::{
@@ -8,23 +8,58 @@ use yew::{
 
 const VIEW_FREQ: u64 = 50000;
 
+/// Output formats of the world.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Format {
+    /// A mix of Plaintext and RLE format, with `?` for unknown cells.
+    Mixed,
+    /// The standard RLE format.
+    ///
+    /// Falls back to `Mixed` for partial results.
+    Rle,
+    /// All generations side by side in the standard RLE format.
+    ///
+    /// Falls back to `Mixed` for partial results.
+    Phases,
+    /// Generation 0 in LifeHistory format, marking the stator and the rotor.
+    ///
+    /// Falls back to `Mixed` for partial results.
+    History,
+}
+
 #[derive(Serialize, Deserialize)]
 pub enum Request {
     Start,
     Pause,
     SetWorld(Config),
+    SetFormat(Format),
+    /// Whether to send the heatmap of conflicts with the world.
+    SetHeatmap(bool),
     DisplayGen(isize),
+    /// Explains why the cell at the coordinates has its state.
+    Explain((isize, isize, isize)),
     Store,
     Restore(WorldSer),
 }
 
 #[derive(Serialize, Deserialize)]
 pub enum Response {
-    UpdateWorld((String, usize)),
+    /// The displayed world, its cell count, and whether it is
+    /// the deepest partial result of a failed search.
+    UpdateWorld((String, usize, bool)),
+    /// Number of conflicts found at each cell of the displayed generation,
+    /// row by row, or `None` if they are not recorded.
+    ///
+    /// Only sent when the heatmap is shown. The world is then displayed
+    /// in the mixed format.
+    UpdateHeatmap(Option<Vec<Vec<u64>>>),
     UpdateStatus(Status),
     UpdateConfig(Config),
     InvalidRule,
     Store(WorldSer),
+    /// The chain of deductions that gives the cell its state,
+    /// one deduction per line, or why it cannot be explained.
+    Explain(Result<Vec<String>, String>),
 }
 
 pub enum WorkerMsg {
@@ -34,6 +69,8 @@ pub enum WorkerMsg {
 pub struct Worker {
     status: Status,
     search: Box<dyn Search>,
+    format: Format,
+    heatmap: bool,
     link: AgentLink<Worker>,
     timeout: TimeoutService,
     job: Option<Box<dyn Task>>,
@@ -53,9 +90,44 @@ impl Worker {
     }
 
     fn update_world(&mut self, id: HandlerId, gen: isize) {
-        let world = self.search.rle_gen(gen);
+        let heatmap = self.heatmap && {
+            let map = self.search.conflict_map(gen);
+            let is_some = map.is_some();
+            self.link.respond(id, Response::UpdateHeatmap(map));
+            is_some
+        };
+        if self.status == Status::None {
+            if let Some(pattern) = self.search.deepest_pattern(gen) {
+                let world = self.search.rle_deepest_gen(gen).unwrap();
+                let count = pattern
+                    .known_cells()
+                    .filter(|&(_, state)| state == ALIVE)
+                    .count();
+                self.link
+                    .respond(id, Response::UpdateWorld((world, count, true)));
+                self.update_status(id);
+                return;
+            }
+        }
+        let world = match self.format {
+            Format::Mixed => self.search.rle_gen(gen),
+            _ if heatmap => self.search.rle_gen(gen),
+            Format::Rle => self
+                .search
+                .rle_std_gen(gen)
+                .unwrap_or_else(|_| self.search.rle_gen(gen)),
+            Format::Phases => self
+                .search
+                .rle_std_phases()
+                .unwrap_or_else(|_| self.search.rle_gen(gen)),
+            Format::History => self
+                .search
+                .rle_history()
+                .unwrap_or_else(|_| self.search.rle_gen(gen)),
+        };
         let count = self.search.cell_count_gen(gen);
-        self.link.respond(id, Response::UpdateWorld((world, count)));
+        self.link
+            .respond(id, Response::UpdateWorld((world, count, false)));
         self.update_status(id);
     }
 
@@ -83,6 +155,8 @@ impl Agent for Worker {
         Worker {
             status: Status::Initial,
             search,
+            format: Format::Mixed,
+            heatmap: false,
             link,
             timeout,
             job: None,
@@ -124,9 +198,23 @@ impl Agent for Worker {
                     self.link.respond(id, Response::InvalidRule);
                 }
             }
+            Request::SetFormat(format) => {
+                self.format = format;
+            }
+            Request::SetHeatmap(heatmap) => {
+                self.heatmap = heatmap;
+            }
             Request::DisplayGen(gen) => {
                 self.update_world(id, gen);
             }
+            Request::Explain(coord) => {
+                let explanation = self
+                    .search
+                    .explain(coord)
+                    .map(|chain| chain.iter().map(ToString::to_string).collect())
+                    .map_err(|e| e.to_string());
+                self.link.respond(id, Response::Explain(explanation));
+            }
             Request::Store => {
                 let world_ser = self.search.ser();
                 self.link.respond(id, Response::Store(world_ser));
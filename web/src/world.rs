@@ -1,22 +1,40 @@
 use stdweb::web::{self, event::IEvent};
 use yew::{
-    events::DoubleClickEvent, html, Component, ComponentLink, Html, NodeRef, Properties,
+    events::DoubleClickEvent, html, Callback, Component, ComponentLink, Html, NodeRef, Properties,
     ShouldRender,
 };
 
 pub struct World {
     link: ComponentLink<Self>,
-    world: String,
+    props: Props,
     node_ref: NodeRef,
 }
 
-#[derive(Clone, Properties)]
+#[derive(Clone, PartialEq, Properties)]
 pub struct Props {
     pub world: String,
+    /// Whether the cells can be clicked.
+    ///
+    /// Only set when the world is displayed in the mixed format,
+    /// where the `x`-th character of the `y + 1`-th line is the cell `(x, y)`.
+    #[prop_or_default]
+    pub clickable: bool,
+    /// The clicked cell, which is highlighted.
+    #[prop_or_default]
+    pub selected: Option<(isize, isize)>,
+    /// Number of conflicts found at each cell, row by row.
+    ///
+    /// If set, the world is in the mixed format, and each cell is colored
+    /// by its number of conflicts, from blue (few) to red (many).
+    #[prop_or_default]
+    pub heatmap: Option<Vec<Vec<u64>>>,
+    #[prop_or_default]
+    pub onselect: Callback<(isize, isize)>,
 }
 
 pub enum Msg {
     Select,
+    Click((isize, isize)),
 }
 
 impl Component for World {
@@ -26,7 +44,7 @@ impl Component for World {
     fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
         World {
             link,
-            world: props.world,
+            props,
             node_ref: NodeRef::default(),
         }
     }
@@ -41,12 +59,16 @@ impl Component for World {
                 }
                 false
             }
+            Msg::Click(coord) => {
+                self.props.onselect.emit(coord);
+                false
+            }
         }
     }
 
     fn change(&mut self, props: Self::Properties) -> ShouldRender {
-        self.world != props.world && {
-            self.world = props.world;
+        self.props != props && {
+            self.props = props;
             true
         }
     }
@@ -60,8 +82,81 @@ impl Component for World {
             <pre id="world"
                 ref=self.node_ref.clone()
                 ondoubleclick=ondoubleclick>
-                { &self.world }
+                {
+                    if self.props.clickable || self.props.heatmap.is_some() {
+                        self.cells()
+                    } else {
+                        html! { &self.props.world }
+                    }
+                }
             </pre>
         }
     }
 }
+
+impl World {
+    /// The world with each cell in a `span`, which can be clicked
+    /// and colored.
+    ///
+    /// The first line is the header.
+    fn cells(&self) -> Html {
+        let mut lines = self.props.world.lines();
+        let header = lines.next().unwrap_or_default();
+        let max = self
+            .props
+            .heatmap
+            .iter()
+            .flatten()
+            .flatten()
+            .copied()
+            .max()
+            .unwrap_or(0);
+        html! {
+            <>
+                { header }
+                { "\n" }
+                {
+                    for lines.enumerate().map(|(y, line)| html! {
+                        <>
+                            { for line.chars().enumerate().map(|(x, c)| self.cell(x, y, c, max)) }
+                            { "\n" }
+                        </>
+                    })
+                }
+            </>
+        }
+    }
+
+    /// A cell, colored by its number of conflicts relative to `max`.
+    fn cell(&self, x: usize, y: usize, c: char, max: u64) -> Html {
+        const LEVELS: u64 = 4;
+
+        let coord = (x as isize, y as isize);
+        let mut class = Vec::new();
+        if self.props.clickable {
+            class.push(String::from("cell"));
+        }
+        if self.props.selected == Some(coord) {
+            class.push(String::from("selected"));
+        }
+        if let Some(map) = &self.props.heatmap {
+            let count = map.get(y).and_then(|row| row.get(x)).copied().unwrap_or(0);
+            if count > 0 {
+                class.push(format!("heat-{}", (count - 1) * LEVELS / max));
+            }
+        }
+        if self.props.clickable {
+            html! {
+                <span class=class onclick=self.link.callback(move |_| Msg::Click(coord))>
+                    { c }
+                </span>
+            }
+        } else {
+            html! {
+                <span class=class>
+                    { c }
+                </span>
+            }
+        }
+    }
+}